@@ -0,0 +1,29 @@
+//! Generates the C header of the ffi feature.
+
+fn main() {
+    #[cfg(feature = "ffi")]
+    generate_c_header();
+}
+
+/// Write the declarations of src/ffi.rs to game_of_life.h in OUT_DIR, and pass its path to the crate as
+/// GAME_OF_LIFE_HEADER, so the tests can check include/game_of_life.h is up to date.
+#[cfg(feature = "ffi")]
+fn generate_c_header() {
+    let crate_dir =
+        std::env::var("CARGO_MANIFEST_DIR").expect("cargo should set CARGO_MANIFEST_DIR");
+    let out_dir = std::env::var("OUT_DIR").expect("cargo should set OUT_DIR");
+    let header = format!("{out_dir}/game_of_life.h");
+
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rustc-env=GAME_OF_LIFE_HEADER={header}");
+
+    // Only ffi.rs, since the other bindings declare types of the same names.
+    cbindgen::Builder::new()
+        .with_src(format!("{crate_dir}/src/ffi.rs"))
+        .with_language(cbindgen::Language::C)
+        .with_include_guard("GAME_OF_LIFE_H")
+        .with_header("/* Generated from src/ffi.rs by build.rs.  Do not edit. */")
+        .generate()
+        .expect("should be able to generate the C header")
+        .write_to_file(header);
+}
@@ -0,0 +1,109 @@
+/* Generated from src/ffi.rs by build.rs.  Do not edit. */
+
+#ifndef GAME_OF_LIFE_H
+#define GAME_OF_LIFE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * An opaque universe of cells, and the bytes its cells were last read into.
+ */
+typedef struct Universe Universe;
+
+/**
+ * Create a new universe with the standard height and width.
+ */
+struct Universe *universe_new(void);
+
+/**
+ * Release a universe.  Does nothing if universe is null.
+ *
+ * # Safety
+ * universe must be null or have been returned by universe_new(), and not already released.
+ */
+void universe_free(struct Universe *universe);
+
+/**
+ * Return the number of cells in each row.
+ *
+ * # Safety
+ * universe must have been returned by universe_new(), and not released.
+ */
+uint32_t universe_width(const struct Universe *universe);
+
+/**
+ * Return the number of cells in each column.
+ *
+ * # Safety
+ * universe must have been returned by universe_new(), and not released.
+ */
+uint32_t universe_height(const struct Universe *universe);
+
+/**
+ * Return the number of ticks since the universe was last cleared or loaded.
+ *
+ * # Safety
+ * universe must have been returned by universe_new(), and not released.
+ */
+uint64_t universe_generation(const struct Universe *universe);
+
+/**
+ * Return a pointer to the width * height cells of the universe, as they are now.
+ *
+ * The pointer is valid until this is next called or the universe is released.
+ *
+ * # Safety
+ * universe must have been returned by universe_new(), and not released.
+ */
+const uint8_t *universe_cells_ptr(const struct Universe *universe);
+
+/**
+ * Advance the universe one generation.
+ *
+ * # Safety
+ * universe must have been returned by universe_new(), and not released.
+ */
+void universe_tick(struct Universe *universe);
+
+/**
+ * Toggle the state of the cell at row, column.  Cells outside of the universe are ignored.
+ *
+ * # Safety
+ * universe must have been returned by universe_new(), and not released.
+ */
+void universe_toggle_cell(struct Universe *universe, uint32_t row, uint32_t column);
+
+/**
+ * Kill all cells.
+ *
+ * # Safety
+ * universe must have been returned by universe_new(), and not released.
+ */
+void universe_clear(struct Universe *universe);
+
+/**
+ * Clear the universe and load an RLE pattern into its top left corner.
+ *
+ * Returns false, leaving the universe unchanged, if the pattern cannot be read.
+ *
+ * # Safety
+ * universe must have been returned by universe_new(), and not released.  rle must be a nul terminated string.
+ */
+bool universe_load_rle(struct Universe *universe,
+                       const char *rle);
+
+/**
+ * Set the rule the universe advances by, in B/S notation such as "B3/S23".
+ *
+ * Returns false, leaving the rule unchanged, if the rule cannot be read.
+ *
+ * # Safety
+ * universe must have been returned by universe_new(), and not released.  rule must be a nul terminated string.
+ */
+bool universe_set_rule(struct Universe *universe,
+                       const char *rule);
+
+#endif /* GAME_OF_LIFE_H */
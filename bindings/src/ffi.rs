@@ -0,0 +1,225 @@
+//! C API of the universe, for embedding the engine in non-Rust hosts.
+//!
+//! The header `include/game_of_life.h` declares these functions.  Building with the `ffi` feature generates
+//! it again in the build directory, and the tests check the two are the same.  A universe is created with
+//! universe_new(), and must be released with universe_free().  Cells are read row by row, one byte per cell:
+//! 0 if dead, 1 if alive.
+
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CStr},
+};
+
+use game_of_life_core::{rle::read_rle, universe};
+
+/// An opaque universe of cells, and the bytes its cells were last read into.
+pub struct Universe(universe::Universe, RefCell<Vec<u8>>);
+
+/// Create a new universe with the standard height and width.
+#[no_mangle]
+pub extern "C" fn universe_new() -> *mut Universe {
+    Box::into_raw(Box::new(Universe(
+        universe::Universe::new(),
+        RefCell::default(),
+    )))
+}
+
+/// Release a universe.  Does nothing if universe is null.
+///
+/// # Safety
+/// universe must be null or have been returned by universe_new(), and not already released.
+#[no_mangle]
+pub unsafe extern "C" fn universe_free(universe: *mut Universe) {
+    if !universe.is_null() {
+        drop(Box::from_raw(universe));
+    }
+}
+
+/// Return the number of cells in each row.
+///
+/// # Safety
+/// universe must have been returned by universe_new(), and not released.
+#[no_mangle]
+pub unsafe extern "C" fn universe_width(universe: *const Universe) -> u32 {
+    (*universe).0.width()
+}
+
+/// Return the number of cells in each column.
+///
+/// # Safety
+/// universe must have been returned by universe_new(), and not released.
+#[no_mangle]
+pub unsafe extern "C" fn universe_height(universe: *const Universe) -> u32 {
+    (*universe).0.height()
+}
+
+/// Return the number of ticks since the universe was last cleared or loaded.
+///
+/// # Safety
+/// universe must have been returned by universe_new(), and not released.
+#[no_mangle]
+pub unsafe extern "C" fn universe_generation(universe: *const Universe) -> u64 {
+    (*universe).0.generation()
+}
+
+/// Return a pointer to the width * height cells of the universe, as they are now.
+///
+/// The pointer is valid until this is next called or the universe is released.
+///
+/// # Safety
+/// universe must have been returned by universe_new(), and not released.
+#[no_mangle]
+pub unsafe extern "C" fn universe_cells_ptr(universe: *const Universe) -> *const u8 {
+    let mut bytes = (*universe).1.borrow_mut();
+    bytes.clear();
+    // Cells are repr(u8), so they can be read as bytes.
+    bytes.extend((*universe).0.cells().map(|cell| cell as u8));
+    bytes.as_ptr()
+}
+
+/// Advance the universe one generation.
+///
+/// # Safety
+/// universe must have been returned by universe_new(), and not released.
+#[no_mangle]
+pub unsafe extern "C" fn universe_tick(universe: *mut Universe) {
+    (*universe).0.tick();
+}
+
+/// Toggle the state of the cell at row, column.  Cells outside of the universe are ignored.
+///
+/// # Safety
+/// universe must have been returned by universe_new(), and not released.
+#[no_mangle]
+pub unsafe extern "C" fn universe_toggle_cell(universe: *mut Universe, row: u32, column: u32) {
+    let universe = &mut (*universe).0;
+
+    if row < universe.height() && column < universe.width() {
+        universe.toggle_cell(row, column);
+    }
+}
+
+/// Kill all cells.
+///
+/// # Safety
+/// universe must have been returned by universe_new(), and not released.
+#[no_mangle]
+pub unsafe extern "C" fn universe_clear(universe: *mut Universe) {
+    (*universe).0.clear();
+}
+
+/// Clear the universe and load an RLE pattern into its top left corner.
+///
+/// Returns false, leaving the universe unchanged, if the pattern cannot be read.
+///
+/// # Safety
+/// universe must have been returned by universe_new(), and not released.  rle must be a nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn universe_load_rle(universe: *mut Universe, rle: *const c_char) -> bool {
+    let universe = &mut (*universe).0;
+    let size = (universe.height(), universe.width());
+    let Ok(cells) = CStr::from_ptr(rle)
+        .to_str()
+        .map_err(|error| error.to_string())
+        .and_then(|rle| read_rle(rle, size))
+    else {
+        return false;
+    };
+
+    universe.clear();
+    universe.set_cells(&cells);
+    true
+}
+
+/// Set the rule the universe advances by, in B/S notation such as "B3/S23".
+///
+/// Returns false, leaving the rule unchanged, if the rule cannot be read.
+///
+/// # Safety
+/// universe must have been returned by universe_new(), and not released.  rule must be a nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn universe_set_rule(universe: *mut Universe, rule: *const c_char) -> bool {
+    match CStr::from_ptr(rule).to_str().map(str::parse) {
+        Ok(Ok(rule)) => {
+            (*universe).0.set_rule(rule);
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return the cells of the universe, as C reads them through universe_cells_ptr().
+    unsafe fn cells(universe: *const Universe) -> Vec<u8> {
+        let count = universe_width(universe) * universe_height(universe);
+        std::slice::from_raw_parts(universe_cells_ptr(universe), count as usize).to_vec()
+    }
+
+    #[test]
+    fn universes_are_created_ticked_and_freed() {
+        unsafe {
+            let universe = universe_new();
+            assert!(universe_load_rle(universe, c"bo$2bo$3o!".as_ptr()));
+            for _ in 0..4 {
+                universe_tick(universe);
+            }
+
+            assert_eq!(universe_generation(universe), 4);
+            assert_eq!(cells(universe).iter().filter(|cell| **cell == 1).count(), 5);
+            universe_free(universe);
+            universe_free(std::ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn cells_are_read_row_by_row() {
+        unsafe {
+            let universe = universe_new();
+            let width = universe_width(universe) as usize;
+            universe_toggle_cell(universe, 0, 1);
+            universe_toggle_cell(universe, 2, 0);
+            universe_toggle_cell(universe, universe_height(universe), 0);
+
+            let cells = cells(universe);
+            let alive: Vec<usize> = (0..cells.len())
+                .filter(|index| cells[*index] == 1)
+                .collect();
+            assert_eq!(alive, vec![1, 2 * width]);
+            assert!(cells.iter().all(|cell| *cell <= 1));
+            universe_free(universe);
+        }
+    }
+
+    #[test]
+    fn rules_and_patterns_that_cant_be_read_are_refused() {
+        unsafe {
+            let universe = universe_new();
+            universe_toggle_cell(universe, 3, 4);
+
+            assert!(!universe_set_rule(universe, c"B3S23".as_ptr()));
+            assert!(!universe_set_rule(universe, c"B9/S23".as_ptr()));
+            assert!(!universe_load_rle(universe, c"3o%!".as_ptr()));
+            assert_eq!((*universe).0.rule().to_string(), "B3/S23");
+            assert_eq!(cells(universe).iter().filter(|cell| **cell == 1).count(), 1);
+
+            assert!(universe_set_rule(universe, c"B36/S23".as_ptr()));
+            assert_eq!((*universe).0.rule().to_string(), "B36/S23");
+            universe_free(universe);
+        }
+    }
+
+    #[test]
+    fn header_is_up_to_date() {
+        let generated = include_str!(env!("GAME_OF_LIFE_HEADER"));
+        let header = include_str!("../include/game_of_life.h");
+
+        assert!(
+            header == generated,
+            "include/game_of_life.h is out of date: copy {} over it",
+            env!("GAME_OF_LIFE_HEADER")
+        );
+    }
+}
@@ -0,0 +1,15 @@
+//! Bindings of the game of life engine (the game-of-life-core crate) for other languages.
+//!
+//! Each language is behind a feature:
+//! * `wasm`: wasm-bindgen exports for JavaScript.
+//! * `python`: pyo3 bindings for Python, built as a module for the interpreter with `extension-module`.
+//! * `ffi`: a C API, with a generated header.
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "python")]
+pub mod python;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
@@ -0,0 +1,232 @@
+//! Python bindings (pyo3) of the universe, rules and pattern reading, for scripting experiments with the same engine
+//! the app runs.
+//!
+//! Build with `maturin develop -m bindings/Cargo.toml --features extension-module`, then:
+//! ```python
+//! from game_of_life_bindings import Rule, Universe, read_rle
+//!
+//! universe = Universe()
+//! universe.rule = Rule("B36/S23")
+//! universe.random_with_seed(42)
+//! universe.tick(100)
+//! print(universe.generation, universe.population)
+//! ```
+
+use game_of_life_core::{life106, rle, rule, universe};
+use pyo3::{
+    exceptions::{PyIndexError, PyValueError},
+    prelude::*,
+};
+
+/// A life-like rule in B/S notation, such as "B3/S23".
+#[pyclass(name = "Rule")]
+#[derive(Clone)]
+pub struct PyRule(rule::Rule);
+
+#[pymethods]
+impl PyRule {
+    #[new]
+    #[pyo3(signature = (rule = "B3/S23"))]
+    fn new(rule: &str) -> PyResult<Self> {
+        rule.parse().map(PyRule).map_err(PyValueError::new_err)
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Rule('{}')", self.0)
+    }
+}
+
+/// A universe of cells.  Cells are given as (row, column) tuples.
+#[pyclass(name = "Universe")]
+pub struct PyUniverse(universe::Universe);
+
+#[pymethods]
+impl PyUniverse {
+    /// Create a new universe with the standard height and width.
+    #[new]
+    fn new() -> Self {
+        PyUniverse(universe::Universe::new())
+    }
+
+    /// Read a universe written as rows of `.` (dead) and `O` (alive) cells.
+    #[staticmethod]
+    fn from_text(text: &str) -> PyResult<Self> {
+        text.parse().map(PyUniverse).map_err(PyValueError::new_err)
+    }
+
+    #[getter]
+    fn width(&self) -> u32 {
+        self.0.width()
+    }
+
+    #[getter]
+    fn height(&self) -> u32 {
+        self.0.height()
+    }
+
+    #[getter]
+    fn generation(&self) -> u64 {
+        self.0.generation()
+    }
+
+    #[getter]
+    fn population(&self) -> usize {
+        self.0.population()
+    }
+
+    #[getter]
+    fn rule(&self) -> PyRule {
+        PyRule(self.0.rule())
+    }
+
+    #[setter]
+    fn set_rule(&mut self, rule: PyRule) {
+        self.0.set_rule(rule.0);
+    }
+
+    /// Advance the universe the given number of generations.  Other Python threads run in the meantime.
+    #[pyo3(signature = (generations = 1))]
+    fn tick(&mut self, py: Python<'_>, generations: u64) {
+        let universe = &mut self.0;
+        py.allow_threads(|| {
+            for _ in 0..generations {
+                universe.tick();
+            }
+        });
+    }
+
+    /// Randomly set all cells, the same way for a seed and density (the percent of cells alive on average)
+    /// as the app does.
+    #[pyo3(signature = (seed, density = universe::DEFAULT_DENSITY))]
+    fn random_with_seed(&mut self, seed: u64, density: u8) {
+        self.0.random_with_seed_and_density(seed, density.min(100));
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Set the given cells alive.  Cells outside of the universe are ignored.
+    fn set_cells(&mut self, cells: Vec<(u32, u32)>) {
+        self.0.set_cells(&cells);
+    }
+
+    /// Set the cells of a pattern alive, with its top left corner at (row, col).  Cells past the edges wrap
+    /// around, or are left out if the edges are dead.
+    fn stamp(&mut self, pattern: Vec<(u32, u32)>, row: u32, col: u32) {
+        self.0.stamp(&pattern, row, col);
+    }
+
+    /// Return the universe turned a quarter turn clockwise, swapping its width and height.
+    fn rotate90(&self) -> Self {
+        PyUniverse(self.0.rotate90())
+    }
+
+    /// Return the universe turned a half turn.
+    fn rotate180(&self) -> Self {
+        PyUniverse(self.0.rotate180())
+    }
+
+    /// Return the universe mirrored left to right.
+    fn flip_h(&self) -> Self {
+        PyUniverse(self.0.flip_h())
+    }
+
+    /// Return the universe mirrored top to bottom.
+    fn flip_v(&self) -> Self {
+        PyUniverse(self.0.flip_v())
+    }
+
+    fn toggle_cell(&mut self, row: u32, column: u32) -> PyResult<()> {
+        if row >= self.0.height() || column >= self.0.width() {
+            return Err(PyIndexError::new_err("cell is outside of the universe"));
+        }
+        self.0.toggle_cell(row, column);
+        Ok(())
+    }
+
+    /// Return the cells that are alive.
+    fn living_cells(&self) -> Vec<(u32, u32)> {
+        self.0
+            .get_living_cells()
+            .into_iter()
+            .map(|(x, y)| (y as u32, x as u32))
+            .collect()
+    }
+
+    /// Return the living cells in the Life 1.06 format, one `x y` line per cell.
+    fn to_life106(&self) -> String {
+        life106::write_life106(&self.0)
+    }
+
+    /// Return a hash of the cells that is the same on every platform and run.
+    fn board_hash(&self) -> u64 {
+        self.0.board_hash()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Return the living cells of an RLE pattern, relative to its top left corner.  Cells past the height and
+/// width (those of a standard universe unless given) are cut off.
+#[pyfunction]
+#[pyo3(signature = (text, height = universe::CELLS_PER_COL, width = universe::CELLS_PER_ROW))]
+fn read_rle(text: &str, height: u32, width: u32) -> PyResult<Vec<(u32, u32)>> {
+    rle::read_rle(text, (height, width)).map_err(PyValueError::new_err)
+}
+
+/// Return the living cells of a Life 1.06 pattern.  Patterns with negative coordinates are moved until
+/// their top left cell is at (0, 0).
+#[pyfunction]
+fn read_life106(text: &str) -> PyResult<Vec<(u32, u32)>> {
+    life106::read_life106(text).map_err(PyValueError::new_err)
+}
+
+#[pymodule]
+fn game_of_life_bindings(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyRule>()?;
+    module.add_class::<PyUniverse>()?;
+    module.add_function(wrap_pyfunction!(read_rle, module)?)?;
+    module.add_function(wrap_pyfunction!(read_life106, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_that_cant_be_read_raise_value_errors() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            for rule in ["B3S23", "X3/S23", "B3/Q23", "B39/S23"] {
+                let Err(error) = PyRule::new(rule) else {
+                    panic!("{rule:?} should not be read as a rule");
+                };
+                assert!(error.is_instance_of::<PyValueError>(py), "{rule:?}");
+            }
+            assert_eq!(PyRule::new("b36/s23").unwrap().__str__(), "B36/S23");
+        });
+    }
+
+    #[test]
+    fn ticks_advance_the_universe_without_the_gil() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let mut universe = PyUniverse::new();
+            universe.set_cells(vec![(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+            universe.tick(py, 4);
+
+            let mut cells = universe.living_cells();
+            cells.sort_unstable();
+            assert_eq!(universe.generation(), 4);
+            assert_eq!(cells, vec![(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)]);
+        });
+    }
+}
@@ -0,0 +1,87 @@
+//! wasm-bindgen exports of the universe for JavaScript, mirroring the interface of the rust wasm tutorial:
+//! https://rustwasm.github.io/docs/book/game-of-life/implementing.html
+//!
+//! Build with `wasm-pack build bindings -- --features wasm`, then read the cells straight from wasm memory:
+//! ```js
+//! import { Universe } from "game-of-life-bindings";
+//! import { memory } from "game-of-life-bindings/game_of_life_bindings_bg";
+//!
+//! const universe = Universe.new();
+//! universe.tick();
+//! const cells = new Uint8Array(memory.buffer, universe.cells(), universe.width() * universe.height());
+//! ```
+
+use std::cell::RefCell;
+
+use game_of_life_core::universe;
+use wasm_bindgen::prelude::*;
+
+/// A universe of cells.
+///
+/// The cells are read row by row, one byte per cell: 0 if dead, 1 if alive.  The universe packs its cells
+/// a bit each, so they are copied to the bytes each time they are read.
+#[wasm_bindgen]
+pub struct Universe(universe::Universe, RefCell<Vec<u8>>);
+
+impl Default for Universe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Create a new universe with the standard height and width.
+    pub fn new() -> Universe {
+        Universe(universe::Universe::new(), RefCell::default())
+    }
+
+    pub fn width(&self) -> u32 {
+        self.0.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.0.height()
+    }
+
+    /// Set the width of the universe, resetting all cells to dead and taking away ants outside of it.
+    pub fn set_width(&mut self, width: u32) {
+        self.0.set_width(width);
+    }
+
+    /// Set the height of the universe, resetting all cells to dead and taking away ants outside of it.
+    pub fn set_height(&mut self, height: u32) {
+        self.0.set_height(height);
+    }
+
+    /// Return a pointer to the cells in wasm memory, as they are now.
+    pub fn cells(&self) -> *const u8 {
+        let mut bytes = self.1.borrow_mut();
+        bytes.clear();
+        // Cells are repr(u8), so they can be read as bytes.
+        bytes.extend(self.0.cells().map(|cell| cell as u8));
+        bytes.as_ptr()
+    }
+
+    /// Return the number of ticks since the universe was last cleared or randomized.
+    pub fn generation(&self) -> u64 {
+        self.0.generation()
+    }
+
+    /// Advance the universe one generation.
+    pub fn tick(&mut self) {
+        self.0.tick();
+    }
+
+    /// Toggle the state of the cell at row, column.  Cells outside of the universe are ignored.
+    pub fn toggle_cell(&mut self, row: u32, column: u32) {
+        if row < self.0.height() && column < self.0.width() {
+            self.0.toggle_cell(row, column);
+        }
+    }
+
+    /// Return the universe as text, with `.` for dead cells and `O` for living ones.
+    pub fn render(&self) -> String {
+        self.0.to_string()
+    }
+}
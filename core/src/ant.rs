@@ -0,0 +1,74 @@
+//! Langton's Ant: ants that walk the universe, turning right on dead cells and left on living ones, and
+//! toggling each cell they leave.
+//!
+//! After about ten thousand steps of seemingly random walking, a single ant starts building a "highway"
+//! that leads off forever in one direction.  See https://conwaylife.com/wiki/Langton%27s_ant
+
+/// The way an ant is facing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Heading {
+    #[default]
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Heading {
+    /// Return the heading after a quarter turn clockwise.
+    pub fn right(self) -> Heading {
+        match self {
+            Heading::Up => Heading::Right,
+            Heading::Right => Heading::Down,
+            Heading::Down => Heading::Left,
+            Heading::Left => Heading::Up,
+        }
+    }
+
+    /// Return the heading after a quarter turn counterclockwise.
+    pub fn left(self) -> Heading {
+        self.right().right().right()
+    }
+
+    /// Return the (row, col) change of a step in this heading.
+    pub fn delta(self) -> (i64, i64) {
+        match self {
+            Heading::Up => (-1, 0),
+            Heading::Right => (0, 1),
+            Heading::Down => (1, 0),
+            Heading::Left => (0, -1),
+        }
+    }
+
+    /// Return the clockwise angle of the heading from up, in degrees.
+    pub fn degrees(self) -> u32 {
+        match self {
+            Heading::Up => 0,
+            Heading::Right => 90,
+            Heading::Down => 180,
+            Heading::Left => 270,
+        }
+    }
+}
+
+/// An ant on the cell at row, col of a universe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Ant {
+    pub row: u32,
+    pub col: u32,
+    pub heading: Heading,
+}
+
+impl Ant {
+    pub fn new(row: u32, col: u32, heading: Heading) -> Ant {
+        Ant { row, col, heading }
+    }
+
+    /// Turn the ant for the cell it is on: right if the cell is dead and left if it is alive.
+    pub fn turn(&mut self, alive: bool) {
+        self.heading = match alive {
+            false => self.heading.right(),
+            true => self.heading.left(),
+        };
+    }
+}
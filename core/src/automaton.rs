@@ -0,0 +1,164 @@
+//! Cellular automata other than the life-like rules: Langton's Ant (see the ant module), and automata whose
+//! cells have more than two states.
+//!
+//! The cells of these automata are numbered states, with 0 for an empty (or dead) cell.  Each generation,
+//! a cell's next state depends on its state and how many of its eight neighbors are in one particular
+//! state, such as the firing cells of Brian's Brain or the electron heads of Wireworld.
+
+use alloc::{format, string::String};
+use core::{fmt, str::FromStr};
+
+use crate::ltl::LtlRule;
+
+/// The prefix of a Larger than Life automaton written by Display, before its rule.
+const LTL_PREFIX: &str = "ltl:";
+
+/// How the cells of a universe advance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Automaton {
+    /// Two state cells advanced by a life-like rule, such as Conway's B3/S23.
+    #[default]
+    Life,
+    /// Ready (0), firing (1) and refractory (2) cells: a ready cell with exactly two firing neighbors fires,
+    /// a firing cell becomes refractory, and a refractory cell becomes ready again.
+    BriansBrain,
+    /// Empty (0), conductor (1), electron head (2) and electron tail (3) cells, for simulating digital
+    /// circuits: a conductor with one or two electron head neighbors becomes an electron head, a head becomes a
+    /// tail, and a tail becomes a conductor again.  Empty cells stay empty.
+    Wireworld,
+    /// Ants that walk two state cells: an ant turns right on a dead cell and left on a living one, toggles
+    /// the cell, and steps forward (see the ant module).
+    LangtonsAnt,
+    /// Two state cells advanced by a Larger than Life rule, whose neighborhood reaches further than the eight
+    /// cells around each cell.
+    LargerThanLife(LtlRule),
+    /// Cells in one of four colors (1 to 4), advanced by a life-like rule.  A surviving cell keeps its color,
+    /// and a cell born takes the color most of its parents have, or if no two parents have the same color,
+    /// the first color none of them has: with three parents of different colors, the fourth color.
+    QuadLife,
+}
+
+impl Automaton {
+    /// Each automaton, with Bosco's Rule for Larger than Life.
+    pub const ALL: [Automaton; 6] = [
+        Automaton::Life,
+        Automaton::QuadLife,
+        Automaton::BriansBrain,
+        Automaton::Wireworld,
+        Automaton::LangtonsAnt,
+        Automaton::LargerThanLife(LtlRule::BOSCO),
+    ];
+
+    /// Return the name of the automaton, as shown in the app.
+    pub fn label(self) -> &'static str {
+        match self {
+            Automaton::Life => "Life",
+            Automaton::BriansBrain => "Brian's Brain",
+            Automaton::Wireworld => "Wireworld",
+            Automaton::LangtonsAnt => "Langton's Ant",
+            Automaton::LargerThanLife(_) => "Larger than Life",
+            Automaton::QuadLife => "QuadLife",
+        }
+    }
+
+    /// Return the names of the states other than the empty state, in order from state 1.
+    pub fn state_names(self) -> &'static [&'static str] {
+        match self {
+            Automaton::Life | Automaton::LangtonsAnt | Automaton::LargerThanLife(_) => &["Alive"],
+            Automaton::BriansBrain => &["Firing", "Refractory"],
+            Automaton::Wireworld => &["Conductor", "Electron head", "Electron tail"],
+            Automaton::QuadLife => &["Color 1", "Color 2", "Color 3", "Color 4"],
+        }
+    }
+
+    /// Return the number of states a cell can be in, including the empty state.
+    pub fn states(self) -> u8 {
+        match self {
+            Automaton::Life | Automaton::LangtonsAnt | Automaton::LargerThanLife(_) => 2,
+            Automaton::BriansBrain => 3,
+            Automaton::Wireworld => 4,
+            Automaton::QuadLife => 5,
+        }
+    }
+
+    /// True if cells have more than two states, so the universe keeps the state of each cell.
+    pub fn is_multi_state(self) -> bool {
+        self.states() > 2
+    }
+
+    /// True if the cells advance by the life-like rule of the universe.
+    pub fn uses_rule(self) -> bool {
+        matches!(self, Automaton::Life | Automaton::QuadLife)
+    }
+
+    /// Return the state whose neighbors are counted to find the next state of a cell.
+    pub fn counted_state(self) -> u8 {
+        match self {
+            Automaton::Life
+            | Automaton::BriansBrain
+            | Automaton::LangtonsAnt
+            | Automaton::LargerThanLife(_)
+            | Automaton::QuadLife => 1,
+            Automaton::Wireworld => 2,
+        }
+    }
+
+    /// Return the next state of a cell in the given state, with the given number of neighbors in the
+    /// counted state.
+    ///
+    /// Life-like rules, ants and Larger than Life rules are applied by the universe, so for those automata the
+    /// state is returned unchanged.
+    pub fn next_state(self, state: u8, counted_neighbors: u8) -> u8 {
+        match self {
+            Automaton::Life
+            | Automaton::LangtonsAnt
+            | Automaton::LargerThanLife(_)
+            | Automaton::QuadLife => state,
+            Automaton::BriansBrain => match state {
+                0 if counted_neighbors == 2 => 1,
+                0 => 0,
+                1 => 2,
+                _ => 0,
+            },
+            Automaton::Wireworld => match state {
+                1 if counted_neighbors == 1 || counted_neighbors == 2 => 2,
+                2 => 3,
+                3 => 1,
+                state => state,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Automaton {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Automaton::Life => write!(f, "life"),
+            Automaton::BriansBrain => write!(f, "brians-brain"),
+            Automaton::Wireworld => write!(f, "wireworld"),
+            Automaton::LangtonsAnt => write!(f, "langtons-ant"),
+            Automaton::LargerThanLife(rule) => write!(f, "{LTL_PREFIX}{rule}"),
+            Automaton::QuadLife => write!(f, "quadlife"),
+        }
+    }
+}
+
+impl FromStr for Automaton {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(rule) = s.trim().strip_prefix(LTL_PREFIX) {
+            return rule.parse().map(Automaton::LargerThanLife);
+        }
+        match s.trim() {
+            "life" => Ok(Automaton::Life),
+            "brians-brain" => Ok(Automaton::BriansBrain),
+            "wireworld" => Ok(Automaton::Wireworld),
+            "langtons-ant" => Ok(Automaton::LangtonsAnt),
+            "quadlife" => Ok(Automaton::QuadLife),
+            _ => Err(format!(
+                "{s:?} is not an automaton: expected life, brians-brain, wireworld, langtons-ant, quadlife or ltl:<rule>"
+            )),
+        }
+    }
+}
@@ -0,0 +1,451 @@
+//! Cells packed one bit to a cell, and advancing them 64 cells at a time.
+//!
+//! Each row is packed into whole 64 bit words, so the cells of a row start at a word boundary and the
+//! cells around each cell are in the same or the next word of the rows above and below.  The bits past
+//! the width of the universe in the last word of each row are always 0.
+//!
+//! With the "parallel" feature, large universes are ticked on rayon's thread pool, each thread writing rows
+//! of the next generation while reading the current generation, which none of them change.
+//!
+//! With the "simd" feature, the cells of two words are advanced at once (see the simd module).
+
+use alloc::{vec, vec::Vec};
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+
+use crate::{rule::Rule, universe::Boundary};
+
+const WORD_BITS: u32 = u64::BITS;
+/// The fewest cells ticked on several threads.
+#[cfg(feature = "parallel")]
+const PARALLEL_CELLS: u64 = 256 * 256;
+
+/// A grid of cells, one bit to a cell: 1 for alive, 0 for dead.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct BitGrid {
+    width: u32,
+    height: u32,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+/// Cloning from a grid reuses the words of the grid cloned into.
+impl Clone for BitGrid {
+    fn clone(&self) -> Self {
+        BitGrid {
+            words: self.words.clone(),
+            ..*self
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        (self.width, self.height, self.words_per_row) =
+            (source.width, source.height, source.words_per_row);
+        self.words.clone_from(&source.words);
+    }
+}
+
+impl BitGrid {
+    /// Return a grid of dead cells.
+    pub fn new(width: u32, height: u32) -> BitGrid {
+        let words_per_row = width.div_ceil(WORD_BITS) as usize;
+
+        BitGrid {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height as usize],
+        }
+    }
+
+    /// Return the number of bytes the cells take.
+    pub fn bytes(&self) -> usize {
+        self.words.len() * core::mem::size_of::<u64>()
+    }
+
+    /// True if the grid holds exactly the words for its width and height.
+    pub fn is_sized(&self) -> bool {
+        self.words.len() == self.words_per_row * self.height as usize
+    }
+
+    /// Return the index of the word holding the cell at row, col, and the bit of the cell in the word.
+    fn locate(&self, row: u32, col: u32) -> (usize, u32) {
+        (
+            row as usize * self.words_per_row + (col / WORD_BITS) as usize,
+            col % WORD_BITS,
+        )
+    }
+
+    pub fn get(&self, row: u32, col: u32) -> bool {
+        let (word, bit) = self.locate(row, col);
+        self.words[word] >> bit & 1 == 1
+    }
+
+    pub fn set(&mut self, row: u32, col: u32, alive: bool) {
+        let (word, bit) = self.locate(row, col);
+        if alive {
+            self.words[word] |= 1 << bit;
+        } else {
+            self.words[word] &= !(1 << bit);
+        }
+    }
+
+    pub fn toggle(&mut self, row: u32, col: u32) {
+        let (word, bit) = self.locate(row, col);
+        self.words[word] ^= 1 << bit;
+    }
+
+    /// Toggle the cells that differ between the two grids, which are the size of this one.
+    pub fn toggle_differences(&mut self, first: &BitGrid, second: &BitGrid) {
+        for (word, (first, second)) in self
+            .words
+            .iter_mut()
+            .zip(first.words.iter().zip(&second.words))
+        {
+            *word ^= first ^ second;
+        }
+    }
+
+    /// Return the number of cells alive in this grid that are dead in the previous grid, and the number
+    /// dead in this grid that are alive in the previous grid, which is the size of this one.
+    pub fn births_and_deaths(&self, previous: &BitGrid) -> (usize, usize) {
+        self.words
+            .iter()
+            .zip(&previous.words)
+            .fold((0, 0), |(births, deaths), (word, previous)| {
+                (
+                    births + (word & !previous).count_ones() as usize,
+                    deaths + (previous & !word).count_ones() as usize,
+                )
+            })
+    }
+
+    /// Write each row of the grid with write_row(row, words of the row).  With the "parallel" feature, the
+    /// rows of large grids are written on rayon's thread pool (see tick_threads()).
+    pub fn write_rows(&mut self, write_row: impl Fn(u32, &mut [u64]) + Send + Sync) {
+        if self.words.is_empty() {
+            return;
+        }
+
+        #[cfg(feature = "parallel")]
+        if tick_threads(self.width, self.height) > 1 {
+            use rayon::prelude::*;
+
+            self.words
+                .par_chunks_mut(self.words_per_row)
+                .enumerate()
+                .for_each(|(row, words)| write_row(row as u32, words));
+            return;
+        }
+        for (row, words) in self.words.chunks_mut(self.words_per_row).enumerate() {
+            write_row(row as u32, words);
+        }
+    }
+
+    /// Set every cell dead.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Set every cell dead, resizing the grid to the given width and height if it isn't that size already.
+    pub fn reset(&mut self, width: u32, height: u32) {
+        if self.width != width || self.height != height || !self.is_sized() {
+            *self = BitGrid::new(width, height);
+        } else {
+            self.clear();
+        }
+    }
+
+    /// Return the number of living cells.
+    pub fn count_alive(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Return the (row, col) of every living cell, row by row.
+    pub fn alive(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.set_cells(|index| self.words[index])
+    }
+
+    /// Return the (row, col) of every cell alive in this grid or in the other grid, which is the size of
+    /// this one, row by row.
+    pub fn alive_in_either<'a>(
+        &'a self,
+        other: &'a BitGrid,
+    ) -> impl Iterator<Item = (u32, u32)> + 'a {
+        self.set_cells(|index| self.words[index] | other.words[index])
+    }
+
+    /// Return the (row, col) of every cell that differs between this grid and the other grid, which is the
+    /// size of this one, row by row.
+    pub fn differences<'a>(&'a self, other: &'a BitGrid) -> impl Iterator<Item = (u32, u32)> + 'a {
+        self.set_cells(|index| self.words[index] ^ other.words[index])
+    }
+
+    /// Return the (row, col) of every cell whose bit is set in the words returned for each word index.
+    fn set_cells<'a>(
+        &self,
+        word: impl Fn(usize) -> u64 + 'a,
+    ) -> impl Iterator<Item = (u32, u32)> + 'a {
+        let words_per_row = self.words_per_row;
+
+        (0..self.words.len()).flat_map(move |index| {
+            let row = (index / words_per_row) as u32;
+            let first_col = (index % words_per_row) as u32 * WORD_BITS;
+            SetBits(word(index)).map(move |bit| (row, first_col + bit))
+        })
+    }
+}
+
+/// The cells of one or more words, side by side, advanced together by the same bit operations.
+pub(crate) trait Lanes:
+    Copy
+    + Send
+    + Sync
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+{
+    /// The words held, in order.
+    type Words: Copy + Default + AsRef<[u64]> + AsMut<[u64]>;
+
+    /// Return the lanes with every word set to word.
+    fn splat(word: u64) -> Self;
+
+    fn load(words: Self::Words) -> Self;
+
+    fn store(self) -> Self::Words;
+}
+
+impl Lanes for u64 {
+    type Words = [u64; 1];
+
+    fn splat(word: u64) -> u64 {
+        word
+    }
+
+    fn load([word]: [u64; 1]) -> u64 {
+        word
+    }
+
+    fn store(self) -> [u64; 1] {
+        [self]
+    }
+}
+
+/// The lanes the cells are advanced in: two words at a time with the "simd" feature, or one.
+#[cfg(feature = "simd")]
+type TickLanes = crate::simd::Wide;
+#[cfg(not(feature = "simd"))]
+type TickLanes = u64;
+
+/// Advancing the cells a word (or with the "simd" feature, two words) at a time.
+impl BitGrid {
+    /// Return the words of a row.
+    fn row(&self, row: u32) -> &[u64] {
+        let start = row as usize * self.words_per_row;
+        &self.words[start..start + self.words_per_row]
+    }
+
+    /// Return the bits of the last word of each row that hold cells.
+    fn last_word_mask(&self) -> u64 {
+        match self.width % WORD_BITS {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    /// Write the next generation of the grid to next, which is resized to match.
+    pub fn tick_into(&self, next: &mut BitGrid, rule: &Rule, boundary: Boundary) {
+        self.tick_lanes::<TickLanes>(next, rule, boundary);
+    }
+
+    /// Write the next generation of the grid to next, advancing the cells in lanes of the given type.
+    pub(crate) fn tick_lanes<L: Lanes>(&self, next: &mut BitGrid, rule: &Rule, boundary: Boundary) {
+        if next.width != self.width || next.height != self.height || !next.is_sized() {
+            *next = BitGrid::new(self.width, self.height);
+        }
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+
+        let wraps = boundary == Boundary::Wrap;
+        let dead_row = vec![0; self.words_per_row];
+        let rule = RuleMasks::<L>::new(rule);
+
+        next.write_rows(|row, words| self.tick_row(row, words, &rule, wraps, &dead_row));
+    }
+
+    /// Write the next generation of the row to words.
+    fn tick_row<L: Lanes>(
+        &self,
+        row: u32,
+        words: &mut [u64],
+        rule: &RuleMasks<L>,
+        wraps: bool,
+        dead_row: &[u64],
+    ) {
+        let last_word_mask = self.last_word_mask();
+        let above = match row {
+            0 if !wraps => dead_row,
+            _ => self.row((row + self.height - 1) % self.height),
+        };
+        let below = match row + 1 {
+            next_row if next_row == self.height && !wraps => dead_row,
+            next_row => self.row(next_row % self.height),
+        };
+        let cells = self.row(row);
+
+        let lane_words = L::Words::default().as_ref().len();
+        for (chunk, next_words) in words.chunks_mut(lane_words).enumerate() {
+            // The cells of each word of the chunk, and the cells around them, each gathered into lanes.
+            let mut lanes = [L::Words::default(); 9];
+            for (lane, index) in (chunk * lane_words..).take(next_words.len()).enumerate() {
+                let neighbors = [above, cells, below].map(|row| self.sides(row, index, wraps));
+                let [(above_west, above_east), (west, east), (below_west, below_east)] = neighbors;
+                let words = [
+                    cells[index],
+                    above_west,
+                    above[index],
+                    above_east,
+                    west,
+                    east,
+                    below_west,
+                    below[index],
+                    below_east,
+                ];
+                for (lane_words, word) in lanes.iter_mut().zip(words) {
+                    lane_words.as_mut()[lane] = word;
+                }
+            }
+
+            let [cells, neighbors @ ..] = lanes.map(L::load);
+            let mut count = Count::new();
+            for neighbor in neighbors {
+                count.add(neighbor);
+            }
+
+            let next = rule.apply(cells, &count).store();
+            next_words.copy_from_slice(&next.as_ref()[..next_words.len()]);
+        }
+        if let Some(last) = words.last_mut() {
+            *last &= last_word_mask;
+        }
+    }
+
+    /// Return the (west, east) words of the cells to the left and right of the cells of the word at index
+    /// in the row.
+    fn sides(&self, row: &[u64], index: usize, wraps: bool) -> (u64, u64) {
+        let word = row[index];
+        let last = self.words_per_row - 1;
+        let last_col = (self.width - 1) % WORD_BITS;
+        // The cells past the left and right edges of the row.
+        let (past_left, past_right) = if wraps {
+            (row[last] >> last_col & 1, row[0] & 1)
+        } else {
+            (0, 0)
+        };
+
+        let west = word << 1
+            | match index {
+                0 => past_left,
+                _ => row[index - 1] >> (WORD_BITS - 1),
+            };
+        let east = word >> 1
+            | match index {
+                _ if index == last => past_right << last_col,
+                _ => row[index + 1] << (WORD_BITS - 1),
+            };
+        (west, east)
+    }
+}
+
+/// Return the number of threads a universe of the given size is ticked on.
+///
+/// Universes of fewer than PARALLEL_CELLS cells are ticked on one thread, since handing their rows to the
+/// thread pool would take longer than ticking them.
+#[cfg(feature = "parallel")]
+pub fn tick_threads(width: u32, height: u32) -> usize {
+    if (width as u64 * height as u64) < PARALLEL_CELLS {
+        return 1;
+    }
+    rayon::current_num_threads().min(height as usize)
+}
+
+/// The bits set in a word, from the lowest.
+struct SetBits(u64);
+
+impl Iterator for SetBits {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.0 == 0 {
+            return None;
+        }
+        let bit = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(bit)
+    }
+}
+
+/// The number of living neighbors (0 to 8) of each cell of the lanes, as four bits held in four lanes.
+struct Count<L>([L; 4]);
+
+impl<L: Lanes> Count<L> {
+    fn new() -> Count<L> {
+        Count([L::splat(0); 4])
+    }
+
+    /// Add one to the count of each cell whose bit is set in the lanes.
+    fn add(&mut self, word: L) {
+        let mut carry = word;
+        for bits in self.0.iter_mut() {
+            let next_carry = *bits & carry;
+            *bits = *bits ^ carry;
+            carry = next_carry;
+        }
+    }
+
+    /// Return the lanes with the bits set of the cells whose count is the given number.
+    fn equals(&self, count: u8) -> L {
+        self.0
+            .iter()
+            .enumerate()
+            .fold(L::splat(u64::MAX), |equal, (bit, bits)| {
+                equal & if count >> bit & 1 == 1 { *bits } else { !*bits }
+            })
+    }
+}
+
+/// A rule as lanes: for each neighbor count, all ones if a cell is born (or survives), or all zeros.
+struct RuleMasks<L> {
+    birth: [L; 9],
+    survival: [L; 9],
+}
+
+impl<L: Lanes> RuleMasks<L> {
+    fn new(rule: &Rule) -> RuleMasks<L> {
+        let mask = |alive, count| {
+            L::splat(if rule.next_alive(alive, count) {
+                u64::MAX
+            } else {
+                0
+            })
+        };
+
+        RuleMasks {
+            birth: core::array::from_fn(|count| mask(false, count as u8)),
+            survival: core::array::from_fn(|count| mask(true, count as u8)),
+        }
+    }
+
+    /// Return the next generation of the cells of the lanes, given their counts of living neighbors.
+    fn apply(&self, cells: L, count: &Count<L>) -> L {
+        (0..=8).fold(L::splat(0), |next, neighbors| {
+            let rule = cells & self.survival[neighbors] | !cells & self.birth[neighbors];
+            next | count.equals(neighbors as u8) & rule
+        })
+    }
+}
@@ -0,0 +1,255 @@
+//! Recognizing known objects (blocks, blinkers, gliders...) among the living cells of a universe.
+//!
+//! The living cells are split into objects: groups of cells within two cells of each other.  Each object is
+//! reduced to a canonical form that is the same for every rotation, reflection and position of it, and the
+//! hash of that form is looked up among the phases of the known objects.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use crate::{
+    patterns::{Pattern, BEEHIVE, BLINKER, BLOCK, GLIDER, LIGHTWEIGHT_SPACESHIP},
+    universe::Universe,
+};
+
+/// The objects that are recognized, with their periods.
+const KNOWN_OBJECTS: &[(Pattern, u32)] = &[
+    (BLOCK, 1),
+    (BEEHIVE, 1),
+    (BLINKER, 2),
+    (GLIDER, 4),
+    (LIGHTWEIGHT_SPACESHIP, 4),
+];
+
+/// The number of each kind of object found in a universe.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Census {
+    /// The number of each recognized object, by name.
+    pub objects: BTreeMap<&'static str, usize>,
+    /// The number of objects that were not recognized.
+    pub unrecognized: usize,
+}
+
+/// Recognizes the known objects in any phase, rotation or reflection.
+pub struct Recognizer {
+    /// The name of the object of each canonical hash.
+    known: BTreeMap<u64, &'static str>,
+}
+
+impl Default for Recognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recognizer {
+    /// Create a recognizer of the known objects, learning every phase of each.
+    pub fn new() -> Recognizer {
+        let mut known = BTreeMap::new();
+
+        for (pattern, period) in KNOWN_OBJECTS {
+            // Run each object alone in a small universe to see all of its phases.
+            let mut universe = Universe::with_size(16, 16);
+            universe.set_cells(&pattern.cells_at(6, 6));
+
+            for _ in 0..*period {
+                for object in objects(&universe) {
+                    known.insert(canonical_hash(&object), pattern.name);
+                }
+                universe.tick();
+            }
+        }
+        Recognizer { known }
+    }
+
+    /// Return the name of the object made of the given cells, if it is known.
+    pub fn recognize(&self, cells: &[(u32, u32)]) -> Option<&'static str> {
+        self.known.get(&canonical_hash(cells)).copied()
+    }
+
+    /// Count the known objects in the universe.
+    pub fn census(&self, universe: &Universe) -> Census {
+        let mut census = Census::default();
+
+        for object in objects(universe) {
+            match self.recognize(&object) {
+                Some(name) => *census.objects.entry(name).or_default() += 1,
+                None => census.unrecognized += 1,
+            }
+        }
+        census
+    }
+}
+
+/// Advance the universe until it returns to a state it has been in before, and return the period it then
+/// repeats with.
+///
+/// Gliders that escape the debris travel around the universe until they return to where they started, so
+/// a universe with gliders takes longer to repeat.  Returns None, leaving the universe advanced
+/// max_generations, if it doesn't repeat by then.
+pub fn run_until_stable(universe: &mut Universe, max_generations: u64) -> Option<u64> {
+    let mut seen = BTreeMap::new();
+
+    for _ in 0..=max_generations {
+        let generation = universe.generation();
+        if let Some(first_seen) = seen.insert(universe.zobrist_hash(), generation) {
+            return Some(generation - first_seen);
+        }
+        universe.tick();
+    }
+    None
+}
+
+/// Split the living cells of the universe into objects: groups of (row, col) cells that are each within two
+/// cells of another cell of the group.
+///
+/// Objects are not joined across the edges of the universe.
+pub fn objects(universe: &Universe) -> Vec<Vec<(u32, u32)>> {
+    let (width, height) = (universe.width(), universe.height());
+    let mut seen = vec![false; (width * height) as usize];
+    let mut objects = Vec::new();
+
+    for row in 0..height {
+        for col in 0..width {
+            if seen[(row * width + col) as usize] || !universe.is_alive(row, col) {
+                continue;
+            }
+
+            seen[(row * width + col) as usize] = true;
+            let mut object = vec![(row, col)];
+            let mut next = 0;
+
+            while next < object.len() {
+                let (cell_row, cell_col) = object[next];
+                next += 1;
+
+                for neighbor_row in cell_row.saturating_sub(2)..=(cell_row + 2).min(height - 1) {
+                    for neighbor_col in cell_col.saturating_sub(2)..=(cell_col + 2).min(width - 1) {
+                        let idx = (neighbor_row * width + neighbor_col) as usize;
+
+                        if !seen[idx] && universe.is_alive(neighbor_row, neighbor_col) {
+                            seen[idx] = true;
+                            object.push((neighbor_row, neighbor_col));
+                        }
+                    }
+                }
+            }
+            objects.push(object);
+        }
+    }
+    objects
+}
+
+/// Return a hash of the cells that is the same for every rotation, reflection and position of them.
+pub fn canonical_hash(cells: &[(u32, u32)]) -> u64 {
+    // The canonical form is the smallest of the sorted cells of the eight orientations, moved to the origin.
+    let canonical = (0..8)
+        .map(|orientation| {
+            let mut oriented: Vec<(i64, i64)> = cells
+                .iter()
+                .map(|(row, col)| orient(*row as i64, *col as i64, orientation))
+                .collect();
+            let min_row = oriented.iter().map(|(row, _)| *row).min().unwrap_or(0);
+            let min_col = oriented.iter().map(|(_, col)| *col).min().unwrap_or(0);
+
+            for (row, col) in oriented.iter_mut() {
+                *row -= min_row;
+                *col -= min_col;
+            }
+            oriented.sort_unstable();
+            oriented
+        })
+        .min()
+        .unwrap_or_default();
+
+    // FNV-1a, so the hash is the same on every platform and run.
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for (row, col) in canonical {
+        for byte in (row as u32)
+            .to_le_bytes()
+            .into_iter()
+            .chain((col as u32).to_le_bytes())
+        {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// Return the cell rotated and/or reflected into one of the eight orientations of a square.
+fn orient(row: i64, col: i64, orientation: u8) -> (i64, i64) {
+    match orientation {
+        0 => (row, col),
+        1 => (row, -col),
+        2 => (-row, col),
+        3 => (-row, -col),
+        4 => (col, row),
+        5 => (col, -row),
+        6 => (-col, row),
+        _ => (-col, -row),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::Transform;
+
+    /// Return a universe holding two blocks, three blinkers, a glider and a lone cell, apart from each other.
+    fn known_soup() -> Universe {
+        let mut universe = Universe::with_size(32, 32);
+        universe.set_cells(&BLOCK.cells_at(2, 2));
+        universe.set_cells(&BLOCK.cells_at(20, 26));
+        universe.set_cells(&BLINKER.cells_at(2, 12));
+        universe.set_cells(&BLINKER.cells_at(12, 24));
+        // A blinker in its other phase, standing up.
+        universe.set_cells(&[(24, 5), (25, 5), (26, 5)]);
+        universe.set_cells(&GLIDER.cells_at(12, 8));
+        universe.set_cells(&[(28, 16)]);
+        universe
+    }
+
+    #[test]
+    fn census_counts_each_known_object() {
+        let census = Recognizer::new().census(&known_soup());
+
+        assert_eq!(
+            census.objects,
+            BTreeMap::from([("Block", 2), ("Blinker", 3), ("Glider", 1)])
+        );
+        assert_eq!(census.unrecognized, 1);
+    }
+
+    #[test]
+    fn objects_are_recognized_in_every_phase_and_orientation() {
+        let recognizer = Recognizer::new();
+        let mut universe = Universe::with_size(16, 16);
+        universe.set_cells(&GLIDER.cells_at(6, 6));
+
+        for _ in 0..4 {
+            let mut turned = universe.clone();
+            for _ in 0..4 {
+                turned = turned.transformed(Transform::Rotate90);
+                for oriented in [&turned, &turned.transformed(Transform::FlipH)] {
+                    let cells = objects(oriented).concat();
+                    assert_eq!(recognizer.recognize(&cells), Some("Glider"));
+                }
+            }
+            universe.tick();
+        }
+    }
+
+    #[test]
+    fn cells_within_two_cells_of_each_other_are_one_object() {
+        let mut universe = Universe::with_size(16, 16);
+        // Two blocks with a dead column between them are one object; a second dead column splits them.
+        universe.set_cells(&BLOCK.cells_at(2, 2));
+        universe.set_cells(&BLOCK.cells_at(2, 5));
+        universe.set_cells(&BLOCK.cells_at(10, 2));
+        universe.set_cells(&BLOCK.cells_at(10, 6));
+
+        let census = Recognizer::new().census(&universe);
+        assert_eq!(census.objects, BTreeMap::from([("Block", 2)]));
+        assert_eq!(census.unrecognized, 1);
+    }
+}
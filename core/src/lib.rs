@@ -0,0 +1,30 @@
+//! The game of life engine: the universe, its rules, reading patterns, and a library of well known patterns.
+//!
+//! Free of UI and platform dependencies, so that it can be used by other frontends.  Only needs an
+//! allocator, so it builds with no_std when the default "std" feature is disabled.
+//!
+//! Adapted from the rust wasm tutorial: https://rustwasm.github.io/docs/book/game-of-life/introduction.html
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod ant;
+pub mod automaton;
+mod bits;
+pub mod census;
+pub mod life106;
+pub mod ltl;
+pub mod macrocell;
+pub mod motion;
+pub mod patterns;
+pub mod period;
+pub mod predecessor;
+pub mod random;
+pub mod rle;
+pub mod rule;
+#[cfg(feature = "simd")]
+mod simd;
+pub mod soup_search;
+pub mod transform;
+pub mod universe;
@@ -0,0 +1,123 @@
+//! Larger than Life rules: life-like rules whose neighborhood reaches past the eight cells around each cell,
+//! with a range of neighbor counts to be born and to survive.
+//!
+//! Rules are written in the notation Golly uses, e.g. Bosco's Rule is `R5,C0,M1,S34..58,B34..45,NM`: a
+//! radius of 5, two states, counting the middle cell, surviving with 34 to 58 living cells in the
+//! neighborhood and born with 34 to 45, in the square (Moore) neighborhood.
+//!
+//! See https://conwaylife.com/wiki/Larger_than_Life
+
+use alloc::{format, string::String};
+use core::{fmt, str::FromStr};
+
+/// The largest radius of a neighborhood.
+pub const MAX_RADIUS: u32 = 50;
+
+/// A Larger than Life rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LtlRule {
+    /// How many cells the neighborhood reaches in each direction, so it is 2 * radius + 1 cells across.
+    pub radius: u32,
+    /// True if a cell counts itself as one of its neighbors.
+    pub middle: bool,
+    /// The fewest and most living neighbors a living cell survives with.
+    pub survival: (u32, u32),
+    /// The fewest and most living neighbors a dead cell is born with.
+    pub birth: (u32, u32),
+}
+
+impl LtlRule {
+    /// Bosco's Rule, which has spaceships and oscillators much like Conway's Life.
+    pub const BOSCO: LtlRule = LtlRule {
+        radius: 5,
+        middle: true,
+        survival: (34, 58),
+        birth: (34, 45),
+    };
+
+    /// Return true if a cell that is alive or not, with the given number of living cells in its
+    /// neighborhood, is alive in the next generation.
+    pub fn next_alive(&self, alive: bool, live_neighbors: u32) -> bool {
+        let (fewest, most) = match alive {
+            true => self.survival,
+            false => self.birth,
+        };
+        (fewest..=most).contains(&live_neighbors)
+    }
+}
+
+impl fmt::Display for LtlRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let LtlRule {
+            radius,
+            middle,
+            survival: (fewest_survival, most_survival),
+            birth: (fewest_birth, most_birth),
+        } = self;
+        write!(
+            f,
+            "R{radius},C0,M{},S{fewest_survival}..{most_survival},B{fewest_birth}..{most_birth},NM",
+            *middle as u8
+        )
+    }
+}
+
+/// Read a rule in Golly's notation.  The number of states (C) must be 0 or 2 and the neighborhood (N) must
+/// be M, the square neighborhood; both may be left out, as may the middle cell (M), which is then not counted.
+impl FromStr for LtlRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mut radius, mut middle, mut survival, mut birth) = (None, false, None, None);
+
+        for field in s.trim().split(',').map(str::trim) {
+            let mut chars = field.chars();
+            let (name, value) = (chars.next(), chars.as_str());
+            match name {
+                Some('R') => radius = Some(parse_count(value, field)?),
+                Some('C') if value == "0" || value == "2" => {}
+                Some('C') => {
+                    return Err(format!("{field}: only rules with two states are supported"))
+                }
+                Some('M') if value == "0" || value == "1" => middle = value == "1",
+                Some('S') => survival = Some(parse_range(value, field)?),
+                Some('B') => birth = Some(parse_range(value, field)?),
+                Some('N') if value == "M" => {}
+                Some('N') => {
+                    return Err(format!(
+                        "{field}: only the square neighborhood NM is supported"
+                    ))
+                }
+                _ => return Err(format!("{field:?} is not part of a Larger than Life rule")),
+            }
+        }
+
+        let radius = radius.ok_or(format!("{s:?} has no radius R"))?;
+        if !(1..=MAX_RADIUS).contains(&radius) {
+            return Err(format!(
+                "the radius must be from 1 to {MAX_RADIUS}, not {radius}"
+            ));
+        }
+        Ok(LtlRule {
+            radius,
+            middle,
+            survival: survival.ok_or(format!("{s:?} has no survival range S"))?,
+            birth: birth.ok_or(format!("{s:?} has no birth range B"))?,
+        })
+    }
+}
+
+/// Return the count written in the field.
+fn parse_count(value: &str, field: &str) -> Result<u32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{field}: bad number {value}"))
+}
+
+/// Return the (fewest, most) of a range written as `fewest..most`, or a single count.
+fn parse_range(value: &str, field: &str) -> Result<(u32, u32), String> {
+    match value.split_once("..") {
+        Some((fewest, most)) => Ok((parse_count(fewest, field)?, parse_count(most, field)?)),
+        None => parse_count(value, field).map(|count| (count, count)),
+    }
+}
@@ -0,0 +1,421 @@
+//! Reading and writing patterns in Golly's macrocell format (.mc), which stores the universe as a quadtree.
+//!
+//! Each line after the header is a node, numbered from 1.  An 8x8 leaf is written as its rows of `.` (dead)
+//! and `*` (alive), each ending with `$`.  A larger node is written as its level (a node of level k is 2^k
+//! cells wide) and the numbers of its four quarters, with 0 for an empty quarter.  The last node is the
+//! root, whose center is Golly's origin.
+//!
+//! Repeated parts of a pattern are written once, so a macrocell file can hold a pattern far larger than the
+//! universe.  Reading expands only the nodes within a window the size of the universe, at the top left of
+//! the pattern, so the rest of the pattern is cut off without being expanded.
+//!
+//! See https://conwaylife.com/wiki/Macrocell
+
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+
+use crate::{rle::golly_rule, universe::Universe};
+
+/// The level of leaf nodes: 8x8 cells.
+const LEAF_LEVEL: u32 = 3;
+/// The level of the smallest root written.
+const MIN_ROOT_LEVEL: u32 = 4;
+/// The level of the largest node read, so that the (row, col) of every cell fits in an i64.
+const MAX_LEVEL: u32 = 62;
+
+/// Return the universe in macrocell format, placed the way Golly places a bounded universe of the same size:
+/// with its top left cell at (-width / 2, -height / 2).  Returns an error if Golly can't run the universe
+/// (see `rle::golly_rule()`).
+pub fn write_macrocell(universe: &Universe) -> Result<String, String> {
+    let rule = golly_rule(universe)?;
+    let (width, height) = (universe.width(), universe.height());
+    // The root is centered on the origin, so half of it must cover the larger half of the universe.
+    let half_size = width.div_ceil(2).max(height.div_ceil(2));
+    let level = (half_size.next_power_of_two().trailing_zeros() + 1).max(MIN_ROOT_LEVEL);
+    let root_half = 1_i64 << (level - 1);
+    // The (row, col) of the root where the top left cell of the universe is.
+    let origin = (
+        root_half - (height / 2) as i64,
+        root_half - (width / 2) as i64,
+    );
+
+    let is_alive = |row: i64, col: i64| {
+        let (row, col) = (row - origin.0, col - origin.1);
+        (0..height as i64).contains(&row)
+            && (0..width as i64).contains(&col)
+            && universe.is_alive(row as u32, col as u32)
+    };
+
+    let mut writer = Writer::default();
+    if writer.node(&is_alive, (0, 0), level) == 0 {
+        // Golly expects at least one node.
+        writer.lines.push(format!("{MIN_ROOT_LEVEL} 0 0 0 0"));
+    }
+
+    let mut mc = format!(
+        "[M2] (game-of-life)\n#R {rule}\n#G {}\n",
+        universe.generation()
+    );
+    for line in writer.lines {
+        mc.push_str(&line);
+        mc.push('\n');
+    }
+    Ok(mc)
+}
+
+/// The nodes written so far, each written once however many times it appears.
+#[derive(Default)]
+struct Writer {
+    lines: Vec<String>,
+    leaves: BTreeMap<[u8; 8], usize>,
+    nodes: BTreeMap<(u32, [usize; 4]), usize>,
+}
+
+impl Writer {
+    /// Write the node of the given level with its top left cell at (row, col), after the nodes it is made
+    /// of, and return its number, or 0 if it is empty.
+    fn node(
+        &mut self,
+        is_alive: &dyn Fn(i64, i64) -> bool,
+        (row, col): (i64, i64),
+        level: u32,
+    ) -> usize {
+        if level == LEAF_LEVEL {
+            let mut rows = [0_u8; 8];
+            for (row_offset, bits) in rows.iter_mut().enumerate() {
+                for col_offset in 0..8 {
+                    if is_alive(row + row_offset as i64, col + col_offset) {
+                        *bits |= 1 << col_offset;
+                    }
+                }
+            }
+            if rows == [0; 8] {
+                return 0;
+            }
+            if let Some(number) = self.leaves.get(&rows) {
+                return *number;
+            }
+            self.lines.push(leaf_line(&rows));
+            self.leaves.insert(rows, self.lines.len());
+            return self.lines.len();
+        }
+
+        let half = 1_i64 << (level - 1);
+        let quarters = quarter_offsets(half)
+            .map(|(down, right)| self.node(is_alive, (row + down, col + right), level - 1));
+        if quarters == [0; 4] {
+            return 0;
+        }
+        if let Some(number) = self.nodes.get(&(level, quarters)) {
+            return *number;
+        }
+        let [nw, ne, sw, se] = quarters;
+        self.lines.push(format!("{level} {nw} {ne} {sw} {se}"));
+        self.nodes.insert((level, quarters), self.lines.len());
+        self.lines.len()
+    }
+}
+
+/// A node read from a macrocell file.
+enum Node {
+    /// An 8x8 leaf, each row given as bits from the leftmost cell up.
+    Leaf([u8; 8]),
+    /// A node of the level, made of the nodes numbered in its quarters: nw, ne, sw, se.  0 is empty.
+    Branch(u32, [usize; 4]),
+}
+
+impl Node {
+    fn level(&self) -> u32 {
+        match self {
+            Node::Leaf(_) => LEAF_LEVEL,
+            Node::Branch(level, _) => *level,
+        }
+    }
+}
+
+/// The (top, left, bottom, right) of the living cells of a node, relative to its top left cell.
+type Bounds = (i64, i64, i64, i64);
+
+/// Return the (row, col) of every living cell of a macrocell pattern, relative to the top left corner of
+/// its living cells, within a window of the given (height, width) at that corner.
+///
+/// Cells past the window are cut off.  Comment lines (`#`), including the rule and generation, are
+/// skipped, so the rule is ignored.  Only two state patterns are read.
+pub fn read_macrocell(text: &str, (height, width): (u32, u32)) -> Result<Vec<(u32, u32)>, String> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    if !lines
+        .next()
+        .is_some_and(|header| header.starts_with("[M2]"))
+    {
+        return Err("expected a macrocell file, starting with [M2]".into());
+    }
+
+    let mut nodes: Vec<Node> = Vec::new();
+    for line in lines.filter(|line| !line.starts_with('#')) {
+        let node = match line.chars().next() {
+            Some('.' | '*' | '$') => Node::Leaf(read_leaf(line)?),
+            _ => read_branch(line, &nodes)?,
+        };
+        nodes.push(node);
+    }
+
+    // Nodes only refer to nodes before them, so each node's bounds are found after its quarters'.
+    let mut bounds: Vec<Option<Bounds>> = Vec::with_capacity(nodes.len());
+    for node in nodes.iter() {
+        let node_bounds = match node {
+            Node::Leaf(rows) => leaf_bounds(rows),
+            Node::Branch(level, quarters) => {
+                let half = 1_i64 << (level - 1);
+                quarter_offsets(half)
+                    .into_iter()
+                    .zip(quarters)
+                    .filter_map(|((down, right), quarter)| {
+                        let (top, left, bottom, right_col) = (*quarter).checked_sub(1).and_then(|index| bounds[index])?;
+                        Some((top + down, left + right, bottom + down, right_col + right))
+                    })
+                    .reduce(|(top, left, bottom, right), (other_top, other_left, other_bottom, other_right)| {
+                        (top.min(other_top), left.min(other_left), bottom.max(other_bottom), right.max(other_right))
+                    })
+            }
+        };
+        bounds.push(node_bounds);
+    }
+
+    let Some((top, left, _, _)) = bounds.last().copied().flatten() else {
+        return Ok(Vec::new());
+    };
+    let window = Window {
+        top,
+        left,
+        bottom: top + height as i64,
+        right: left + width as i64,
+    };
+    let mut cells = Vec::new();
+    window.expand(&nodes, &bounds, nodes.len(), (0, 0), &mut cells);
+    Ok(cells)
+}
+
+/// Return the rows of a leaf line, such as `.*$..*$***$`.
+fn read_leaf(line: &str) -> Result<[u8; 8], String> {
+    let mut rows = [0_u8; 8];
+    let (mut row, mut col) = (0, 0);
+
+    for symbol in line.chars() {
+        if row >= 8 || col >= 8 && symbol != '$' {
+            return Err(format!("leaf {line:?} is larger than 8x8 cells"));
+        }
+        match symbol {
+            '.' => col += 1,
+            '*' => {
+                rows[row] |= 1 << col;
+                col += 1;
+            }
+            '$' => {
+                row += 1;
+                col = 0;
+            }
+            _ => return Err(format!("unexpected {symbol:?} in leaf {line:?}")),
+        }
+    }
+    Ok(rows)
+}
+
+/// Return the node of a line such as `5 1 0 2 3`, which follows the nodes read so far.
+fn read_branch(line: &str, nodes: &[Node]) -> Result<Node, String> {
+    let numbers: Vec<usize> = line
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("expected a level and four node numbers, not {line:?}"))?;
+    let [level, nw, ne, sw, se] = numbers[..] else {
+        return Err(format!(
+            "expected a level and four node numbers, not {line:?}"
+        ));
+    };
+
+    if level <= LEAF_LEVEL as usize {
+        return Err("only two state macrocell patterns, with 8x8 leaves, can be read".into());
+    }
+    if level > MAX_LEVEL as usize {
+        return Err(format!("node level {level} is too large"));
+    }
+    for quarter in [nw, ne, sw, se].into_iter().filter(|quarter| *quarter != 0) {
+        match nodes.get(quarter - 1) {
+            None => return Err(format!("node {line:?} refers to a node not yet defined")),
+            Some(node) if node.level() as usize != level - 1 => {
+                return Err(format!(
+                    "node {line:?} has a quarter that isn't one level below it"
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(Node::Branch(level as u32, [nw, ne, sw, se]))
+}
+
+/// Return the bounds of the living cells of a leaf, or None if it is empty.
+fn leaf_bounds(rows: &[u8; 8]) -> Option<Bounds> {
+    let top = rows.iter().position(|bits| *bits != 0)? as i64;
+    let bottom = rows.iter().rposition(|bits| *bits != 0)? as i64;
+    let all = rows.iter().fold(0, |all, bits| all | bits);
+    let left = all.trailing_zeros() as i64;
+    let right = 7 - all.leading_zeros() as i64;
+    Some((top, left, bottom, right))
+}
+
+/// Return the (rows, cols) offsets of the nw, ne, sw and se quarters of a node whose quarters are half wide.
+fn quarter_offsets(half: i64) -> [(i64, i64); 4] {
+    [(0, 0), (0, half), (half, 0), (half, half)]
+}
+
+/// The cells read from the root: rows from top to before bottom, and columns from left to before right.
+struct Window {
+    top: i64,
+    left: i64,
+    bottom: i64,
+    right: i64,
+}
+
+impl Window {
+    /// Add the living cells within the window of the node numbered `number`, with its top left cell at
+    /// (row, col) of the root, to cells, relative to the top left of the window.
+    fn expand(
+        &self,
+        nodes: &[Node],
+        bounds: &[Option<Bounds>],
+        number: usize,
+        (row, col): (i64, i64),
+        cells: &mut Vec<(u32, u32)>,
+    ) {
+        let Some((top, left, bottom, right)) =
+            number.checked_sub(1).and_then(|index| bounds[index])
+        else {
+            return;
+        };
+        let outside = row + bottom < self.top
+            || row + top >= self.bottom
+            || col + right < self.left
+            || col + left >= self.right;
+        if outside {
+            return;
+        }
+
+        match &nodes[number - 1] {
+            Node::Leaf(rows) => {
+                for (row_offset, bits) in rows.iter().enumerate() {
+                    for col_offset in (0..8).filter(|col_offset| bits & (1 << col_offset) != 0) {
+                        let (cell_row, cell_col) = (row + row_offset as i64, col + col_offset);
+                        let inside = (self.top..self.bottom).contains(&cell_row)
+                            && (self.left..self.right).contains(&cell_col);
+                        if inside {
+                            cells.push((
+                                (cell_row - self.top) as u32,
+                                (cell_col - self.left) as u32,
+                            ));
+                        }
+                    }
+                }
+            }
+            Node::Branch(level, quarters) => {
+                let half = 1_i64 << (level - 1);
+                for ((down, right), quarter) in quarter_offsets(half).into_iter().zip(quarters) {
+                    self.expand(nodes, bounds, *quarter, (row + down, col + right), cells);
+                }
+            }
+        }
+    }
+}
+
+/// Return the line of an 8x8 leaf, each row given as bits from the leftmost cell up.
+///
+/// Dead cells at the ends of rows, and empty rows at the end, are left out.
+fn leaf_line(rows: &[u8; 8]) -> String {
+    let last_row = rows.iter().rposition(|bits| *bits != 0).unwrap_or(0);
+
+    rows[..=last_row]
+        .iter()
+        .map(|bits| {
+            let length = 8 - bits.leading_zeros() as usize;
+            let cells: String = (0..length)
+                .map(|col| if bits & (1 << col) != 0 { '*' } else { '.' })
+                .collect();
+            format!("{cells}$")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return the living cells of the universe moved so their top left corner is at (0, 0), in row order,
+    /// as read_macrocell() returns them.
+    fn from_corner(universe: &Universe) -> Vec<(u32, u32)> {
+        let cells: Vec<(u32, u32)> = universe
+            .get_living_cells()
+            .into_iter()
+            .map(|(x, y)| (y as u32, x as u32))
+            .collect();
+        let top = cells.iter().map(|(row, _)| *row).min().unwrap_or(0);
+        let left = cells.iter().map(|(_, col)| *col).min().unwrap_or(0);
+        let mut cells: Vec<(u32, u32)> = cells
+            .into_iter()
+            .map(|(row, col)| (row - top, col - left))
+            .collect();
+        cells.sort_unstable();
+        cells
+    }
+
+    /// Return the cells read from the macrocell text, in row order.
+    fn read_sorted(mc: &str, size: (u32, u32)) -> Vec<(u32, u32)> {
+        let mut cells = read_macrocell(mc, size).expect("written macrocell should read back");
+        cells.sort_unstable();
+        cells
+    }
+
+    #[test]
+    fn glider_round_trips() {
+        let mut universe = Universe::with_size(64, 64);
+        universe.set_cells(&[(10, 21), (11, 22), (12, 20), (12, 21), (12, 22)]);
+
+        let mc = write_macrocell(&universe).unwrap();
+        assert!(mc.starts_with("[M2]"));
+        assert_eq!(read_sorted(&mc, (64, 64)), from_corner(&universe));
+    }
+
+    #[test]
+    fn empty_quarters_are_written_as_zero_and_round_trip() {
+        // A glider in the nw quarter and a block in the se quarter of the root.
+        let mut universe = Universe::with_size(32, 32);
+        universe.set_cells(&[(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)]);
+        universe.set_cells(&[(20, 25), (20, 26), (21, 25), (21, 26)]);
+
+        let mc = write_macrocell(&universe).unwrap();
+        let root = mc.lines().last().unwrap();
+        let quarters: Vec<&str> = root.split_whitespace().skip(1).collect();
+        assert_eq!((quarters[1], quarters[2]), ("0", "0"));
+        assert_eq!(read_sorted(&mc, (32, 32)), from_corner(&universe));
+    }
+
+    #[test]
+    fn empty_universe_round_trips() {
+        let universe = Universe::with_size(16, 16);
+
+        assert!(read_sorted(&write_macrocell(&universe).unwrap(), (16, 16)).is_empty());
+    }
+
+    #[test]
+    fn rejects_leaves_larger_than_8_by_8() {
+        let nine_rows = "*$".repeat(9);
+        let nine_cols = ".........*$";
+        for leaf in [nine_rows.as_str(), nine_cols] {
+            assert!(read_macrocell(&format!("[M2]\n{leaf}\n"), (64, 64)).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_branches_with_quarters_not_yet_defined_or_of_the_wrong_level() {
+        assert!(read_macrocell("[M2]\n*$\n4 1 0 0 2\n", (64, 64)).is_err());
+        assert!(read_macrocell("[M2]\n*$\n4 1 0 0 0\n4 1 2 0 0\n", (64, 64)).is_err());
+        assert!(read_macrocell("[M2]\n*$\n4 1 0 0 1\n", (64, 64)).is_ok());
+    }
+}
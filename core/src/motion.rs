@@ -0,0 +1,283 @@
+//! Measuring how fast an object moves, and whether it is a still life, oscillator or spaceship.
+//!
+//! The object is run alone in an empty universe with the rule, automaton, topology and boundary of the
+//! universe it came from, and its bounding box is followed across generations until the same shape appears
+//! again.  The distance the bounding box moved in that many generations is the speed of the object.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::universe::{BoundingBox, Topology, Universe};
+
+/// The (row, column) and state of living cells, relative to the top left of their bounding box.
+type Shape = Vec<(i64, i64, u8)>;
+
+/// How an object moves: after `period` generations it has the same shape, moved by `displacement`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Motion {
+    /// Generations until the object repeats its shape.
+    pub period: u32,
+    /// The (rows, columns) the object moved in one period.  Positive rows are down, positive columns right.
+    pub displacement: (i64, i64),
+}
+
+impl Motion {
+    /// True if the object moves.
+    pub fn is_moving(&self) -> bool {
+        self.displacement != (0, 0)
+    }
+}
+
+/// A speed is written as a fraction of the speed of light (one cell per generation) and a direction,
+/// such as "c/4 diagonal" for a glider, or "2c/5 orthogonal".  Objects that don't move are "stationary".
+impl fmt::Display for Motion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (rows, cols) = (
+            self.displacement.0.unsigned_abs(),
+            self.displacement.1.unsigned_abs(),
+        );
+        if rows == 0 && cols == 0 {
+            return write!(f, "stationary");
+        }
+
+        let distance = rows.max(cols);
+        let divisor = gcd(distance, self.period as u64);
+        let (distance, period) = (distance / divisor, self.period as u64 / divisor);
+
+        if distance != 1 {
+            write!(f, "{distance}")?;
+        }
+        write!(f, "c")?;
+        if period != 1 {
+            write!(f, "/{period}")?;
+        }
+
+        let direction = if rows == 0 || cols == 0 {
+            "orthogonal"
+        } else if rows == cols {
+            "diagonal"
+        } else {
+            "oblique"
+        };
+        write!(f, " {direction}")
+    }
+}
+
+/// What kind of object repeats itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// Never changes.
+    StillLife,
+    /// Returns to the same state in the same place after its period.
+    Oscillator,
+    /// Returns to the same shape in a different place after its period.
+    Spaceship,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::StillLife => write!(f, "still life"),
+            Kind::Oscillator => write!(f, "oscillator"),
+            Kind::Spaceship => write!(f, "spaceship"),
+        }
+    }
+}
+
+/// The result of running an object until it repeats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Analysis {
+    pub motion: Motion,
+    /// The number of columns covered by the object over its period.
+    pub width: u32,
+    /// The number of rows covered by the object over its period.
+    pub height: u32,
+}
+
+impl Analysis {
+    /// Return whether the object is a still life, oscillator or spaceship.
+    pub fn kind(&self) -> Kind {
+        if self.motion.is_moving() {
+            Kind::Spaceship
+        } else if self.motion.period == 1 {
+            Kind::StillLife
+        } else {
+            Kind::Oscillator
+        }
+    }
+}
+
+/// Run the object made of the given (row, column) cells of the universe alone, and return how it moves.
+///
+/// Returns None if the object dies, or doesn't repeat its shape within max_generations.
+pub fn find_motion(
+    cells: &[(u32, u32)],
+    universe: &Universe,
+    max_generations: u32,
+) -> Option<Motion> {
+    analyze(cells, universe, max_generations).map(|analysis| analysis.motion)
+}
+
+/// Run the object made of the given (row, column) cells of the universe alone until it repeats its shape,
+/// and return its period, motion and size.
+///
+/// Returns None if the object dies, or doesn't repeat its shape within max_generations.
+pub fn analyze(
+    cells: &[(u32, u32)],
+    universe: &Universe,
+    max_generations: u32,
+) -> Option<Analysis> {
+    let mut universe = isolate(cells, universe, max_generations)?;
+    let mut covered = universe.bounding_box()?;
+    let (start_shape, start_corner) = shape(&universe)?;
+
+    for period in 1..=max_generations {
+        universe.tick();
+
+        let (next_shape, corner) = shape(&universe)?;
+        let displacement = (corner.0 - start_corner.0, corner.1 - start_corner.1);
+        // Hex rows are offset by half a cell in turn, so a shape moved an odd number of rows has other
+        // neighbors, and isn't the same object.
+        let same_rows = universe.topology() == Topology::Square || displacement.0 % 2 == 0;
+        if next_shape == start_shape && same_rows {
+            return Some(Analysis {
+                motion: Motion {
+                    period,
+                    displacement,
+                },
+                width: covered.width(),
+                height: covered.height(),
+            });
+        }
+
+        let bounds = universe.bounding_box()?;
+        covered = BoundingBox {
+            top: covered.top.min(bounds.top),
+            left: covered.left.min(bounds.left),
+            bottom: covered.bottom.max(bounds.bottom),
+            right: covered.right.max(bounds.right),
+        };
+    }
+    None
+}
+
+/// Return an empty universe with the settings of the given universe, holding only the given cells in their
+/// states, with room for them to move one cell each generation for the given generations without reaching
+/// its edges.
+fn isolate(cells: &[(u32, u32)], universe: &Universe, generations: u32) -> Option<Universe> {
+    let top = cells.iter().map(|(row, _)| *row).min()?;
+    let left = cells.iter().map(|(_, col)| *col).min()?;
+    let bottom = cells.iter().map(|(row, _)| *row).max()?;
+    let right = cells.iter().map(|(_, col)| *col).max()?;
+    // One more cell of margin, so cells that are born just outside the object don't reach the edges.  The
+    // margin is even, so hex rows keep their offsets.
+    let margin = (generations + 2).next_multiple_of(2);
+    // Moving the object an odd number of rows would change the neighbors of its hex cells.
+    let above = margin + top % 2;
+
+    let mut isolated = Universe::with_size(
+        right - left + 1 + margin * 2,
+        bottom - top + 1 + above + margin,
+    );
+    isolated.set_rule(universe.rule());
+    isolated.set_topology(universe.topology());
+    isolated.set_boundary(universe.boundary());
+    isolated.set_automaton(universe.automaton());
+    isolated.set_ants(&[]);
+    for (row, col) in cells.iter().copied() {
+        let state = universe.state(row, col);
+        isolated.set_state(row - top + above, col - left + margin, state);
+    }
+    Some(isolated)
+}
+
+/// Return the living cells of the universe and their states relative to the top left of their bounding
+/// box, and the (row, column) of that corner, or None if all cells are dead.
+fn shape(universe: &Universe) -> Option<(Shape, (i64, i64))> {
+    let cells = universe.get_living_cells();
+    let top = cells.iter().map(|(_, y)| *y).min()?;
+    let left = cells.iter().map(|(x, _)| *x).min()?;
+
+    let shape = cells
+        .iter()
+        .map(|(x, y)| (y - top, x - left, universe.state(*y as u32, *x as u32)))
+        .collect();
+    Some((shape, (top, left)))
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::universe::Boundary;
+
+    const GLIDER: [(u32, u32); 5] = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+    const BLINKER: [(u32, u32); 3] = [(4, 3), (4, 4), (4, 5)];
+
+    /// Return a universe of the given size holding the cells moved down and right by offset.
+    fn universe_with(cells: &[(u32, u32)], size: u32, offset: u32) -> (Universe, Vec<(u32, u32)>) {
+        let cells: Vec<_> = cells
+            .iter()
+            .map(|(row, col)| (row + offset, col + offset))
+            .collect();
+        let mut universe = Universe::with_size(size, size);
+        universe.set_cells(&cells);
+        (universe, cells)
+    }
+
+    #[test]
+    fn gliders_move_at_c_over_4_diagonally() {
+        let (universe, cells) = universe_with(&GLIDER, 16, 5);
+        let analysis = analyze(&cells, &universe, 16).unwrap();
+
+        assert_eq!(analysis.kind(), Kind::Spaceship);
+        assert_eq!(
+            analysis.motion,
+            Motion {
+                period: 4,
+                displacement: (1, 1)
+            }
+        );
+        assert_eq!(analysis.motion.to_string(), "c/4 diagonal");
+    }
+
+    #[test]
+    fn blinkers_oscillate_with_period_2() {
+        let (universe, cells) = universe_with(&BLINKER, 10, 0);
+        let analysis = analyze(&cells, &universe, 16).unwrap();
+
+        assert_eq!(analysis.kind(), Kind::Oscillator);
+        assert_eq!(analysis.motion.period, 2);
+        assert_eq!((analysis.width, analysis.height), (3, 3));
+        assert_eq!(analysis.motion.to_string(), "stationary");
+    }
+
+    #[test]
+    fn blinkers_die_on_hex_cells() {
+        // The cells in the rows above and below the middle of a row of three have only two living neighbors
+        // each on hex cells, so nothing is born and the ends die alone.
+        let (mut universe, cells) = universe_with(&BLINKER, 10, 0);
+        universe.set_topology(Topology::Hex);
+
+        assert_eq!(analyze(&cells, &universe, 16), None);
+    }
+
+    #[test]
+    fn gliders_against_dead_edges_are_run_clear_of_them() {
+        // The glider is in the corner of a universe that would stop it, but is run on its own with room to
+        // fly.
+        let (mut universe, cells) = universe_with(&GLIDER, 3, 0);
+        universe.set_boundary(Boundary::Dead);
+
+        let motion = find_motion(&cells, &universe, 16).unwrap();
+        assert_eq!(motion.period, 4);
+        assert_eq!(motion.displacement, (1, 1));
+    }
+}
@@ -0,0 +1,88 @@
+//! A library of well known patterns.
+
+use alloc::vec::Vec;
+
+use crate::rle::read_rle;
+
+/// A named pattern, stored as RLE.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub rle: &'static str,
+}
+
+impl Pattern {
+    /// Return the (row, col) of every living cell, relative to the top left corner of the pattern.
+    pub fn cells(&self) -> Vec<(u32, u32)> {
+        read_rle(self.rle, (u32::MAX, u32::MAX)).expect("library patterns should be valid RLE")
+    }
+
+    /// Return the (row, col) of every living cell, with the top left corner of the pattern at row, col.
+    pub fn cells_at(&self, row: u32, col: u32) -> Vec<(u32, u32)> {
+        self.cells()
+            .into_iter()
+            .map(|(cell_row, cell_col)| (cell_row + row, cell_col + col))
+            .collect()
+    }
+}
+
+pub const BLOCK: Pattern = Pattern {
+    name: "Block",
+    description: "The most common still life.",
+    rle: "2o$2o!",
+};
+
+pub const BEEHIVE: Pattern = Pattern {
+    name: "Beehive",
+    description: "The second most common still life.",
+    rle: "b2o$o2bo$b2o!",
+};
+
+pub const BLINKER: Pattern = Pattern {
+    name: "Blinker",
+    description: "The smallest and most common oscillator, switching between a row and a column of three cells.",
+    rle: "3o!",
+};
+
+pub const GLIDER: Pattern = Pattern {
+    name: "Glider",
+    description: "The smallest spaceship, moving one cell diagonally every four generations.",
+    rle: "bo$2bo$3o!",
+};
+
+pub const LIGHTWEIGHT_SPACESHIP: Pattern = Pattern {
+    name: "Lightweight spaceship",
+    description: "The smallest orthogonal spaceship, moving two cells every four generations.",
+    rle: "bo2bo$o4b$o3bo$4o!",
+};
+
+pub const GOSPER_GLIDER_GUN: Pattern = Pattern {
+    name: "Gosper glider gun",
+    description: "The first known gun, found by Bill Gosper in 1970.  Fires a glider every 30 generations.",
+    rle: "24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4bobo$10bo5bo7bo$11bo3bo$12b2o!",
+};
+
+pub const PUFFER_TRAIN: Pattern = Pattern {
+    name: "Puffer train",
+    description: "Two spaceships escorting an engine that leaves a trail of debris behind it.",
+    rle: "3bo$4bo$o3bo$b4o3$o$b2o$2bo$2bo$bo3$3bo$4bo$o3bo$b4o!",
+};
+
+/// Every pattern in the library.
+pub const LIBRARY: &[Pattern] = &[
+    BLOCK,
+    BEEHIVE,
+    BLINKER,
+    GLIDER,
+    LIGHTWEIGHT_SPACESHIP,
+    GOSPER_GLIDER_GUN,
+    PUFFER_TRAIN,
+];
+
+/// Return the pattern in the library with the given name, ignoring case.
+pub fn find(name: &str) -> Option<&'static Pattern> {
+    LIBRARY
+        .iter()
+        .find(|pattern| pattern.name.eq_ignore_ascii_case(name))
+}
@@ -0,0 +1,178 @@
+//! Noticing when the universe has become periodic: a still life repeats every generation, a blinker every
+//! two, a pulsar every three.
+//!
+//! The hashes of recent generations are remembered, so the universe is known to repeat as soon as the hash
+//! of an earlier generation comes back.  Generations can be skipped between observations, such as when
+//! several are ticked each frame, so the period is then confirmed by running a copy of the universe.
+//!
+//! The universe keeps its Zobrist hash up to date as it changes, so observing a generation doesn't read its
+//! cells.
+
+use alloc::collections::{BTreeMap, VecDeque};
+
+use crate::universe::Universe;
+
+/// The most recent generations remembered.  Universes that repeat after longer are not noticed.
+pub const RECENT_GENERATIONS: usize = 1024;
+
+/// How a universe repeats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Periodic {
+    /// Generations until the universe repeats: 1 for still lifes (and empty universes).
+    pub period: u64,
+    /// The first generation seen repeating.  If every generation was observed, the universe became periodic
+    /// at this generation.
+    pub generation: u64,
+}
+
+/// Follows the universe from generation to generation, to notice it repeating.
+#[derive(Clone, Debug, Default)]
+pub struct PeriodDetector {
+    /// The generation each recent hash was seen at.
+    seen: BTreeMap<u64, u64>,
+    /// The recent hashes, oldest first, to forget the oldest.
+    recent: VecDeque<u64>,
+    generation: u64,
+    periodic: Option<Periodic>,
+}
+
+impl PeriodDetector {
+    pub fn new() -> PeriodDetector {
+        PeriodDetector::default()
+    }
+
+    /// Observe the universe, returning how it repeats if it has become periodic.
+    ///
+    /// The detector starts over when the generation goes back, such as when the universe is cleared, and
+    /// forgets the universe was periodic once it is edited.
+    pub fn observe(&mut self, universe: &Universe) -> Option<Periodic> {
+        let generation = universe.generation();
+        if generation < self.generation {
+            *self = PeriodDetector::default();
+        }
+        self.generation = generation;
+
+        let hash = universe.zobrist_hash();
+        match self.seen.get(&hash) {
+            // Observed again without changing, such as when redrawn.
+            Some(&first) if first == generation => {}
+            // Still repeating.
+            Some(_) if self.periodic.is_some() => {}
+            Some(&first) => {
+                match period(universe, generation - first) {
+                    Some(period) => {
+                        self.periodic = Some(Periodic {
+                            period,
+                            generation: first,
+                        })
+                    }
+                    // Edited back to an earlier state it doesn't return to.
+                    None => self.remember(hash, generation),
+                }
+            }
+            None => {
+                self.periodic = None;
+                self.remember(hash, generation);
+            }
+        }
+        self.periodic
+    }
+
+    /// Remember the hash of the universe at the generation, forgetting the oldest hash if there are too many.
+    fn remember(&mut self, hash: u64, generation: u64) {
+        if self.seen.insert(hash, generation).is_none() {
+            self.recent.push_back(hash);
+        }
+        if self.recent.len() > RECENT_GENERATIONS {
+            if let Some(oldest) = self.recent.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Return the fewest generations, up to max_generations, after which the universe is the same again, or
+/// None if it doesn't repeat within them.
+pub fn period(universe: &Universe, max_generations: u64) -> Option<u64> {
+    let mut later = universe.clone();
+
+    (1..=max_generations).find(|_| {
+        later.tick();
+        later.same_cells(universe)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{BLINKER, BLOCK, GLIDER};
+
+    fn universe_with(cells: &[(u32, u32)]) -> Universe {
+        let mut universe = Universe::with_size(64, 64);
+        universe.set_cells(cells);
+        universe
+    }
+
+    /// Observe every generation of the universe up to the given generations, returning what was observed
+    /// last.
+    fn observe_for(universe: &mut Universe, generations: u64) -> Option<Periodic> {
+        let mut detector = PeriodDetector::new();
+        let mut periodic = detector.observe(universe);
+        for _ in 0..generations {
+            universe.tick();
+            periodic = detector.observe(universe);
+        }
+        periodic
+    }
+
+    #[test]
+    fn still_lifes_repeat_every_generation() {
+        let mut universe = universe_with(&BLOCK.cells_at(10, 10));
+
+        assert_eq!(period(&universe, 8), Some(1));
+        assert_eq!(
+            observe_for(&mut universe, 3),
+            Some(Periodic {
+                period: 1,
+                generation: 0
+            })
+        );
+    }
+
+    #[test]
+    fn oscillators_repeat_after_their_period() {
+        let mut universe = universe_with(&BLINKER.cells_at(10, 10));
+
+        assert_eq!(period(&universe, 8), Some(2));
+        assert_eq!(observe_for(&mut universe.clone(), 1), None);
+        assert_eq!(
+            observe_for(&mut universe, 2),
+            Some(Periodic {
+                period: 2,
+                generation: 0
+            })
+        );
+    }
+
+    #[test]
+    fn gliders_dont_repeat_until_they_fly_around_the_universe() {
+        // The glider moves a cell diagonally every four generations, so it is back after 4 * 64 generations.
+        let mut universe = universe_with(&GLIDER.cells_at(10, 10));
+
+        assert_eq!(period(&universe, 255), None);
+        assert_eq!(period(&universe, 1000), Some(256));
+        assert_eq!(observe_for(&mut universe, 255), None);
+    }
+
+    #[test]
+    fn edits_forget_the_universe_was_periodic() {
+        let mut universe = universe_with(&BLOCK.cells_at(10, 10));
+        let mut detector = PeriodDetector::new();
+        detector.observe(&universe);
+        universe.tick();
+        assert!(detector.observe(&universe).is_some());
+
+        universe.set_cells(&GLIDER.cells_at(30, 30));
+        assert_eq!(detector.observe(&universe), None);
+    }
+}
@@ -0,0 +1,244 @@
+//! Searching backwards in time: finding a pattern that evolves into a given pattern.
+//!
+//! The search is bounded: the predecessor one generation back may only have living cells within one cell of
+//! the pattern, and must leave every cell around the pattern dead.  Cells are assigned one at a time in rows,
+//! and each cell of the next generation is checked as soon as all of its neighbors are assigned, so most
+//! dead ends are abandoned early.  Searching several generations back tries each predecessor found in turn.
+
+use alloc::{vec, vec::Vec};
+
+use crate::rule::Rule;
+
+/// The result of a predecessor search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Predecessor {
+    /// The (row, column) of the living cells of a predecessor, relative to the top left of the pattern.
+    ///
+    /// Rows and columns may be negative, since a predecessor can be larger than the pattern.
+    Found(Vec<(i64, i64)>),
+    /// No predecessor exists within the searched region, so the pattern is likely a Garden of Eden (a
+    /// pattern with no predecessor at all).
+    NotFound,
+    /// The search was abandoned after using up its budget of cell assignments.
+    GaveUp,
+}
+
+/// Search for a pattern that evolves into the given (row, column) cells after the given generations.
+///
+/// The search gives up after `budget` cell assignments.
+pub fn find_predecessor(
+    cells: &[(u32, u32)],
+    rule: Rule,
+    generations: u32,
+    budget: u64,
+) -> Predecessor {
+    let top = cells.iter().map(|(row, _)| *row).min().unwrap_or(0);
+    let left = cells.iter().map(|(_, col)| *col).min().unwrap_or(0);
+    let target = Grid::from_cells(
+        cells
+            .iter()
+            .map(|(row, col)| ((row - top) as i64, (col - left) as i64)),
+    );
+
+    let mut search = Search { rule, budget };
+    match search.predecessor(&target, generations) {
+        Ok(Some(grid)) => Predecessor::Found(grid.living_cells()),
+        Ok(None) => Predecessor::NotFound,
+        Err(OutOfBudget) => Predecessor::GaveUp,
+    }
+}
+
+/// A rectangle of cells, placed at an origin relative to the top left of the pattern searched from.
+#[derive(Clone)]
+struct Grid {
+    origin: (i64, i64),
+    width: i64,
+    height: i64,
+    cells: Vec<bool>,
+}
+
+impl Grid {
+    /// Return an all dead grid of the given size.
+    fn dead(origin: (i64, i64), width: i64, height: i64) -> Grid {
+        Grid {
+            origin,
+            width,
+            height,
+            cells: vec![false; (width * height) as usize],
+        }
+    }
+
+    /// Return the smallest grid holding the given (row, column) cells.
+    fn from_cells(cells: impl Iterator<Item = (i64, i64)> + Clone) -> Grid {
+        let top = cells.clone().map(|(row, _)| row).min().unwrap_or(0);
+        let left = cells.clone().map(|(_, col)| col).min().unwrap_or(0);
+        let bottom = cells.clone().map(|(row, _)| row).max().unwrap_or(-1);
+        let right = cells.clone().map(|(_, col)| col).max().unwrap_or(-1);
+
+        let mut grid = Grid::dead((top, left), right - left + 1, bottom - top + 1);
+        for (row, col) in cells {
+            let idx = grid.index(row - top, col - left);
+            grid.cells[idx] = true;
+        }
+        grid
+    }
+
+    fn index(&self, row: i64, col: i64) -> usize {
+        (row * self.width + col) as usize
+    }
+
+    /// Return the cell at the row and column within the grid.  Cells outside of the grid are dead.
+    fn get(&self, row: i64, col: i64) -> bool {
+        (0..self.height).contains(&row)
+            && (0..self.width).contains(&col)
+            && self.cells[self.index(row, col)]
+    }
+
+    /// Return the (row, column) of the living cells, relative to the pattern searched from.
+    fn living_cells(&self) -> Vec<(i64, i64)> {
+        (0..self.height)
+            .flat_map(|row| (0..self.width).map(move |col| (row, col)))
+            .filter(|(row, col)| self.get(*row, *col))
+            .map(|(row, col)| (row + self.origin.0, col + self.origin.1))
+            .collect()
+    }
+}
+
+/// Returned when a search uses up its budget.
+struct OutOfBudget;
+
+struct Search {
+    rule: Rule,
+    /// The cell assignments left before giving up.
+    budget: u64,
+}
+
+impl Search {
+    /// Return a pattern that evolves into the target after the given generations, if there is one.
+    fn predecessor(
+        &mut self,
+        target: &Grid,
+        generations: u32,
+    ) -> Result<Option<Grid>, OutOfBudget> {
+        if generations == 0 || target.cells.iter().all(|alive| !alive) {
+            return Ok(Some(target.clone()));
+        }
+
+        // The predecessor has a border of one cell around the target, so the target cell at (row, col) is
+        // at (row + 1, col + 1) in it.
+        let mut predecessor = Grid::dead(
+            (target.origin.0 - 1, target.origin.1 - 1),
+            target.width + 2,
+            target.height + 2,
+        );
+
+        // The cells of the next generation to check when each cell of the predecessor is assigned: those
+        // whose last neighbor (in rows) it is.  Cells further out than two cells from the target can't
+        // have a living neighbor.
+        let mut checks = vec![Vec::new(); predecessor.cells.len()];
+        for row in -2..target.height + 2 {
+            for col in -2..target.width + 2 {
+                let last_neighbor = predecessor.index(
+                    (row + 2).min(predecessor.height - 1),
+                    (col + 2).min(predecessor.width - 1),
+                );
+                checks[last_neighbor].push((row, col));
+            }
+        }
+
+        self.assign(target, &mut predecessor, &checks, 0, generations)
+    }
+
+    /// Try both states of the predecessor cell at idx, and then the following cells, returning the first
+    /// complete predecessor that evolves into the target, and further back if more generations are left.
+    fn assign(
+        &mut self,
+        target: &Grid,
+        predecessor: &mut Grid,
+        checks: &[Vec<(i64, i64)>],
+        idx: usize,
+        generations: u32,
+    ) -> Result<Option<Grid>, OutOfBudget> {
+        if idx == predecessor.cells.len() {
+            let living = predecessor.living_cells();
+            let trimmed = Grid::from_cells(living.iter().cloned());
+            return self.predecessor(&trimmed, generations - 1);
+        }
+
+        for alive in [false, true] {
+            self.budget = self.budget.checked_sub(1).ok_or(OutOfBudget)?;
+            predecessor.cells[idx] = alive;
+
+            let consistent = checks[idx]
+                .iter()
+                .all(|(row, col)| self.evolves_to(predecessor, target, *row, *col));
+            if consistent {
+                if let Some(found) =
+                    self.assign(target, predecessor, checks, idx + 1, generations)?
+                {
+                    return Ok(Some(found));
+                }
+            }
+        }
+        predecessor.cells[idx] = false;
+        Ok(None)
+    }
+
+    /// True if the target cell at row, col is the next generation of the same cell of the predecessor.
+    fn evolves_to(&self, predecessor: &Grid, target: &Grid, row: i64, col: i64) -> bool {
+        let (row_in_predecessor, col_in_predecessor) = (row + 1, col + 1);
+        let mut live_neighbors = 0;
+
+        for neighbor_row in row_in_predecessor - 1..=row_in_predecessor + 1 {
+            for neighbor_col in col_in_predecessor - 1..=col_in_predecessor + 1 {
+                if (neighbor_row, neighbor_col) != (row_in_predecessor, col_in_predecessor)
+                    && predecessor.get(neighbor_row, neighbor_col)
+                {
+                    live_neighbors += 1;
+                }
+            }
+        }
+
+        let alive = predecessor.get(row_in_predecessor, col_in_predecessor);
+        self.rule.next_alive(alive, live_neighbors) == target.get(row, col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUDGET: u64 = 1_000_000;
+
+    #[test]
+    fn finds_the_other_phase_of_a_blinker() {
+        let vertical = [(0, 1), (1, 1), (2, 1)];
+
+        assert_eq!(
+            find_predecessor(&vertical, Rule::CONWAY, 1, BUDGET),
+            Predecessor::Found(vec![(1, -1), (1, 0), (1, 1)])
+        );
+    }
+
+    #[test]
+    fn reports_a_garden_of_eden_as_not_found() {
+        // In Life without Death cells never die, so a predecessor of the L tromino is part of it, and the
+        // whole tromino gives birth to the cell that completes the block, while less of it gives birth to
+        // nothing.
+        let life_without_death: Rule = "B3/S012345678".parse().unwrap();
+        let tromino = [(0, 0), (0, 1), (1, 0)];
+
+        assert_eq!(
+            find_predecessor(&tromino, life_without_death, 1, BUDGET),
+            Predecessor::NotFound
+        );
+    }
+
+    #[test]
+    fn gives_up_when_out_of_budget() {
+        assert_eq!(
+            find_predecessor(&[(0, 1), (1, 1), (2, 1)], Rule::CONWAY, 1, 10),
+            Predecessor::GaveUp
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! Sources of random numbers for randomizing the universe.
+//!
+//! SeededRandom produces the same numbers for a seed on every platform.  Frontends provide their own
+//! sources backed by the platform's random number generator.
+
+use alloc::vec::Vec;
+
+/// A source of random numbers.
+pub trait RandomSource {
+    /// Return the next random number.
+    fn next_u64(&mut self) -> u64;
+
+    /// Return a random number from 0 up to, but not including, max.
+    fn gen_range(&mut self, max: u32) -> u32 {
+        (self.next_u64() % max as u64) as u32
+    }
+}
+
+/// A small pseudo random number generator (SplitMix64) that produces the same numbers for a seed on every platform.
+pub struct SeededRandom(u64);
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> SeededRandom {
+        SeededRandom(seed)
+    }
+}
+
+impl RandomSource for SeededRandom {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Return the (row, col) of the living cells of a random soup of the given size, with half of the cells
+/// alive on average.
+///
+/// The top left quarter is random, and is mirrored left to right and top to bottom, giving the soup the
+/// four-fold symmetry that makes for some of the most striking evolutions.
+pub fn symmetric_soup(random: &mut impl RandomSource, rows: u32, cols: u32) -> Vec<(u32, u32)> {
+    let mut cells = Vec::new();
+
+    for row in 0..rows.div_ceil(2) {
+        for col in 0..cols.div_ceil(2) {
+            if random.gen_range(2) == 0 {
+                continue;
+            }
+            let (mirror_row, mirror_col) = (rows - 1 - row, cols - 1 - col);
+            cells.extend([
+                (row, col),
+                (row, mirror_col),
+                (mirror_row, col),
+                (mirror_row, mirror_col),
+            ]);
+        }
+    }
+    // Cells on the middle row or column of an odd sized soup are their own mirror images.
+    cells.sort_unstable();
+    cells.dedup();
+    cells
+}
@@ -0,0 +1,290 @@
+//! Reading and writing patterns in the run length encoded (RLE) format used by most game of life programs.
+//!
+//! See https://conwaylife.com/wiki/Run_Length_Encoded
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+    automaton::Automaton,
+    universe::{Boundary, Topology, Universe},
+};
+
+/// The longest line written, as recommended by the format.
+const MAX_LINE_LENGTH: usize = 70;
+
+/// Return the (row, col) of every living cell of an RLE pattern, relative to its top left corner, within a
+/// window of the given (height, width) at that corner.
+///
+/// Cells past the window are cut off, so a pattern costs no more than the window however long its runs are.
+/// Comment lines (`#`) and the header line (`x = 3, y = 3, rule = B3/S23`) are skipped, so the rule is ignored.
+/// Any tag other than `b` (dead) or `$` (end of row) is read as a living cell, so patterns with more than two
+/// states load as two state patterns.
+pub fn read_rle(text: &str, (height, width): (u32, u32)) -> Result<Vec<(u32, u32)>, String> {
+    let mut cells = Vec::new();
+    let (mut row, mut col) = (0_u32, 0_u32);
+    // The run count read so far for the next tag.
+    let mut count: Option<u32> = None;
+
+    let lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('x'));
+
+    for line in lines {
+        for symbol in line.chars() {
+            if symbol.is_whitespace() {
+                continue;
+            }
+            if let Some(digit) = symbol.to_digit(10) {
+                count = Some(
+                    count
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|count| count.checked_add(digit))
+                        .ok_or("run count is too large")?,
+                );
+                continue;
+            }
+
+            let run = count.take().unwrap_or(1);
+            match symbol {
+                'b' | '.' => col = col.saturating_add(run),
+                '$' => {
+                    row = row.saturating_add(run);
+                    col = 0;
+                }
+                '!' => return Ok(cells),
+                symbol if symbol.is_ascii_alphabetic() => {
+                    if row < height {
+                        let kept = run.min(width.saturating_sub(col));
+                        cells.extend((col..col + kept).map(|col| (row, col)));
+                    }
+                    col = col.saturating_add(run);
+                }
+                _ => return Err(format!("unexpected {symbol:?} in pattern")),
+            }
+        }
+    }
+    Ok(cells)
+}
+
+/// Return the rule of the universe as Golly writes it, including the size of the universe if it is bounded.
+///
+/// A universe that wraps around at its edges is a torus to Golly (`B3/S23:T64,64`), one with dead cells past
+/// its edges is a plane (`B3/S23:P64,64`), and an infinite universe is written as the rule alone.  Only
+/// life-like rules on square cells are written, as Golly's other rules and its hexagonal grid don't match
+/// the app's.
+pub fn golly_rule(universe: &Universe) -> Result<String, String> {
+    if universe.automaton() != Automaton::Life {
+        return Err(format!(
+            "{} can't be exported for Golly, only life-like rules",
+            universe.automaton().label()
+        ));
+    }
+    if universe.topology() != Topology::Square {
+        return Err("hexagonal universes can't be exported for Golly".into());
+    }
+
+    let (rule, width, height) = (universe.rule(), universe.width(), universe.height());
+    Ok(match universe.boundary() {
+        Boundary::Wrap => format!("{rule}:T{width},{height}"),
+        Boundary::Dead => format!("{rule}:P{width},{height}"),
+        Boundary::Infinite => format!("{rule}"),
+    })
+}
+
+/// Return the universe as RLE that Golly opens as it is: with the same rule, size, cells and generation,
+/// or an error if Golly can't run the universe (see `golly_rule()`).
+///
+/// Golly places the top left cell of a bounded universe at (-width / 2, -height / 2), so the pattern is
+/// written with that position in an `#CXRLE` line.
+pub fn write_rle(universe: &Universe, name: &str) -> Result<String, String> {
+    let rule = golly_rule(universe)?;
+    let mut rle = String::new();
+    let (width, height) = (universe.width(), universe.height());
+
+    if !name.is_empty() {
+        let _ = writeln!(rle, "#N {name}");
+    }
+    let _ = writeln!(
+        rle,
+        "#CXRLE Pos={},{} Gen={}",
+        -((width / 2) as i64),
+        -((height / 2) as i64),
+        universe.generation()
+    );
+    let _ = writeln!(rle, "x = {width}, y = {height}, rule = {rule}");
+    write_cells(&mut rle, universe);
+    Ok(rle)
+}
+
+/// Return the living cells of the universe as an RLE pattern of their own, cut down to their bounding box,
+/// with the rule of the universe.
+///
+/// Unlike `write_rle()`, the size and position of the universe are left out, so the pattern can be pasted
+/// into Golly or posted online as it is.
+pub fn write_pattern_rle(universe: &Universe) -> String {
+    let pattern = match universe.bounding_box() {
+        Some(bounds) => universe.extract_region(bounds),
+        None => Universe::with_size(0, 0),
+    };
+    let mut rle = format!(
+        "x = {}, y = {}, rule = {}\n",
+        pattern.width(),
+        pattern.height(),
+        universe.rule()
+    );
+    write_cells(&mut rle, &pattern);
+    rle
+}
+
+/// Write the cells of the universe as runs, wrapped to lines of at most MAX_LINE_LENGTH.
+fn write_cells(rle: &mut String, universe: &Universe) {
+    let (width, height) = (universe.width(), universe.height());
+
+    // Runs of (count, tag), leaving out dead cells at the ends of rows and empty rows at the end.
+    let mut runs: Vec<(u32, char)> = Vec::new();
+    let mut push = |count: u32, tag: char| match runs.last_mut() {
+        Some((run, last)) if *last == tag => *run += count,
+        _ => runs.push((count, tag)),
+    };
+    // The row the runs have reached, which each $ moves down one.
+    let mut current_row = 0;
+    for row in 0..height {
+        let Some(last_alive) = (0..width).rev().find(|col| universe.is_alive(row, *col)) else {
+            continue;
+        };
+        if row > current_row {
+            push(row - current_row, '$');
+            current_row = row;
+        }
+        for col in 0..=last_alive {
+            push(
+                1,
+                if universe.is_alive(row, col) {
+                    'o'
+                } else {
+                    'b'
+                },
+            );
+        }
+    }
+    push(1, '!');
+
+    let mut line = String::new();
+    for (count, tag) in runs {
+        let token = match count {
+            1 => format!("{tag}"),
+            count => format!("{count}{tag}"),
+        };
+        if line.len() + token.len() > MAX_LINE_LENGTH {
+            let _ = writeln!(rle, "{line}");
+            line.clear();
+        }
+        line.push_str(&token);
+    }
+    let _ = writeln!(rle, "{line}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return the (row, col) of every living cell of the universe, in row order.
+    fn living_cells(universe: &Universe) -> Vec<(u32, u32)> {
+        let mut cells: Vec<(u32, u32)> = universe
+            .get_living_cells()
+            .into_iter()
+            .map(|(x, y)| (y as u32, x as u32))
+            .collect();
+        cells.sort_unstable();
+        cells
+    }
+
+    /// Return the cells read from the RLE, in row order.
+    fn read_sorted(rle: &str, size: (u32, u32)) -> Vec<(u32, u32)> {
+        let mut cells = read_rle(rle, size).expect("written RLE should read back");
+        cells.sort_unstable();
+        cells
+    }
+
+    #[test]
+    fn reads_a_glider() {
+        let rle = "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!";
+
+        assert_eq!(
+            read_sorted(rle, (64, 64)),
+            [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn universe_round_trips() {
+        for (width, height) in [(64, 64), (100, 37), (1, 1)] {
+            let mut universe = Universe::with_size(width, height);
+            universe.random_with_seed(7);
+
+            let rle = write_rle(&universe, "random").unwrap();
+            assert!(rle.lines().all(|line| line.len() <= MAX_LINE_LENGTH));
+            assert_eq!(read_sorted(&rle, (height, width)), living_cells(&universe));
+        }
+    }
+
+    #[test]
+    fn pattern_round_trips_from_its_corner() {
+        let mut universe = Universe::with_size(20, 20);
+        universe.set_cells(&[(5, 6), (5, 7), (6, 6), (9, 12)]);
+
+        let rle = write_pattern_rle(&universe);
+        assert!(rle.starts_with("x = 7, y = 5, rule = B3/S23\n"));
+        assert_eq!(
+            read_sorted(&rle, (20, 20)),
+            [(0, 0), (0, 1), (1, 0), (4, 6)]
+        );
+    }
+
+    #[test]
+    fn empty_universe_round_trips() {
+        let universe = Universe::with_size(10, 10);
+
+        assert!(read_sorted(&write_rle(&universe, "").unwrap(), (10, 10)).is_empty());
+    }
+
+    #[test]
+    fn golly_rule_matches_the_boundary() {
+        let mut universe = Universe::with_size(64, 32);
+        assert_eq!(golly_rule(&universe).unwrap(), "B3/S23:T64,32");
+
+        universe.set_boundary(Boundary::Dead);
+        assert_eq!(golly_rule(&universe).unwrap(), "B3/S23:P64,32");
+
+        universe.set_boundary(Boundary::Infinite);
+        assert_eq!(golly_rule(&universe).unwrap(), "B3/S23");
+    }
+
+    #[test]
+    fn golly_rule_rejects_what_golly_runs_differently() {
+        let mut universe = Universe::with_size(64, 32);
+        universe.set_automaton(Automaton::Wireworld);
+        assert!(golly_rule(&universe).is_err());
+        assert!(write_rle(&universe, "").is_err());
+
+        let mut universe = Universe::with_size(64, 32);
+        universe.set_topology(Topology::Hex);
+        assert!(golly_rule(&universe).is_err());
+    }
+
+    #[test]
+    fn long_runs_are_cut_off_to_the_window() {
+        let rle = "x = 1, y = 1\n4000000000o$4000000000$2o!";
+
+        assert_eq!(read_sorted(rle, (2, 3)), [(0, 0), (0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn rejects_unexpected_symbols_and_huge_counts() {
+        assert!(read_rle("3o%!", (10, 10)).is_err());
+        assert!(read_rle("99999999999o!", (10, 10)).is_err());
+    }
+}
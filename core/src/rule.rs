@@ -0,0 +1,105 @@
+//! Rules deciding which cells are alive in the next generation, written in B/S notation (such as B3/S23).
+
+use alloc::{format, string::String};
+use core::{fmt, str::FromStr};
+
+/// A life-like rule: the numbers of living neighbors that bring a dead cell to life (birth), and the
+/// numbers that keep a living cell alive (survival).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rule {
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+
+impl Rule {
+    /// Conway's game of life, B3/S23:
+    /// * Any live cell with fewer than two live neighbours dies, as if caused by underpopulation.
+    /// * Any live cell with two or three live neighbours lives on to the next generation.
+    /// * Any live cell with more than three live neighbours dies, as if by overpopulation.
+    /// * Any dead cell with exactly three live neighbours becomes a live cell, as if by reproduction.
+    pub const CONWAY: Rule = Rule {
+        birth: neighbor_counts(&[3]),
+        survival: neighbor_counts(&[2, 3]),
+    };
+
+    /// Return true if a cell with the given number of living neighbors is alive in the next generation.
+    pub fn next_alive(&self, alive: bool, live_neighbors: u8) -> bool {
+        let counts = if alive { &self.survival } else { &self.birth };
+
+        counts
+            .get(live_neighbors as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::CONWAY
+    }
+}
+
+/// Return a table of which neighbor counts (0 to 8) are in the given list.
+const fn neighbor_counts(counts: &[u8]) -> [bool; 9] {
+    let mut table = [false; 9];
+    let mut i = 0;
+
+    while i < counts.len() {
+        table[counts[i] as usize] = true;
+        i += 1;
+    }
+    table
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let write_counts = |f: &mut fmt::Formatter<'_>, counts: &[bool; 9]| {
+            for (count, included) in counts.iter().enumerate() {
+                if *included {
+                    write!(f, "{count}")?;
+                }
+            }
+            Ok(())
+        };
+
+        write!(f, "B")?;
+        write_counts(f, &self.birth)?;
+        write!(f, "/S")?;
+        write_counts(f, &self.survival)
+    }
+}
+
+/// Read a rule in B/S notation, such as `B3/S23` for Conway's game of life or `B36/S23` for HighLife.
+///
+/// Letters may be either case, and either count list may be empty (`B2/S`).
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (birth, survival) = s
+            .trim()
+            .split_once('/')
+            .ok_or_else(|| format!("rule {s:?} is not in B/S notation"))?;
+
+        let read_counts = |counts: &str, letter: char| -> Result<[bool; 9], String> {
+            let digits = counts
+                .strip_prefix(letter)
+                .or_else(|| counts.strip_prefix(letter.to_ascii_lowercase()))
+                .ok_or_else(|| format!("expected {letter} at the start of {counts:?}"))?;
+            let mut table = [false; 9];
+
+            for digit in digits.chars() {
+                match digit.to_digit(10) {
+                    Some(count) if count <= 8 => table[count as usize] = true,
+                    _ => return Err(format!("{digit:?} is not a neighbor count from 0 to 8")),
+                }
+            }
+            Ok(table)
+        };
+
+        Ok(Rule {
+            birth: read_counts(birth, 'B')?,
+            survival: read_counts(survival, 'S')?,
+        })
+    }
+}
@@ -0,0 +1,85 @@
+//! Soup search: run many random soups, each from its own seed, and keep the seeds of the most interesting.
+//!
+//! A soup is interesting if it lives long before settling down (a methuselah), or settles into an
+//! oscillator with a period longer than the common blinkers.  Since a soup is made again from its seed, the
+//! seeds alone are enough to replay the soups found.
+
+use alloc::vec::Vec;
+
+use crate::{period::PeriodDetector, universe::Universe};
+
+/// What became of a soup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SoupOutcome {
+    /// The seed the soup was made from.
+    pub seed: u64,
+    /// The generation the soup became periodic at, or the generations it was run for if it never did.
+    pub lifespan: u64,
+    /// The period the soup settled into, or None if it was still changing when the run ended.
+    pub period: Option<u64>,
+    /// The living cells in the last generation run.
+    pub final_population: usize,
+}
+
+impl SoupOutcome {
+    /// Run a soup made from the seed with density percent of the cells alive, in a universe like the given
+    /// one (its size, rule, boundary and automaton), until it settles or has run max_generations.
+    pub fn run(universe: &Universe, seed: u64, density: u8, max_generations: u64) -> SoupOutcome {
+        let mut soup = universe.clone();
+        soup.random_with_seed_and_density(seed, density);
+        let mut detector = PeriodDetector::new();
+
+        let mut periodic = detector.observe(&soup);
+        while periodic.is_none() && soup.generation() < max_generations {
+            soup.tick();
+            periodic = detector.observe(&soup);
+        }
+
+        SoupOutcome {
+            seed,
+            lifespan: periodic.map_or(soup.generation(), |periodic| periodic.generation),
+            period: periodic.map(|periodic| periodic.period),
+            final_population: soup.population(),
+        }
+    }
+
+    /// Return how interesting the outcome is, to sort outcomes by: soups that settled into an oscillator
+    /// with a period over 2 come first, then soups by how long they lived.
+    pub fn interest(&self) -> (bool, u64) {
+        (self.period.is_some_and(|period| period > 2), self.lifespan)
+    }
+}
+
+/// The most interesting outcomes of the soups searched so far.
+#[derive(Clone, Debug, Default)]
+pub struct SoupSearch {
+    /// The soups searched.
+    pub searched: u64,
+    /// The most interesting outcomes, the most interesting first.
+    pub best: Vec<SoupOutcome>,
+    /// How many outcomes are kept.
+    kept: usize,
+}
+
+impl SoupSearch {
+    /// Start a search that keeps the given number of the most interesting outcomes.
+    pub fn new(kept: usize) -> SoupSearch {
+        SoupSearch {
+            searched: 0,
+            best: Vec::with_capacity(kept + 1),
+            kept,
+        }
+    }
+
+    /// Add the outcome of another soup, keeping it if it is among the most interesting.
+    pub fn record(&mut self, outcome: SoupOutcome) {
+        self.searched += 1;
+        let index = self
+            .best
+            .partition_point(|best| best.interest() >= outcome.interest());
+        if index < self.kept {
+            self.best.insert(index, outcome);
+            self.best.truncate(self.kept);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Rotating and flipping patterns, so a glider or spaceship can be placed heading the right way.
+//!
+//! Transforms apply to a pattern within its bounding box: the transformed pattern starts at the same top
+//! left corner, and a rotation by a quarter turn swaps its width and height.  Universes are transformed as a
+//! whole, with `Universe::transformed()` and the shorthands for each transform.
+
+use alloc::vec::Vec;
+
+/// A rotation or flip of a pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transform {
+    /// A quarter turn clockwise.
+    Rotate90,
+    /// A half turn.
+    Rotate180,
+    /// Mirrored left to right.
+    FlipH,
+    /// Mirrored top to bottom.
+    FlipV,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 4] = [
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::FlipH,
+        Transform::FlipV,
+    ];
+
+    /// Return the (height, width) of a pattern of the given (height, width) after the transform.
+    pub fn size(self, (height, width): (u32, u32)) -> (u32, u32) {
+        match self {
+            Transform::Rotate90 => (width, height),
+            _ => (height, width),
+        }
+    }
+
+    /// Return where the (row, col) cell of a pattern of the given (height, width) is after the transform.
+    pub fn cell(self, (row, col): (u32, u32), (height, width): (u32, u32)) -> (u32, u32) {
+        match self {
+            Transform::Rotate90 => (col, height - 1 - row),
+            Transform::Rotate180 => (height - 1 - row, width - 1 - col),
+            Transform::FlipH => (row, width - 1 - col),
+            Transform::FlipV => (height - 1 - row, col),
+        }
+    }
+
+    /// Return the (row, col) cells of a pattern after the transform, within the same bounding box corner as
+    /// the cells were relative to.
+    pub fn cells(self, cells: &[(u32, u32)]) -> Vec<(u32, u32)> {
+        let height = cells.iter().map(|(row, _)| row + 1).max().unwrap_or(0);
+        let width = cells.iter().map(|(_, col)| col + 1).max().unwrap_or(0);
+
+        cells
+            .iter()
+            .map(|cell| self.cell(*cell, (height, width)))
+            .collect()
+    }
+}
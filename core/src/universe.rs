@@ -0,0 +1,1813 @@
+//! Implements the game of life universe, which is represented by a grid of cells.
+use alloc::{format, string::String, vec, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use crate::{
+    ant::{Ant, Heading},
+    automaton::Automaton,
+    bits::BitGrid,
+    ltl::LtlRule,
+    random::{RandomSource, SeededRandom},
+    rule::Rule,
+    transform::Transform,
+};
+
+/// The number of cells in each row of a new universe: its width.
+pub const CELLS_PER_ROW: u32 = 64;
+/// The number of cells in each column of a new universe: its height.
+pub const CELLS_PER_COL: u32 = 64;
+/// The percent of cells alive, on average, in a randomized universe unless another density is given.
+pub const DEFAULT_DENSITY: u8 = 60;
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Dead = 0,
+    Alive = 1,
+}
+
+/// What lies past the edges of the universe.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Boundary {
+    /// The universe wraps around at its edges, like a torus: a glider that flies off one edge comes back
+    /// on the other.
+    #[default]
+    Wrap,
+    /// Past the edges, all cells are dead, so patterns that leave the universe are lost.
+    Dead,
+    /// The universe grows past its edges as patterns reach them, so a glider flies on forever.  The grid grows
+    /// by GROWTH cells at a time, and keeps every cell a pattern has reached, so memory and the time to tick
+    /// grow with the box around everything the pattern has reached.  Until it grows, the cells past the
+    /// edges are dead.
+    Infinite,
+}
+
+impl Boundary {
+    pub const ALL: [Boundary; 3] = [Boundary::Wrap, Boundary::Dead, Boundary::Infinite];
+}
+
+/// The cells an infinite universe grows by, past an edge that a pattern reaches.
+pub const GROWTH: u32 = 32;
+
+impl fmt::Display for Boundary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Boundary::Wrap => write!(f, "wrap"),
+            Boundary::Dead => write!(f, "dead"),
+            Boundary::Infinite => write!(f, "infinite"),
+        }
+    }
+}
+
+/// Read a boundary written by Display.
+impl FromStr for Boundary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "wrap" => Ok(Boundary::Wrap),
+            "dead" => Ok(Boundary::Dead),
+            "infinite" => Ok(Boundary::Infinite),
+            _ => Err(format!(
+                "{s:?} is not a boundary: expected wrap, dead or infinite"
+            )),
+        }
+    }
+}
+
+/// How the cells of the universe are arranged, which decides which cells are neighbors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Topology {
+    /// Square cells, each with eight neighbors.
+    #[default]
+    Square,
+    /// Hexagonal cells, each with six neighbors: the cells to the left and right, and two cells in each of
+    /// the rows above and below.  Odd rows are offset half a cell to the right, so the neighbors above and
+    /// below a cell in an even row are in its column and the column to the left, and for an odd row in its
+    /// column and the column to the right.
+    ///
+    /// A universe of odd height doesn't wrap evenly from its bottom row to its top row.
+    Hex,
+}
+
+impl Topology {
+    pub const ALL: [Topology; 2] = [Topology::Square, Topology::Hex];
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topology::Square => write!(f, "square"),
+            Topology::Hex => write!(f, "hex"),
+        }
+    }
+}
+
+/// Read a topology written by Display.
+impl FromStr for Topology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "square" => Ok(Topology::Square),
+            "hex" => Ok(Topology::Hex),
+            _ => Err(format!("{s:?} is not a topology: expected square or hex")),
+        }
+    }
+}
+
+/// Return the color of a QuadLife cell born with the given number of parents of each color: the color most
+/// parents have, or if no two have the same color, the first color none of them has.
+fn born_color(parents: [u8; 4]) -> u8 {
+    let most = parents.iter().copied().max().unwrap_or(0);
+    let color = match most {
+        0 | 1 => parents.iter().position(|count| *count == 0),
+        most => parents.iter().position(|count| *count == most),
+    };
+    color.map_or(1, |color| color as u8 + 1)
+}
+
+/// The (row, col) offsets of the neighbors of a square cell.
+const SQUARE_NEIGHBORS: [(i64, i64); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+/// The (row, col) offsets of the neighbors of a hexagonal cell in an even row, and in an odd row.
+const HEX_EVEN_ROW_NEIGHBORS: [(i64, i64); 6] =
+    [(-1, -1), (-1, 0), (0, -1), (0, 1), (1, -1), (1, 0)];
+const HEX_ODD_ROW_NEIGHBORS: [(i64, i64); 6] = [(-1, 0), (-1, 1), (0, -1), (0, 1), (1, 0), (1, 1)];
+
+/// Represents the state of all cells in the universe.
+///
+/// The cells are packed one bit to a cell, so large universes take little memory.
+pub struct Universe {
+    width: u32,
+    height: u32,
+    cells: BitGrid,
+    /// Where tick writes the next generation before swapping it with the cells, so ticking doesn't
+    /// allocate.  Its contents are left over from an earlier generation.
+    next: BitGrid,
+    /// The cells that changed since the changes were last taken, or None if every cell is to be read again.
+    changed: Option<BitGrid>,
+    generation: u64,
+    /// The cells born and the cells that died in the last tick.
+    births: usize,
+    deaths: usize,
+    /// If ages are tracked, the generations each cell has been alive, row by row: 1 in the generation it
+    /// is born, and 0 while it is dead.
+    ages: Option<Vec<u16>>,
+    /// The Zobrist hash of the living cells: the cell keys of every living cell XORed together, kept up to
+    /// date as cells change.
+    zobrist: u64,
+    automaton: Automaton,
+    /// For automata with more than two states, the state of each cell, row by row.  Cells in any state
+    /// but 0 are alive in the cells.  Empty for Life.
+    states: Vec<u8>,
+    /// The ants walking the universe, for Langton's Ant.  Empty for other automata.
+    ants: Vec<Ant>,
+    rule: Rule,
+    boundary: Boundary,
+    topology: Topology,
+    /// Where the top left cell is, as (row, col), in the unbounded plane of an infinite universe: how far the
+    /// universe has grown up and left.  (0, 0) until it grows.
+    origin: (i64, i64),
+}
+
+/// The buffer of the next generation is not cloned, since tick makes it again when needed.  Every cell of
+/// a clone counts as changed.  Cloning from a universe reuses the cells and states of the universe cloned
+/// into, so universes kept one after another (such as for history) don't allocate each time.
+impl Clone for Universe {
+    fn clone(&self) -> Self {
+        Universe {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.clone(),
+            next: BitGrid::default(),
+            changed: None,
+            generation: self.generation,
+            births: self.births,
+            deaths: self.deaths,
+            ages: self.ages.clone(),
+            zobrist: self.zobrist,
+            automaton: self.automaton,
+            states: self.states.clone(),
+            ants: self.ants.clone(),
+            rule: self.rule,
+            boundary: self.boundary,
+            topology: self.topology,
+            origin: self.origin,
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        (self.width, self.height) = (source.width, source.height);
+        self.cells.clone_from(&source.cells);
+        self.changed = None;
+        (self.generation, self.births, self.deaths) =
+            (source.generation, source.births, source.deaths);
+        self.ages.clone_from(&source.ages);
+        self.zobrist = source.zobrist;
+        self.automaton = source.automaton;
+        self.states.clone_from(&source.states);
+        self.ants.clone_from(&source.ants);
+        (self.rule, self.boundary, self.topology) = (source.rule, source.boundary, source.topology);
+        self.origin = source.origin;
+    }
+}
+
+/// Universes are equal if they are the same size and hold the same cells at the same generation, with the
+/// same rule and boundary.
+impl PartialEq for Universe {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.cells == other.cells
+            && self.generation == other.generation
+            && self.rule == other.rule
+            && self.boundary == other.boundary
+            && self.automaton == other.automaton
+            && self.states == other.states
+            && self.ants == other.ants
+            && self.topology == other.topology
+            && self.origin == other.origin
+    }
+}
+
+impl Eq for Universe {}
+
+/// The cells that changed since the changes of a universe were last taken, so a view of the universe can
+/// be updated without reading every cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Changes {
+    /// Every cell may have changed, and must be read again.
+    All,
+    /// The (row, col) of the cells born, and of the cells that died.
+    Cells {
+        births: Vec<(u32, u32)>,
+        deaths: Vec<(u32, u32)>,
+    },
+}
+
+/// The statistics of a universe at its current generation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub generation: u64,
+    /// The number of living cells.
+    pub population: usize,
+    /// The cells born in the last tick.
+    pub births: usize,
+    /// The cells that died in the last tick.
+    pub deaths: usize,
+}
+
+/// The smallest rectangle holding all living cells.  All bounds are inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub top: u32,
+    pub left: u32,
+    pub bottom: u32,
+    pub right: u32,
+}
+
+impl BoundingBox {
+    pub fn width(&self) -> u32 {
+        self.right - self.left + 1
+    }
+
+    pub fn height(&self) -> u32 {
+        self.bottom - self.top + 1
+    }
+}
+
+impl Default for Universe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Return the Zobrist key of the cell at row, col in the given state, or 0 for an empty cell.  A living
+/// cell of Life is in state 1.
+fn state_key(row: u32, col: u32, state: u8) -> u64 {
+    match state {
+        0 => 0,
+        state => zobrist_key(row, col).rotate_left(8 * (state as u32 - 1)),
+    }
+}
+
+/// Return the Zobrist key of the cell at row, col: a random looking number, the same on every platform
+/// and run, made by mixing the position with SplitMix64 rather than kept in a table.
+fn zobrist_key(row: u32, col: u32) -> u64 {
+    let mut key = ((row as u64) << 32 | col as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    key = (key ^ (key >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    key = (key ^ (key >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    key ^ (key >> 31)
+}
+
+impl Universe {
+    /// Create a new universe with the standard height and width.
+    pub fn new() -> Universe {
+        Universe::with_size(CELLS_PER_ROW, CELLS_PER_COL)
+    }
+
+    /// Create a new universe of dead cells with the given width and height.
+    pub fn with_size(width: u32, height: u32) -> Universe {
+        Universe {
+            width,
+            height,
+            cells: BitGrid::new(width, height),
+            next: BitGrid::default(),
+            changed: None,
+            generation: 0,
+            births: 0,
+            deaths: 0,
+            ages: None,
+            zobrist: 0,
+            automaton: Automaton::Life,
+            states: Vec::new(),
+            ants: Vec::new(),
+            rule: Rule::default(),
+            boundary: Boundary::default(),
+            topology: Topology::default(),
+            origin: (0, 0),
+        }
+    }
+
+    /// Randomly set the value of all cells in the universe, using the given seed, with DEFAULT_DENSITY
+    /// percent of the cells alive on average.
+    ///
+    /// The same seed always produces the same universe, on every platform.
+    pub fn random_with_seed(&mut self, seed: u64) {
+        self.random_with_seed_and_density(seed, DEFAULT_DENSITY);
+    }
+
+    /// Randomly set the value of all cells in the universe, using the given seed, with the given percent of
+    /// the cells alive on average.
+    pub fn random_with_seed_and_density(&mut self, seed: u64, density: u8) {
+        self.random_from(&mut SeededRandom::new(seed), density);
+    }
+
+    // Randomly set the value of all cells in the universe, using numbers from the given source.
+    //
+    // density percent of the cells on average are set to be alive.
+    pub fn random_from(&mut self, random: &mut impl RandomSource, density: u8) {
+        self.cells = BitGrid::new(self.width, self.height);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                self.cells
+                    .set(row, col, random.gen_range(100) < density as u32);
+            }
+        }
+        self.reset_states();
+        // QuadLife soups start with a random color in each cell, to show which regions descend from which.
+        if self.automaton == Automaton::QuadLife {
+            for state in self.states.iter_mut().filter(|state| **state != 0) {
+                *state = 1 + random.gen_range(4) as u8;
+            }
+        }
+        self.rehash();
+        self.changed = None;
+        self.generation = 0;
+        (self.births, self.deaths) = (0, 0);
+        self.reset_ages();
+    }
+
+    // Return every cell, row by row.
+    #[allow(unused)]
+    pub fn cells(&self) -> impl ExactSizeIterator<Item = Cell> + '_ {
+        let width = self.width.max(1) as usize;
+
+        (0..(self.width * self.height) as usize).map(move |idx| {
+            if self.cells.get((idx / width) as u32, (idx % width) as u32) {
+                Cell::Alive
+            } else {
+                Cell::Dead
+            }
+        })
+    }
+
+    // Return a Vector of tuples of the (x,y) coordinates of all cells that are currently alive.
+    //
+    // The cells are in column order: by column, and then by row within each column.
+    pub fn get_living_cells(&self) -> Vec<(i64, i64)> {
+        let mut cells: Vec<(i64, i64)> = self
+            .cells
+            .alive()
+            .map(|(row, col)| (col as i64, row as i64))
+            .collect();
+
+        cells.sort_unstable();
+        cells
+    }
+
+    /// Advance the universe one tick.
+    ///
+    /// Kill dead cells and spawn new ones depending the neigbor count of each cell and the rule.
+    pub fn tick(&mut self) {
+        if self.boundary == Boundary::Infinite {
+            self.grow_to_fit();
+        }
+        match self.automaton {
+            Automaton::Life if self.topology == Topology::Hex => return self.tick_counting(),
+            Automaton::Life => {}
+            Automaton::LangtonsAnt => return self.tick_ants(),
+            Automaton::LargerThanLife(rule) => return self.tick_larger_than_life(rule),
+            Automaton::QuadLife => return self.tick_colors(),
+            _ => return self.tick_states(),
+        }
+        // Every cell of the next generation is written, so what the buffer held before doesn't matter.
+        self.cells
+            .tick_into(&mut self.next, &self.rule, self.boundary);
+        core::mem::swap(&mut self.cells, &mut self.next);
+        self.record_tick();
+        self.generation += 1;
+    }
+
+    /// Advance the cells of an automaton with more than two states one tick.
+    fn tick_states(&mut self) {
+        let counted = self.automaton.counted_state();
+        let mut states = Vec::with_capacity(self.states.len());
+        // The buffer of the last generation but one, cleared, so ticking doesn't allocate.
+        let mut next = core::mem::take(&mut self.next);
+        next.reset(self.width, self.height);
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let mut count = 0;
+                for (delta_row, delta_col) in self.neighbor_offsets(row) {
+                    let neighbor = self.offset(row, col, *delta_row, *delta_col);
+                    if let Some((neighbor_row, neighbor_col)) = neighbor {
+                        count += (self.state(neighbor_row, neighbor_col) == counted) as u8;
+                    }
+                }
+                let state = self.automaton.next_state(self.state(row, col), count);
+                next.set(row, col, state != 0);
+                states.push(state);
+            }
+        }
+
+        self.next = next;
+        core::mem::swap(&mut self.cells, &mut self.next);
+        self.states = states;
+        self.record_tick();
+        self.rehash();
+        self.generation += 1;
+    }
+
+    /// Advance the colored cells of QuadLife one tick by the rule, coloring each cell born by the colors of its
+    /// parents (see Automaton::QuadLife).
+    fn tick_colors(&mut self) {
+        let mut states = Vec::with_capacity(self.states.len());
+        let mut next = core::mem::take(&mut self.next);
+        next.reset(self.width, self.height);
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                // The living neighbors of each color, from color 1.
+                let mut parents = [0u8; 4];
+                for (delta_row, delta_col) in self.neighbor_offsets(row) {
+                    let neighbor = self.offset(row, col, *delta_row, *delta_col);
+                    if let Some((neighbor_row, neighbor_col)) = neighbor {
+                        match self.state(neighbor_row, neighbor_col) {
+                            0 => {}
+                            color => parents[color as usize - 1] += 1,
+                        }
+                    }
+                }
+                let state = self.state(row, col);
+                let alive = self.rule.next_alive(state != 0, parents.iter().sum());
+                let state = match (alive, state) {
+                    (false, _) => 0,
+                    (true, 0) => born_color(parents),
+                    (true, color) => color,
+                };
+                next.set(row, col, state != 0);
+                states.push(state);
+            }
+        }
+
+        self.next = next;
+        core::mem::swap(&mut self.cells, &mut self.next);
+        self.states = states;
+        self.record_tick();
+        self.rehash();
+        self.generation += 1;
+    }
+
+    /// Advance the cells of Life one tick by counting the living neighbors of each cell, for topologies the
+    /// word at a time tick doesn't know.
+    fn tick_counting(&mut self) {
+        let mut next = core::mem::take(&mut self.next);
+        next.reset(self.width, self.height);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let alive = self.cells.get(row, col);
+                next.set(
+                    row,
+                    col,
+                    self.rule
+                        .next_alive(alive, self.live_neighbor_count(row, col)),
+                );
+            }
+        }
+
+        self.next = next;
+        core::mem::swap(&mut self.cells, &mut self.next);
+        self.record_tick();
+        self.generation += 1;
+    }
+
+    /// Advance the cells one tick by a Larger than Life rule.  The neighborhood is always square, whatever the
+    /// topology.
+    ///
+    /// The living cells of each neighborhood are counted from a table of the living cells above and left of
+    /// each cell, so a tick takes the same time whatever the radius.
+    fn tick_larger_than_life(&mut self, rule: LtlRule) {
+        let radius = rule.radius as i64;
+        // The universe with radius cells past each edge, as they are with the boundary.
+        let (padded_width, padded_height) = (
+            self.width as i64 + 2 * radius,
+            self.height as i64 + 2 * radius,
+        );
+        let stride = padded_width as usize + 1;
+        // The living cells above and left of each padded cell: sums[y * stride + x] counts the cells in rows
+        // before y and columns before x.
+        let mut sums = vec![0u32; stride * (padded_height as usize + 1)];
+        for y in 0..padded_height {
+            let mut row_sum = 0;
+            for x in 0..padded_width {
+                let cell = self.offset(0, 0, y - radius, x - radius);
+                row_sum += cell.is_some_and(|(row, col)| self.cells.get(row, col)) as u32;
+                let index = (y as usize + 1) * stride + x as usize + 1;
+                sums[index] = sums[index - stride] + row_sum;
+            }
+        }
+
+        let across = 2 * radius as usize + 1;
+        let mut next = core::mem::take(&mut self.next);
+        next.reset(self.width, self.height);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                // The neighborhood of the cell is padded rows row..row + across and columns col..col + across.
+                let (top, left) = (row as usize * stride, col as usize);
+                let bottom = (row as usize + across) * stride;
+                let count = sums[bottom + left + across] + sums[top + left]
+                    - sums[top + left + across]
+                    - sums[bottom + left];
+                let alive = self.cells.get(row, col);
+                let count = count - (alive && !rule.middle) as u32;
+                next.set(row, col, rule.next_alive(alive, count));
+            }
+        }
+
+        self.next = next;
+        core::mem::swap(&mut self.cells, &mut self.next);
+        self.record_tick();
+        self.generation += 1;
+    }
+
+    /// Move each ant one step, toggling the cell it leaves.  With dead edges, ants that walk off the universe
+    /// are gone.
+    fn tick_ants(&mut self) {
+        self.next.clone_from(&self.cells);
+
+        let mut ants = Vec::with_capacity(self.ants.len());
+        for mut ant in core::mem::take(&mut self.ants) {
+            ant.turn(self.cells.get(ant.row, ant.col));
+            self.cells.toggle(ant.row, ant.col);
+            let (delta_row, delta_col) = ant.heading.delta();
+            if let Some((row, col)) = self.offset(ant.row, ant.col, delta_row, delta_col) {
+                (ant.row, ant.col) = (row, col);
+                ants.push(ant);
+            }
+        }
+        self.ants = ants;
+
+        self.record_tick();
+        self.generation += 1;
+    }
+
+    /// Count the cells born and the cells that died in the tick just made, and add them to the changes, now
+    /// that next holds the generation before it.
+    fn record_tick(&mut self) {
+        self.record_changes();
+        if let Some(ages) = &mut self.ages {
+            let width = self.width as usize;
+            for (row, col) in self.cells.alive_in_either(&self.next) {
+                let age = &mut ages[row as usize * width + col as usize];
+                *age = match self.cells.get(row, col) {
+                    true => age.saturating_add(1),
+                    false => 0,
+                };
+            }
+        }
+    }
+
+    /// Count the cells born and the cells that died since the generation next holds, update the hash, and add
+    /// the cells to the changes.
+    fn record_changes(&mut self) {
+        (self.births, self.deaths) = self.cells.births_and_deaths(&self.next);
+        self.zobrist ^= self
+            .cells
+            .differences(&self.next)
+            .fold(0, |keys, (row, col)| keys ^ zobrist_key(row, col));
+        if let Some(changed) = &mut self.changed {
+            changed.toggle_differences(&self.cells, &self.next);
+        }
+    }
+
+    /// Return the cells born and the cells that died since the changes were last taken, and start
+    /// collecting changes again.
+    ///
+    /// A cell that changed and then changed back, such as a blinker cell over two ticks, is not reported.
+    /// After the universe is cleared, randomized, resized, or cloned, every cell counts as changed.
+    pub fn take_changes(&mut self) -> Changes {
+        let Some(changed) = &mut self.changed else {
+            self.changed = Some(BitGrid::new(self.width, self.height));
+            return Changes::All;
+        };
+        let (mut births, mut deaths) = (Vec::new(), Vec::new());
+
+        for (row, col) in changed.alive() {
+            if self.cells.get(row, col) {
+                births.push((row, col));
+            } else {
+                deaths.push((row, col));
+            }
+        }
+        changed.clear();
+        Changes::Cells { births, deaths }
+    }
+
+    /// Return the number of threads tick runs on.
+    pub fn tick_threads(&self) -> usize {
+        #[cfg(feature = "parallel")]
+        return crate::bits::tick_threads(self.width, self.height);
+        #[cfg(not(feature = "parallel"))]
+        return 1;
+    }
+
+    /// Return the number of ticks since the universe was last cleared or randomized.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Set the generation, such as when restoring a saved universe.
+    pub fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
+    }
+
+    /// Return the rule the universe advances by.
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Set the rule the universe advances by.
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    /// Return what lies past the edges of the universe.
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    /// Set what lies past the edges of the universe.
+    pub fn set_boundary(&mut self, boundary: Boundary) {
+        self.boundary = boundary;
+    }
+
+    /// Return where the top left cell is, as (row, col), in the unbounded plane of an infinite universe.  A
+    /// universe that grows up or left moves its cells down or right, and its origin up or left, so the row
+    /// and column of a cell plus the origin stay the same.
+    pub fn origin(&self) -> (i64, i64) {
+        self.origin
+    }
+
+    /// Grow an infinite universe past each edge that a pattern could reach next tick.
+    fn grow_to_fit(&mut self) {
+        // How far past its living cells a pattern can reach in one tick.
+        let reach = match self.automaton {
+            Automaton::LargerThanLife(rule) => rule.radius,
+            _ => 1,
+        };
+        let ants = self.ants.iter().map(|ant| (ant.row, ant.col));
+        let (mut top, mut left, mut bottom, mut right) = (u32::MAX, u32::MAX, 0, 0);
+        let mut any = false;
+        for (row, col) in self.cells.alive().chain(ants) {
+            (top, left) = (top.min(row), left.min(col));
+            (bottom, right) = (bottom.max(row), right.max(col));
+            any = true;
+        }
+        if !any {
+            return;
+        }
+
+        let growth = |near: bool| if near { GROWTH.max(reach) } else { 0 };
+        let grow_top = growth(top < reach);
+        let grow_left = growth(left < reach);
+        let grow_bottom = growth(bottom + reach >= self.height);
+        let grow_right = growth(right + reach >= self.width);
+        if grow_top + grow_left + grow_bottom + grow_right > 0 {
+            self.grow(grow_top, grow_left, grow_bottom, grow_right);
+        }
+    }
+
+    /// Add the given number of empty rows and columns past each edge, moving the cells down and right by the
+    /// rows and columns added at the top and left.
+    fn grow(&mut self, top: u32, left: u32, bottom: u32, right: u32) {
+        let (width, height) = (self.width + left + right, self.height + top + bottom);
+        let moved = |index: usize| {
+            let (row, col) = (index / self.width as usize, index % self.width as usize);
+            (row + top as usize) * width as usize + col + left as usize
+        };
+
+        let mut cells = BitGrid::new(width, height);
+        for (row, col) in self.cells.alive() {
+            cells.set(row + top, col + left, true);
+        }
+        if !self.states.is_empty() {
+            let mut states = vec![0; (width * height) as usize];
+            for (index, state) in self
+                .states
+                .iter()
+                .enumerate()
+                .filter(|(_, state)| **state != 0)
+            {
+                states[moved(index)] = *state;
+            }
+            self.states = states;
+        }
+        if let Some(ages) = &self.ages {
+            let mut moved_ages = vec![0; (width * height) as usize];
+            for (index, age) in ages.iter().enumerate().filter(|(_, age)| **age != 0) {
+                moved_ages[moved(index)] = *age;
+            }
+            self.ages = Some(moved_ages);
+        }
+        for ant in self.ants.iter_mut() {
+            (ant.row, ant.col) = (ant.row + top, ant.col + left);
+        }
+
+        (self.width, self.height) = (width, height);
+        self.cells = cells;
+        self.next = BitGrid::default();
+        self.changed = None;
+        self.origin = (self.origin.0 - top as i64, self.origin.1 - left as i64);
+        self.rehash();
+    }
+
+    /// Return how the cells of the universe are arranged.
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Set how the cells of the universe are arranged.  The cells keep their rows and columns.
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    /// Return the (row, col) offsets of the neighbors of a cell in the given row.
+    fn neighbor_offsets(&self, row: u32) -> &'static [(i64, i64)] {
+        match (self.topology, row % 2) {
+            (Topology::Square, _) => &SQUARE_NEIGHBORS,
+            (Topology::Hex, 0) => &HEX_EVEN_ROW_NEIGHBORS,
+            (Topology::Hex, _) => &HEX_ODD_ROW_NEIGHBORS,
+        }
+    }
+
+    /// Return the number of cells in each row.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Return the number of cells in each column.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Set the width of the universe.
+    ///
+    /// Resets all cells to the dead state, and takes away the ants left outside of the universe.
+    pub fn set_width(&mut self, width: u32) {
+        self.width = width;
+        self.clear();
+        self.ants.retain(|ant| ant.col < width);
+    }
+
+    /// Set the height of the universe.
+    ///
+    /// Resets all cells to the dead state, and takes away the ants left outside of the universe.
+    pub fn set_height(&mut self, height: u32) {
+        self.height = height;
+        self.clear();
+        self.ants.retain(|ant| ant.row < height);
+    }
+
+    // Clear all cells in the universe.
+    pub fn clear(&mut self) {
+        self.cells = BitGrid::new(self.width, self.height);
+        self.reset_states();
+        self.zobrist = 0;
+        self.changed = None;
+        self.generation = 0;
+        (self.births, self.deaths) = (0, 0);
+        self.reset_ages();
+    }
+
+    /// Set the cells at the given (row, column) coordinates alive.
+    ///
+    /// Coordinates outside of the universe are ignored.
+    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        for (row, col) in cells.iter().cloned() {
+            if row < self.height && col < self.width {
+                self.set_alive(row, col);
+            }
+        }
+    }
+
+    /// Set the (row, column) cells of a pattern alive, with the top left corner of the pattern at
+    /// origin_row, origin_col.
+    ///
+    /// Cells of the pattern past the edges wrap around to the other side of a universe that wraps around, and
+    /// are left out of a universe with dead edges.
+    pub fn stamp(&mut self, pattern: &[(u32, u32)], origin_row: u32, origin_col: u32) {
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+        for (row, col) in pattern.iter().cloned() {
+            if let Some((row, col)) = self.offset(origin_row, origin_col, row as i64, col as i64) {
+                self.set_alive(row, col);
+            }
+        }
+    }
+
+    /// Return the cells at the (row, column) offsets from the cell at row, column, such as the cells under a
+    /// brush.
+    ///
+    /// As with stamp, offsets past the edges wrap around to the other side of a universe that wraps around, and
+    /// are left out of a universe with dead edges.
+    pub fn region_cells(&self, row: u32, column: u32, offsets: &[(i64, i64)]) -> Vec<(u32, u32)> {
+        if self.width == 0 || self.height == 0 {
+            return Vec::new();
+        }
+        offsets
+            .iter()
+            .filter_map(|(delta_row, delta_col)| self.offset(row, column, *delta_row, *delta_col))
+            .collect()
+    }
+
+    /// Set the cells at the (row, column) offsets from the cell at row, column to the state, as region_cells
+    /// finds them.
+    pub fn set_region(&mut self, row: u32, column: u32, offsets: &[(i64, i64)], state: u8) {
+        for (row, col) in self.region_cells(row, column, offsets) {
+            self.set_state(row, col, state);
+        }
+    }
+
+    /// Return the cells within the bounds as a universe of their own, with the same rule and boundary.
+    ///
+    /// Bounds past the edges of the universe are cut off at the edges.
+    pub fn extract_region(&self, bounds: BoundingBox) -> Universe {
+        let height = (bounds.bottom + 1)
+            .min(self.height)
+            .saturating_sub(bounds.top);
+        let width = (bounds.right + 1)
+            .min(self.width)
+            .saturating_sub(bounds.left);
+        let mut region = Universe::with_size(width, height);
+        region.rule = self.rule;
+        region.boundary = self.boundary;
+
+        for row in 0..height {
+            for col in 0..width {
+                if self.cells.get(bounds.top + row, bounds.left + col) {
+                    region.cells.set(row, col, true);
+                }
+            }
+        }
+        region.set_automaton(self.automaton);
+        for row in 0..height {
+            for col in 0..width {
+                region.set_state(row, col, self.state(bounds.top + row, bounds.left + col));
+            }
+        }
+        region.rehash();
+        region
+    }
+
+    /// Copy every cell of the region, living or dead, into the universe, with the top left corner of the
+    /// region at origin_row, origin_col.
+    ///
+    /// As with stamp, cells of the region past the edges wrap around, or are left out if the edges are dead.
+    pub fn blit_region(&mut self, region: &Universe, origin_row: u32, origin_col: u32) {
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+        for row in 0..region.height {
+            for col in 0..region.width {
+                let Some((to_row, to_col)) =
+                    self.offset(origin_row, origin_col, row as i64, col as i64)
+                else {
+                    continue;
+                };
+                self.set_state(to_row, to_col, region.state(row, col));
+            }
+        }
+    }
+
+    /// Return the universe rotated or flipped, with the same rule and boundary.  A quarter turn swaps its
+    /// width and height.
+    pub fn transformed(&self, transform: Transform) -> Universe {
+        let size = (self.height, self.width);
+        let (height, width) = transform.size(size);
+        let mut transformed = Universe::with_size(width, height);
+        transformed.rule = self.rule;
+        transformed.boundary = self.boundary;
+
+        transformed.set_automaton(self.automaton);
+        for (row, col) in self.cells.alive() {
+            let (to_row, to_col) = transform.cell((row, col), size);
+            transformed.set_state(to_row, to_col, self.state(row, col));
+        }
+        transformed.rehash();
+        transformed
+    }
+
+    /// Return the universe turned a quarter turn clockwise.
+    pub fn rotate90(&self) -> Universe {
+        self.transformed(Transform::Rotate90)
+    }
+
+    /// Return the universe turned a half turn.
+    pub fn rotate180(&self) -> Universe {
+        self.transformed(Transform::Rotate180)
+    }
+
+    /// Return the universe mirrored left to right.
+    pub fn flip_h(&self) -> Universe {
+        self.transformed(Transform::FlipH)
+    }
+
+    /// Return the universe mirrored top to bottom.
+    pub fn flip_v(&self) -> Universe {
+        self.transformed(Transform::FlipV)
+    }
+
+    /// Set the cell at row, column, which is in the universe, alive.
+    fn set_alive(&mut self, row: u32, column: u32) {
+        if !self.cells.get(row, column) {
+            self.cells.set(row, column, true);
+            self.record_toggle(row, column);
+        }
+    }
+
+    /// Toggle the state of the cell at row, column.
+    ///
+    /// Coordinates outside of the universe are ignored.
+    pub fn toggle_cell(&mut self, row: u32, column: u32) {
+        if row >= self.height || column >= self.width {
+            return;
+        }
+        self.cells.toggle(row, column);
+        self.record_toggle(row, column);
+    }
+
+    /// Return the automaton the cells advance by.
+    pub fn automaton(&self) -> Automaton {
+        self.automaton
+    }
+
+    /// Set the automaton the cells advance by.
+    ///
+    /// Living cells become cells in state 1 of an automaton with more than two states, and cells in any
+    /// state but 0 become living cells of Life.
+    pub fn set_automaton(&mut self, automaton: Automaton) {
+        if automaton == self.automaton {
+            return;
+        }
+        self.automaton = automaton;
+        self.reset_states();
+        self.rehash();
+        self.ants.clear();
+        if automaton == Automaton::LangtonsAnt {
+            self.ants
+                .push(Ant::new(self.height / 2, self.width / 2, Heading::Up));
+        }
+    }
+
+    /// Return the ants walking the universe.
+    pub fn ants(&self) -> &[Ant] {
+        &self.ants
+    }
+
+    /// Replace the ants walking the universe, such as when restoring a saved universe.
+    ///
+    /// Only Langton's Ant has ants, so this does nothing for other automata.  Ants outside of the universe
+    /// are left out.
+    pub fn set_ants(&mut self, ants: &[Ant]) {
+        if self.automaton != Automaton::LangtonsAnt {
+            return;
+        }
+        self.ants = ants
+            .iter()
+            .filter(|ant| ant.row < self.height && ant.col < self.width)
+            .copied()
+            .collect();
+    }
+
+    /// Put an ant facing up on the cell at row, column, or take away the ant there if there is one.
+    ///
+    /// Only Langton's Ant has ants, so this does nothing for other automata.  Coordinates outside of the
+    /// universe are ignored.
+    pub fn toggle_ant(&mut self, row: u32, column: u32) {
+        if self.automaton != Automaton::LangtonsAnt || row >= self.height || column >= self.width {
+            return;
+        }
+        match self
+            .ants
+            .iter()
+            .position(|ant| (ant.row, ant.col) == (row, column))
+        {
+            Some(index) => {
+                self.ants.remove(index);
+            }
+            None => self.ants.push(Ant::new(row, column, Heading::Up)),
+        }
+    }
+
+    /// Return the state of the cell at row, column: 0 if it is empty (or dead), and 1 if it is alive in
+    /// Life.
+    pub fn state(&self, row: u32, column: u32) -> u8 {
+        match self.states.is_empty() {
+            true => self.cells.get(row, column) as u8,
+            false => self.states[row as usize * self.width as usize + column as usize],
+        }
+    }
+
+    /// Set the state of the cell at row, column.  States the automaton doesn't have are set to its last
+    /// state, and coordinates outside of the universe are ignored.
+    pub fn set_state(&mut self, row: u32, column: u32, state: u8) {
+        if row >= self.height || column >= self.width {
+            return;
+        }
+        let state = state.min(self.automaton.states() - 1);
+        let previous = self.state(row, column);
+        if state == previous {
+            return;
+        }
+        // Emptying a cell or filling an empty one toggles it, which leaves it in state 0 or 1.
+        if (previous == 0) != (state == 0) {
+            self.toggle_cell(row, column);
+        }
+        let toggled = self.state(row, column);
+        if toggled != state {
+            self.zobrist ^= state_key(row, column, toggled) ^ state_key(row, column, state);
+            self.states[row as usize * self.width as usize + column as usize] = state;
+        }
+    }
+
+    /// Set the state of every cell from the living cells: 1 for a living cell and 0 for a dead one, or no
+    /// states for Life.
+    fn reset_states(&mut self) {
+        self.states.clear();
+        if self.automaton.is_multi_state() {
+            self.states = self.cells().map(|cell| cell as u8).collect();
+        }
+    }
+
+    /// Add the toggled cell at row, column to the changes and the hash, and start its age again.
+    ///
+    /// A toggled cell of an automaton with more than two states is left in state 0 or 1.
+    fn record_toggle(&mut self, row: u32, column: u32) {
+        let index = row as usize * self.width as usize + column as usize;
+        let alive = self.cells.get(row, column) as u8;
+        match self.states.get_mut(index) {
+            Some(state) => {
+                self.zobrist ^= state_key(row, column, *state) ^ state_key(row, column, alive);
+                *state = alive;
+            }
+            None => self.zobrist ^= zobrist_key(row, column),
+        }
+        if let Some(changed) = &mut self.changed {
+            changed.toggle(row, column);
+        }
+        if let Some(ages) = &mut self.ages {
+            ages[row as usize * self.width as usize + column as usize] =
+                self.cells.get(row, column) as u16;
+        }
+    }
+
+    /// Track the age of each cell, or stop tracking ages.
+    ///
+    /// Ages are kept as tick advances the universe, so tracking them costs time each tick.  When tracking
+    /// starts, every living cell is as old as a cell just born.
+    pub fn track_ages(&mut self, on: bool) {
+        match (on, self.ages.is_some()) {
+            (true, false) => {
+                self.ages = Some(Vec::new());
+                self.reset_ages();
+            }
+            (false, true) => self.ages = None,
+            _ => {}
+        }
+    }
+
+    /// True if the age of each cell is tracked.
+    pub fn ages_tracked(&self) -> bool {
+        self.ages.is_some()
+    }
+
+    /// Return the generations the cell at row, column has been alive, counting the generation it was born
+    /// in, or 0 if it is dead, outside of the universe, or ages aren't tracked.
+    pub fn age(&self, row: u32, column: u32) -> u16 {
+        match &self.ages {
+            Some(ages) if row < self.height && column < self.width => {
+                ages[row as usize * self.width as usize + column as usize]
+            }
+            _ => 0,
+        }
+    }
+
+    /// If ages are tracked, make every living cell as old as a cell just born.
+    fn reset_ages(&mut self) {
+        let Some(ages) = &mut self.ages else {
+            return;
+        };
+        let width = self.width as usize;
+
+        ages.clear();
+        ages.resize(width * self.height as usize, 0);
+        for (row, col) in self.cells.alive() {
+            ages[row as usize * width + col as usize] = 1;
+        }
+    }
+
+    /// Stop tracking ages, and return the ages tracked, such as to carry them over to the universe an engine
+    /// advances this one to.
+    pub fn take_ages(&mut self) -> Option<Vec<u16>> {
+        self.ages.take()
+    }
+
+    /// Track ages, carrying them over from the ages taken from the universe this one was advanced from by the
+    /// given number of generations.
+    ///
+    /// Cells alive both then and now are taken to have lived through every generation in between, since
+    /// the generations in between aren't known.  Other living cells are as old as a cell just born.
+    pub fn carry_ages(&mut self, ages: Vec<u16>, generations: u64) {
+        self.ages = Some(Vec::new());
+        self.reset_ages();
+
+        let generations = generations.min(u16::MAX as u64) as u16;
+        let width = self.width as usize;
+        let Some(carried) = self
+            .ages
+            .as_mut()
+            .filter(|carried| carried.len() == ages.len())
+        else {
+            return;
+        };
+        for (row, col) in self.cells.alive() {
+            let index = row as usize * width + col as usize;
+            if ages[index] > 0 {
+                carried[index] = ages[index].saturating_add(generations);
+            }
+        }
+    }
+
+    /// Take the cells of the universe as another engine advanced it by the given number of generations, adding
+    /// the cells that changed to the changes (so only they are redrawn) and carrying ages over.
+    ///
+    /// The births and deaths are those of the whole advance.  A universe of another size is taken whole.
+    pub fn advance_to(&mut self, advanced: &Universe, generations: u64) {
+        if (advanced.width, advanced.height) != (self.width, self.height) {
+            self.clone_from(advanced);
+            return;
+        }
+        self.next.clone_from(&advanced.cells);
+        core::mem::swap(&mut self.cells, &mut self.next);
+        self.record_changes();
+        if let Some(ages) = self.ages.take() {
+            self.carry_ages(ages, generations);
+        }
+        self.generation = advanced.generation;
+    }
+
+    /// Return the number of living cells.
+    pub fn population(&self) -> usize {
+        self.cells.count_alive()
+    }
+
+    /// Return the generation and population, and the cells born and the cells that died in the last tick.
+    ///
+    /// Births and deaths are counted as tick advances the universe, so they are 0 after the universe is
+    /// cleared or randomized, and don't include cells toggled or set since the last tick.
+    pub fn stats(&self) -> Stats {
+        Stats {
+            generation: self.generation,
+            population: self.population(),
+            births: self.births,
+            deaths: self.deaths,
+        }
+    }
+
+    /// Return the living cells as an RLE pattern cut down to their bounding box, with a header giving its
+    /// size and the rule.  See `rle::write_pattern_rle()`.
+    pub fn to_rle(&self) -> String {
+        crate::rle::write_pattern_rle(self)
+    }
+
+    /// Return the smallest rectangle holding all living cells, or None if all cells are dead.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let mut bounding_box: Option<BoundingBox> = None;
+
+        for (row, col) in self.cells.alive() {
+            bounding_box = Some(match bounding_box {
+                None => BoundingBox {
+                    top: row,
+                    left: col,
+                    bottom: row,
+                    right: col,
+                },
+                Some(bounds) => BoundingBox {
+                    top: bounds.top.min(row),
+                    left: bounds.left.min(col),
+                    bottom: bounds.bottom.max(row),
+                    right: bounds.right.max(col),
+                },
+            });
+        }
+        bounding_box
+    }
+
+    /// Return the Zobrist hash of the living cells, which is kept up to date as the universe ticks and its
+    /// cells are changed, so it is returned without reading the cells.
+    ///
+    /// Universes with the same cells alive have the same hash, on every platform and run.  Unlike
+    /// board_hash(), the size of the universe is left out.
+    pub fn zobrist_hash(&self) -> u64 {
+        self.zobrist
+    }
+
+    /// Work out the Zobrist hash again from the cells, after they were set without recording each one.
+    fn rehash(&mut self) {
+        self.zobrist = self.cells.alive().fold(0, |hash, (row, col)| {
+            hash ^ state_key(row, col, self.state(row, col))
+        });
+    }
+
+    /// True if the other universe is the same size, with the same cells alive, whatever its generation.
+    ///
+    /// Universes with different hashes are told apart without reading their cells.
+    pub fn same_cells(&self, other: &Universe) -> bool {
+        self.zobrist == other.zobrist
+            && self.width == other.width
+            && self.height == other.height
+            && self.cells == other.cells
+    }
+
+    /// Return a hash of the size of the universe and the state of every cell (but not the generation).
+    ///
+    /// Uses FNV-1a, so the hash is the same on every platform and run.
+    pub fn board_hash(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let size = self
+            .width
+            .to_le_bytes()
+            .into_iter()
+            .chain(self.height.to_le_bytes());
+        // A byte for each cell, as when the cells were stored a byte each, so hashes stay the same.
+        let cells = self.cells().map(|cell| cell as u8);
+
+        size.chain(cells).fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        })
+    }
+
+    /// True if the universe holds exactly one cell for every row and column within its width and height.
+    pub fn cells_in_bounds(&self) -> bool {
+        self.cells.is_sized()
+    }
+
+    /// Return the number of bytes the cells take, including their ages if they are tracked and their states
+    /// if they have more than two.
+    pub fn cell_bytes(&self) -> usize {
+        let ages = self
+            .ages
+            .as_ref()
+            .map_or(0, |ages| ages.len() * core::mem::size_of::<u16>());
+        self.cells.bytes() + ages + self.states.len()
+    }
+
+    /// Return the next generation of the universe, leaving this universe unchanged.
+    pub fn tick_pure(&self) -> Universe {
+        let mut next = self.clone();
+        next.tick();
+        next
+    }
+
+    /// Return true if the cell at row, column is alive.
+    pub fn is_alive(&self, row: u32, column: u32) -> bool {
+        self.cells.get(row, column)
+    }
+
+    /// Return the (row, column) of the cells around the cell at row, column.
+    ///
+    /// If the universe wraps around at its edges, every cell has eight neighbors.  With dead edges, cells
+    /// at the edges have fewer.
+    pub fn neighbors(&self, row: u32, column: u32) -> Vec<(u32, u32)> {
+        let mut neighbors = Vec::with_capacity(8);
+
+        for delta_row in [-1, 0, 1] {
+            for delta_col in [-1, 0, 1] {
+                if delta_row == 0 && delta_col == 0 {
+                    continue;
+                }
+                if let Some(neighbor) = self.offset(row, column, delta_row, delta_col) {
+                    neighbors.push(neighbor);
+                }
+            }
+        }
+        neighbors
+    }
+
+    /// Return the (row, column) of the cell delta_row rows and delta_col columns from the cell at row,
+    /// column, or None if it is past the edges of a universe with dead edges.
+    fn offset(&self, row: u32, column: u32, delta_row: i64, delta_col: i64) -> Option<(u32, u32)> {
+        let (height, width) = (self.height as i64, self.width as i64);
+        let (offset_row, offset_col) = (row as i64 + delta_row, column as i64 + delta_col);
+
+        match self.boundary {
+            Boundary::Wrap => Some((
+                offset_row.rem_euclid(height) as u32,
+                offset_col.rem_euclid(width) as u32,
+            )),
+            Boundary::Dead | Boundary::Infinite => {
+                let inside = (0..height).contains(&offset_row) && (0..width).contains(&offset_col);
+                inside.then_some((offset_row as u32, offset_col as u32))
+            }
+        }
+    }
+
+    /// Return the count of live cells around cell at row, column.
+    ///
+    /// With dead edges, the cells past the edges count as dead.
+    pub fn live_neighbor_count(&self, row: u32, column: u32) -> u8 {
+        let mut count = 0;
+        for (delta_row, delta_col) in self.neighbor_offsets(row) {
+            let neighbor = self.offset(row, column, *delta_row, *delta_col);
+            if let Some((neighbor_row, neighbor_col)) = neighbor {
+                count += self.cells.get(neighbor_row, neighbor_col) as u8;
+            }
+        }
+        count
+    }
+}
+
+/// A universe is written as rows of `.` (dead) and `O` (alive) cells separated by newlines:
+/// ```text
+/// .....
+/// ..O..
+/// ..O..
+/// ..O..
+/// .....
+/// ```
+impl fmt::Display for Universe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let symbol = if self.is_alive(row, col) { 'O' } else { '.' };
+                write!(f, "{symbol}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Read a universe in the format written by Display, sized to fit the given rows.
+///
+/// Blank lines and surrounding whitespace are ignored, but every row must be the same width.
+impl FromStr for Universe {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<&str> = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        let width = rows
+            .first()
+            .ok_or("a universe needs at least one row")?
+            .chars()
+            .count();
+
+        let mut universe = Universe::with_size(width as u32, rows.len() as u32);
+        for (row_number, row) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(format!("row {row_number} is not {width} cells wide"));
+            }
+            for (col, symbol) in row.chars().enumerate() {
+                match symbol {
+                    '.' => {}
+                    'O' => universe.cells.set(row_number as u32, col as u32, true),
+                    _ => return Err(format!("unexpected cell {symbol:?} in row {row_number}")),
+                }
+            }
+        }
+        universe.rehash();
+
+        Ok(universe)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return the universe read from the rows of cells, which must be valid.
+    fn universe(rows: &str) -> Universe {
+        rows.parse().expect("test universes should be valid")
+    }
+
+    /// Return the next generation of the universe, counting the neighbors of each cell one at a time, as the
+    /// reference the tick is checked against.
+    fn naive_tick(universe: &Universe) -> Universe {
+        let mut next = Universe::with_size(universe.width(), universe.height());
+        next.set_rule(universe.rule());
+        next.set_boundary(universe.boundary());
+
+        for row in 0..universe.height() {
+            for col in 0..universe.width() {
+                let alive = universe.is_alive(row, col);
+                if universe
+                    .rule()
+                    .next_alive(alive, universe.live_neighbor_count(row, col))
+                {
+                    next.set_cells(&[(row, col)]);
+                }
+            }
+        }
+        next
+    }
+
+    #[test]
+    fn blinker_oscillates() {
+        let vertical = universe(
+            "
+            .....
+            ..O..
+            ..O..
+            ..O..
+            .....",
+        );
+        let horizontal = universe(
+            "
+            .....
+            .....
+            .OOO.
+            .....
+            .....",
+        );
+
+        let mut blinker = vertical.clone();
+        blinker.tick();
+        assert!(blinker.same_cells(&horizontal), "got\n{blinker}");
+        blinker.tick();
+        assert!(blinker.same_cells(&vertical), "got\n{blinker}");
+    }
+
+    #[test]
+    fn glider_translates() {
+        let mut glider = universe(
+            "
+            .O......
+            ..O.....
+            OOO.....
+            ........
+            ........
+            ........
+            ........
+            ........",
+        );
+        let moved = universe(
+            "
+            ........
+            ..O.....
+            ...O....
+            .OOO....
+            ........
+            ........
+            ........
+            ........",
+        );
+
+        for _ in 0..4 {
+            glider.tick();
+        }
+        assert!(glider.same_cells(&moved), "got\n{glider}");
+        assert_eq!(glider.generation(), 4);
+    }
+
+    #[test]
+    fn glider_wraps_around_the_edges() {
+        let start = universe(
+            "
+            .O......
+            ..O.....
+            OOO.....
+            ........
+            ........
+            ........
+            ........
+            ........",
+        );
+
+        // A glider moves one cell diagonally every 4 generations, so it is back after 8 * 4 on a torus of 8.
+        let mut glider = start.clone();
+        for _ in 0..32 {
+            glider.tick();
+        }
+        assert!(glider.same_cells(&start), "got\n{glider}");
+    }
+
+    #[test]
+    fn display_reads_back() {
+        let text = "..O\nOO.\n...\n.O.\n";
+
+        assert_eq!(universe(text).to_string(), text);
+    }
+
+    #[test]
+    fn from_str_rejects_ragged_rows_and_unknown_cells() {
+        assert!("...\n..\n".parse::<Universe>().is_err());
+        assert!("..x\n...\n".parse::<Universe>().is_err());
+        assert!("".parse::<Universe>().is_err());
+    }
+
+    #[test]
+    fn tick_matches_naive_reference() {
+        let sizes = [
+            (1, 1),
+            (2, 3),
+            (5, 7),
+            (64, 64),
+            (67, 45),
+            (130, 20),
+            (256, 260),
+        ];
+        let rules = ["B3/S23", "B36/S23", "B2/S", "B3678/S34678"];
+
+        for (width, height) in sizes {
+            for boundary in [Boundary::Wrap, Boundary::Dead] {
+                for rule in rules {
+                    let mut universe = Universe::with_size(width, height);
+                    universe.set_rule(rule.parse().unwrap());
+                    universe.set_boundary(boundary);
+                    universe.random_with_seed(width as u64 * 1000 + height as u64);
+
+                    for generation in 0..20 {
+                        let expected = naive_tick(&universe);
+                        universe.tick();
+                        assert!(
+                            universe.same_cells(&expected),
+                            "{width}x{height} {boundary} {rule} differs at generation {generation}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Return randomized universes of assorted sizes, each boundary that keeps the size, and several seeds.
+    fn random_universes() -> impl Iterator<Item = Universe> {
+        let sizes = [(1, 1), (3, 2), (17, 9), (64, 64), (100, 37)];
+        sizes.into_iter().flat_map(|(width, height)| {
+            [Boundary::Wrap, Boundary::Dead]
+                .into_iter()
+                .flat_map(move |boundary| {
+                    (0..4).map(move |seed| {
+                        let mut universe = Universe::with_size(width, height);
+                        universe.set_boundary(boundary);
+                        universe.random_with_seed(seed);
+                        universe
+                    })
+                })
+        })
+    }
+
+    /// Return the (row, col) of every living cell of the universe.
+    fn living_cells(universe: &Universe) -> Vec<(u32, u32)> {
+        (0..universe.height())
+            .flat_map(|row| (0..universe.width()).map(move |col| (row, col)))
+            .filter(|(row, col)| universe.is_alive(*row, *col))
+            .collect()
+    }
+
+    #[test]
+    fn population_counts_the_living_cells() {
+        for universe in random_universes() {
+            assert_eq!(universe.population(), living_cells(&universe).len());
+        }
+        assert_eq!(Universe::with_size(10, 10).population(), 0);
+    }
+
+    #[test]
+    fn bounding_box_is_the_tightest_box_around_the_living_cells() {
+        for universe in random_universes() {
+            let cells = living_cells(&universe);
+            let Some(bounds) = universe.bounding_box() else {
+                assert!(cells.is_empty());
+                continue;
+            };
+
+            assert!(cells.iter().all(|(row, col)| {
+                (bounds.top..=bounds.bottom).contains(row)
+                    && (bounds.left..=bounds.right).contains(col)
+            }));
+            // Each edge of the box touches a living cell.
+            assert!(cells.iter().any(|(row, _)| *row == bounds.top));
+            assert!(cells.iter().any(|(row, _)| *row == bounds.bottom));
+            assert!(cells.iter().any(|(_, col)| *col == bounds.left));
+            assert!(cells.iter().any(|(_, col)| *col == bounds.right));
+            assert_eq!(bounds.width(), bounds.right - bounds.left + 1);
+            assert_eq!(bounds.height(), bounds.bottom - bounds.top + 1);
+            assert!(bounds.width() <= universe.width() && bounds.height() <= universe.height());
+        }
+        assert_eq!(Universe::with_size(10, 10).bounding_box(), None);
+    }
+
+    #[test]
+    fn tick_pure_matches_tick_and_leaves_the_universe_unchanged() {
+        for universe in random_universes() {
+            let before = universe.clone();
+            let next = universe.tick_pure();
+
+            let mut ticked = universe.clone();
+            ticked.tick();
+            assert!(next.same_cells(&ticked));
+            assert_eq!(next.generation(), universe.generation() + 1);
+            assert!(universe.same_cells(&before));
+            assert_eq!(universe.generation(), before.generation());
+        }
+    }
+
+    #[test]
+    fn ticking_keeps_cells_in_bounds() {
+        for mut universe in random_universes() {
+            for _ in 0..10 {
+                universe.tick();
+                assert!(universe.cells_in_bounds());
+            }
+        }
+        for (width, height) in [(0, 0), (5, 0), (0, 5)] {
+            let mut universe = Universe::with_size(width, height);
+            universe.tick();
+            assert!(universe.cells_in_bounds());
+        }
+    }
+
+    #[test]
+    fn an_empty_universe_stays_empty() {
+        for boundary in [Boundary::Wrap, Boundary::Dead] {
+            let mut universe = Universe::with_size(20, 10);
+            universe.set_boundary(boundary);
+            for _ in 0..5 {
+                universe = universe.tick_pure();
+            }
+            assert_eq!(universe.population(), 0);
+            assert_eq!(universe.bounding_box(), None);
+        }
+    }
+
+    #[test]
+    fn brians_brain_leaves_nothing_of_the_generation_before_last() {
+        let mut universe = Universe::with_size(6, 6);
+        universe.set_automaton(Automaton::BriansBrain);
+        universe.set_cells(&[(2, 2), (2, 3)]);
+
+        // The pair fires the cells beside it and dies, and the second generation is written to the buffer
+        // the first one was ticked from.
+        let states = |universe: &Universe| {
+            (0..6)
+                .flat_map(|row| (0..6).map(move |col| (row, col)))
+                .map(|(row, col)| (row, col, universe.state(row, col)))
+                .filter(|(_, _, state)| *state != 0)
+                .collect::<Vec<_>>()
+        };
+        universe.tick();
+        assert_eq!(
+            states(&universe),
+            [
+                (1, 2, 1),
+                (1, 3, 1),
+                (2, 2, 2),
+                (2, 3, 2),
+                (3, 2, 1),
+                (3, 3, 1)
+            ]
+        );
+        universe.tick();
+        assert_eq!(
+            states(&universe),
+            [
+                (0, 2, 1),
+                (0, 3, 1),
+                (1, 2, 2),
+                (1, 3, 2),
+                (2, 1, 1),
+                (2, 4, 1),
+                (3, 2, 2),
+                (3, 3, 2),
+                (4, 2, 1),
+                (4, 3, 1)
+            ]
+        );
+        assert!(universe.cells_in_bounds());
+    }
+
+    #[test]
+    fn seeded_universes_have_about_the_density_asked_for() {
+        let mut seeded = Universe::with_size(100, 100);
+        for density in [0, 25, 60, 100] {
+            seeded.random_with_seed_and_density(2024, density);
+            let percent = living_cells(&seeded).len() / 100;
+            assert!(
+                percent.abs_diff(density as usize) <= 2,
+                "{percent}% alive at density {density}"
+            );
+        }
+    }
+
+    #[test]
+    fn clone_from_matches_clone_whatever_was_cloned_into() {
+        let mut into = Universe::with_size(7, 3);
+        into.set_automaton(Automaton::BriansBrain);
+        into.random_with_seed(1);
+
+        for universe in random_universes() {
+            into.clone_from(&universe);
+            assert!(into == universe);
+            assert_eq!(into.automaton(), universe.automaton());
+            assert_eq!(into.board_hash(), universe.board_hash());
+            assert!(into.cells_in_bounds());
+        }
+    }
+
+    #[test]
+    fn advance_to_takes_the_cells_and_reports_what_changed() {
+        for mut universe in random_universes() {
+            let mut advanced = universe.clone();
+            for _ in 0..5 {
+                advanced.tick();
+            }
+            let before = universe.clone();
+            universe.take_changes();
+
+            universe.advance_to(&advanced, 5);
+            assert!(universe.same_cells(&advanced));
+            assert_eq!(universe.generation(), advanced.generation());
+            assert_eq!(universe.zobrist_hash(), advanced.zobrist_hash());
+            let Changes::Cells { births, deaths } = universe.take_changes() else {
+                panic!("advancing should report the cells that changed");
+            };
+            let differing = before
+                .cells()
+                .zip(advanced.cells())
+                .filter(|(cell, other)| cell != other);
+            assert_eq!(births.len() + deaths.len(), differing.count());
+            for (row, col) in births {
+                assert!(!before.is_alive(row, col) && advanced.is_alive(row, col));
+            }
+            for (row, col) in deaths {
+                assert!(before.is_alive(row, col) && !advanced.is_alive(row, col));
+            }
+        }
+    }
+
+    #[test]
+    fn board_hash_follows_the_cells_and_size_only() {
+        for universe in random_universes() {
+            let mut copy = universe.clone();
+            copy.generation = 99;
+            assert_eq!(copy.board_hash(), universe.board_hash());
+
+            if let Some((row, col)) = living_cells(&universe).first().copied() {
+                copy.toggle_cell(row, col);
+                assert_ne!(copy.board_hash(), universe.board_hash());
+            }
+        }
+        assert_ne!(
+            Universe::with_size(4, 8).board_hash(),
+            Universe::with_size(8, 4).board_hash()
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! Analyzes the selected object: its period, size, speed, such as c/4 diagonal for a glider, and whether it
+//! is a still life, oscillator or spaceship.
+
+use dioxus::prelude::*;
+
+use crate::{
+    game_of_life::{
+        motion::{analyze, Kind},
+        universe::Universe,
+    },
+    selection::Selection,
+};
+
+/// Generations the selected object is run for before giving up on finding its period.
+const ANALYSIS_GENERATIONS: u32 = 256;
+
+/// A button that analyzes the object in the selection, and the result.
+#[component]
+pub fn SelectionAnalysis(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let selection = use_shared_state::<Selection>(cx).unwrap();
+    let report = use_state(cx, || None::<String>);
+
+    let result = report
+        .get()
+        .as_ref()
+        .map(|text| rsx! { span { font_family: "monospace", "{text}" } });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            button {
+                disabled: selection.read().0.is_none(),
+                onclick: move |_| {
+                    let universe = universe.read();
+                    let cells = selection.read().living_cells(&universe);
+
+                    let text = match analyze(&cells, &universe, ANALYSIS_GENERATIONS) {
+                        Some(analysis) => {
+                            let kind = analysis.kind();
+                            let mut text = format!(
+                                "{kind}, period {}, {}x{} bounding box",
+                                analysis.motion.period,
+                                analysis.width,
+                                analysis.height
+                            );
+                            if kind == Kind::Spaceship {
+                                let (rows, cols) = analysis.motion.displacement;
+                                text += &format!(
+                                    ", moving {}: {rows} rows and {cols} columns every period",
+                                    analysis.motion
+                                );
+                            }
+                            text
+                        }
+                        None if cells.is_empty() => "No living cells are selected".to_string(),
+                        None => format!("The selection died or didn't repeat within {ANALYSIS_GENERATIONS} generations"),
+                    };
+                    report.set(Some(text));
+                },
+                "Analyze selection"
+            }
+            result
+        }
+    }
+}
@@ -2,61 +2,79 @@
 
 use dioxus::prelude::*;
 
-/// A custom Dioxus hook that abstracts the request_animation_frame() and cancel_animation_frame() DOM calls.
+/// A custom Dioxus hook that abstracts the platform's request_frame() and cancel_frame() calls.
 ///
 /// Allows the caller to create a use_effect() which watches the frame_id,
 /// which can then take an action each time a frame is advanced.
 ///
-/// Returns two UseState variables: frame_running and frame_id.
+/// Returns three UseState variables: frame_running, frame_id and frame_time.
 /// * frame_running is true if frames are advancing.
 /// * frame_id is incremented each time a new frame is run.
+/// * frame_time is the time of the latest frame in milliseconds, as given to the frame by the browser, so
+///   it is measured the same way on web and desktop, and doesn't depend on when the app gets to it.
 ///
 /// If frame_running is set to true, frames advance.
-/// If frame_running is set to false, frames stop advancing.
+/// If frame_running is set to false, frames stop advancing, and no more frames are requested, so a paused
+/// app does no work each frame.
 #[cfg(feature = "web")]
-pub fn use_animation_frame(cx: Scope, initial_state: bool) -> (&UseState<bool>, &UseState<i32>) {
+pub fn use_animation_frame(
+    cx: Scope,
+    initial_state: bool,
+) -> (&UseState<bool>, &UseState<i32>, &UseState<f64>) {
     use std::cell::RefCell;
     use std::rc::Rc;
 
     use wasm_bindgen::prelude::Closure;
 
-    use crate::websys_utils::*;
+    use crate::platform::{cancel_frame, request_frame};
 
     let frame_running = use_state(cx, || initial_state);
     let cancel_id = use_state(cx, || None::<i32>);
     let frame_id = use_state(cx, || 0_i32);
+    let frame_time = use_state(cx, || 0_f64);
 
     use_effect(cx, (frame_running,), |(frame_running,)| {
-        to_owned![cancel_id, frame_id, frame_running];
+        to_owned![cancel_id, frame_id, frame_time, frame_running];
 
-        // frame_loop_holder holds a closure that is passed to request_animation_frame().
+        // frame_loop_holder holds a closure that is passed to request_frame().
         // This closure is called each time an animation frame completes.
         let frame_loop_holder = Rc::new(RefCell::new(None));
         let frame_loop_holder_clone = frame_loop_holder.clone();
 
         let cancel_id_clone = cancel_id.clone();
-        *frame_loop_holder.borrow_mut() = Some(Closure::<dyn FnMut()>::new(move || {
-            let new_id =
-                request_animation_frame(frame_loop_holder_clone.borrow().as_ref().unwrap());
+        let frame_running_clone = frame_running.clone();
+        *frame_loop_holder.borrow_mut() = Some(Closure::<dyn FnMut(f64)>::new(move |timestamp| {
+            // A frame requested just before frames were stopped ends the loop, rather than requesting
+            // another frame.
+            if !*frame_running_clone.current() {
+                cancel_id_clone.set(None);
+                return;
+            }
+            let new_id = request_frame(frame_loop_holder_clone.borrow().as_ref().unwrap());
             cancel_id_clone.set(Some(new_id));
 
+            frame_time.set(timestamp);
             frame_id.with_mut(|id| {
                 *id = id.wrapping_add(1);
             })
         }));
 
         async move {
+            // The id of the frame last requested, which changes every frame, so the current value is
+            // needed rather than the value when this effect was created.
+            let requested = cancel_id.current().is_some();
+
             // If we are requested to run, but we are not running, run
-            if *frame_running.get() && cancel_id.get().is_none() {
-                let new_id = request_animation_frame(frame_loop_holder.borrow().as_ref().unwrap());
+            if *frame_running.get() && !requested {
+                let new_id = request_frame(frame_loop_holder.borrow().as_ref().unwrap());
                 cancel_id.set(Some(new_id));
             }
 
             // If we are requested to stop, but we are running, cancel
-            if !*frame_running.get() && cancel_id.get().is_some() {
+            if !*frame_running.get() && requested {
                 cancel_id.with_mut(|maybe_id| {
                     if let Some(id) = maybe_id {
-                        cancel_animation_frame(*id);
+                        cancel_frame(*id);
                         *maybe_id = None;
                     }
                 });
@@ -64,7 +82,7 @@ pub fn use_animation_frame(cx: Scope, initial_state: bool) -> (&UseState<bool>,
         }
     });
 
-    (frame_running, frame_id)
+    (frame_running, frame_id, frame_time)
 }
 
 /// The code of use_animation_frame() is different enough for the desktop version that I made it a separate
@@ -81,47 +99,61 @@ pub fn use_animation_frame(cx: Scope, initial_state: bool) -> (&UseState<bool>,
 /// The two pieces of code we need to set up are one to call window.requestAnimationFrame() recursively and update
 /// the frame_id each time it is called, and another to call window.cancelAnimationFrame() to stop the above.
 #[cfg(feature = "desktop")]
-pub fn use_animation_frame(cx: Scope, initial_state: bool) -> (&UseState<bool>, &UseState<i32>) {
+pub fn use_animation_frame(
+    cx: Scope,
+    initial_state: bool,
+) -> (&UseState<bool>, &UseState<i32>, &UseState<f64>) {
+    use crate::platform::{cancel_frame, request_frame};
+
     let frame_running = use_state(cx, || initial_state);
     let cancel_id = use_state(cx, || None::<i32>);
     let frame_id = use_state(cx, || 0_i32);
+    let frame_time = use_state(cx, || 0_f64);
 
     // Use eval returns a function that can spawn eval instances
     let create_eval = use_eval(cx);
     let run_eval = use_state(cx, || None::<UseEval>);
 
     // get_new_cancel_id is a future that waits for a new cancel_id from the javascript side (the run_eval command).
+    // While frames are stopped there is no run_eval, and the future finishes with None, so no frame is counted.
     let get_new_cancel_id = use_future(cx, (cancel_id, run_eval), |(_, run_eval)| {
         to_owned![run_eval];
         async move {
             // You can receive any message from JavaScript with the recv method
-            if let Some(run_eval) = run_eval.get() {
-                run_eval.recv().await.unwrap()
-            } else {
-                0.into()
+            match run_eval.get() {
+                Some(run_eval) => run_eval.recv().await.ok(),
+                None => None,
             }
         }
     });
 
-    // If we have a new cancel_id, save it to cancel_id and increment the frame_id.
-    match get_new_cancel_id.value() {
-        Some(remote_cancel_id) => {
-            if let Ok(new_cancel_id) = remote_cancel_id.to_string().trim().parse() {
-                if Some(new_cancel_id) != *cancel_id.get() {
-                    cancel_id.with_mut(|cancel_id| {
-                        *cancel_id = Some(new_cancel_id);
-                    });
-
-                    frame_id.with_mut(|id| {
-                        *id = id.wrapping_add(1);
-                    })
-                }
-            } else {
-                println!("Could not convert javascript cancel_id value to number: {}", remote_cancel_id);
+    // If we have a new cancel_id, save it to cancel_id, save the time of the frame, and increment the frame_id.
+    // There is none while stopped, or while waiting for the next frame.
+    //
+    // The javascript side sends [cancel_id, frame_time].
+    if let Some(Some(remote_frame)) = get_new_cancel_id.value() {
+        let new_cancel_id = remote_frame.get(0).and_then(|id| id.as_i64());
+        let new_frame_time = remote_frame.get(1).and_then(|time| time.as_f64());
+
+        if let (Some(new_cancel_id), Some(new_frame_time)) = (new_cancel_id, new_frame_time) {
+            let new_cancel_id = new_cancel_id as i32;
+            if Some(new_cancel_id) != *cancel_id.get() {
+                cancel_id.with_mut(|cancel_id| {
+                    *cancel_id = Some(new_cancel_id);
+                });
+
+                frame_time.set(new_frame_time);
+                frame_id.with_mut(|id| {
+                    *id = id.wrapping_add(1);
+                })
             }
+        } else {
+            println!(
+                "Could not convert javascript frame to [cancel_id, frame_time]: {}",
+                remote_frame
+            );
         }
-        _ => println!("Failed to get cancel_id from javascript"),
-    };
+    }
 
     // TODO: use use_on_create to run create_eval() so that we only compile that code once.
     // use_on_create(cx, future)
@@ -133,30 +165,18 @@ pub fn use_animation_frame(cx: Scope, initial_state: bool) -> (&UseState<bool>,
         async move {
             // If we are requested to run, and we are not running, run
             if *frame_running.get() && run_eval.is_none() {
-                run_eval.with_mut(|run_eval| {
-                    *run_eval = Some(
-                        create_eval(r#"
-                            function gotFrame(last_render_ms) {
-                                dioxus.send(window.requestAnimationFrame(gotFrame));
-                            }
-                            gotFrame(0);
-                        "#)
-                        .unwrap(),
-                    );
-                });
+                run_eval.set(Some(request_frame(&create_eval)));
             }
 
             // If we are requested to stop, and we are running, stop
             if !*frame_running.get() {
                 if let Some(cancel_id) = *cancel_id.get() {
-                    let cancel_str = format!("window.cancelAnimationFrame({cancel_id});");
-
-                    create_eval(&cancel_str).unwrap();
+                    cancel_frame(&create_eval, cancel_id);
                     run_eval.set(None);
                 }
             }
         }
     });
 
-    (frame_running, frame_id)   
-}
\ No newline at end of file
+    (frame_running, frame_id, frame_time)
+}
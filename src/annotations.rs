@@ -0,0 +1,201 @@
+//! Annotations: text labels, arrows and highlight boxes drawn over the grid, for teaching material and
+//! pattern documentation.
+//!
+//! Annotations are anchored to cells, but aren't simulated: they stay where they were placed as the
+//! universe changes.  They are saved with snapshots.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{game_of_life::CellLayer, selection::Selection};
+
+const ANNOTATION_COLOR: &str = "darkorange";
+/// The height of label text, in cells.
+const LABEL_SIZE: f64 = 1.5;
+/// The length and half width of an arrow head, in cells.
+const ARROW_HEAD: (f64, f64) = (1.0, 0.5);
+
+/// A note placed over the grid.  Cells are given as (row, column).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Annotation {
+    /// Text written from the cell, with its baseline along the bottom of the cell.
+    Label { cell: (u32, u32), text: String },
+    /// An arrow from the center of one cell to the center of another.
+    Arrow { from: (u32, u32), to: (u32, u32) },
+    /// A box around the rectangle of cells with the given corners.
+    Highlight {
+        top_left: (u32, u32),
+        bottom_right: (u32, u32),
+    },
+}
+
+impl Annotation {
+    /// A short description, for the list of annotations.
+    fn describe(&self) -> String {
+        match self {
+            Annotation::Label { cell, text } => format!("\"{text}\" at {cell:?}"),
+            Annotation::Arrow { from, to } => format!("Arrow {from:?} to {to:?}"),
+            Annotation::Highlight {
+                top_left,
+                bottom_right,
+            } => format!("Box {top_left:?} to {bottom_right:?}"),
+        }
+    }
+}
+
+/// The annotations over the grid, in the order they were added.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Annotations(pub Vec<Annotation>);
+
+/// Return the corners of the arrow head of an arrow between the (x, y) points, pointing at `to`.
+fn arrow_head(from: (f64, f64), to: (f64, f64)) -> [(f64, f64); 3] {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = dx.hypot(dy).max(f64::EPSILON);
+    let (along, across) = ((dx / length, dy / length), (-dy / length, dx / length));
+    let (head_length, half_width) = ARROW_HEAD;
+    let base = (to.0 - along.0 * head_length, to.1 - along.1 * head_length);
+
+    [
+        to,
+        (
+            base.0 + across.0 * half_width,
+            base.1 + across.1 * half_width,
+        ),
+        (
+            base.0 - across.0 * half_width,
+            base.1 - across.1 * half_width,
+        ),
+    ]
+}
+
+/// Controls to annotate the selected cells, and to remove annotations.
+#[component]
+pub fn AnnotationPanel(cx: Scope) -> Element {
+    let annotations = use_shared_state::<Annotations>(cx).unwrap();
+    let selection = use_shared_state::<Selection>(cx).unwrap();
+    let text = use_state(cx, String::new);
+
+    let selected = selection.read().0;
+    let add = move |annotation: Annotation| annotations.write().0.push(annotation);
+
+    let current = annotations.read().clone();
+    let list = current.0.iter().enumerate().map(|(idx, annotation)| {
+        rsx! {
+            span { key: "{idx}",
+                "{annotation.describe()} "
+                button { onclick: move |_| { annotations.write().0.remove(idx); }, "Remove" }
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            span { "Annotate selection:" }
+            input {
+                r#type: "text",
+                placeholder: "Label text",
+                value: "{text}",
+                oninput: move |event| text.set(event.value.clone()),
+            }
+            button {
+                disabled: selected.is_none() || text.trim().is_empty(),
+                onclick: move |_| {
+                    if let Some(bounds) = selected {
+                        add(Annotation::Label { cell: (bounds.top, bounds.left), text: text.trim().to_string() });
+                        text.set(String::new());
+                    }
+                },
+                "Label"
+            }
+            button {
+                disabled: selected.is_none(),
+                title: "An arrow from the top left to the bottom right of the selection",
+                onclick: move |_| {
+                    if let Some(bounds) = selected {
+                        add(Annotation::Arrow { from: (bounds.top, bounds.left), to: (bounds.bottom, bounds.right) });
+                    }
+                },
+                "Arrow"
+            }
+            button {
+                disabled: selected.is_none(),
+                onclick: move |_| {
+                    if let Some(bounds) = selected {
+                        add(Annotation::Highlight {
+                            top_left: (bounds.top, bounds.left),
+                            bottom_right: (bounds.bottom, bounds.right),
+                        });
+                    }
+                },
+                "Highlight"
+            }
+            button {
+                disabled: current.0.is_empty(),
+                onclick: move |_| annotations.write().0.clear(),
+                "Clear annotations"
+            }
+        }
+        div { display: "flex", justify_content: "center", flex_wrap: "wrap", gap: "8px", list }
+    }
+}
+
+/// Draw the annotations over the grid.
+#[component]
+pub fn AnnotationOverlay(cx: Scope) -> Element {
+    let annotations = use_shared_state::<Annotations>(cx).unwrap();
+
+    let annotations = annotations.read();
+    let drawn = annotations.0.iter().enumerate().map(|(idx, annotation)| {
+        let center = |(row, col): (u32, u32)| (col as f64 + 0.5, row as f64 + 0.5);
+
+        match annotation {
+            Annotation::Label { cell, text } => rsx! {
+                text {
+                    key: "{idx}",
+                    x: cell.1 as f64,
+                    y: (cell.0 + 1) as f64,
+                    font_size: LABEL_SIZE,
+                    font_family: "Helvetica",
+                    fill: ANNOTATION_COLOR,
+                    "{text}"
+                }
+            },
+            Annotation::Arrow { from, to } => {
+                let (from, to) = (center(*from), center(*to));
+                let points = arrow_head(from, to)
+                    .iter()
+                    .map(|(x, y)| format!("{x},{y}"))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                rsx! {
+                    g { key: "{idx}",
+                        line { x1: from.0, y1: from.1, x2: to.0, y2: to.1, stroke: ANNOTATION_COLOR, stroke_width: 0.3 }
+                        polygon { points: "{points}", fill: ANNOTATION_COLOR }
+                    }
+                }
+            }
+            Annotation::Highlight {
+                top_left,
+                bottom_right,
+            } => rsx! {
+                rect {
+                    key: "{idx}",
+                    x: top_left.1 as f64,
+                    y: top_left.0 as f64,
+                    width: (bottom_right.1 - top_left.1 + 1) as f64,
+                    height: (bottom_right.0 - top_left.0 + 1) as f64,
+                    fill: "none",
+                    stroke: ANNOTATION_COLOR,
+                    stroke_width: 0.3,
+                }
+            },
+        }
+    });
+
+    render! {
+        CellLayer {
+            drawn
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Attract mode: a screensaver that takes over when the app has been left idle.
+//!
+//! It cycles through random symmetric soups and patterns from the library, fading the grid out and back in
+//! between them.  Any input ends attract mode and restores the app as it was before.
+
+use serde::{Deserialize, Serialize};
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::{
+        centered,
+        patterns::LIBRARY,
+        random::{random_seed, symmetric_soup, RandomSource, SeededRandom},
+        universe::Universe,
+    },
+    platform::{now_ms, sleep_ms},
+    storage,
+};
+
+const ATTRACT_KEY: &str = "attract.json";
+
+/// The idle times, in minutes, attract mode can start after.
+const IDLE_MINUTES: [u32; 5] = [1, 2, 5, 10, 30];
+/// How often to check for the app becoming idle, or input ending attract mode.
+const POLL_MS: u32 = 250;
+/// How long each soup or pattern is shown.
+const SHOW_MS: f64 = 20_000.0;
+/// How long the grid takes to fade out or in.
+pub const FADE_MS: u32 = 1_000;
+/// The width and height of the soups shown.
+const SOUP_SIZE: u32 = 16;
+
+/// The time of the latest input, from now_ms().
+pub struct LastInput(pub f64);
+
+impl Default for LastInput {
+    fn default() -> Self {
+        LastInput(now_ms())
+    }
+}
+
+/// Record that there was input, ending attract mode if it is showing.
+///
+/// Doesn't redraw anything, so it can be called on every mouse move.
+pub fn note_input(last_input: &UseSharedState<LastInput>) {
+    *last_input.write_silent() = LastInput(now_ms());
+}
+
+/// True while the grid is faded out between attract mode shows.
+#[derive(Default)]
+pub struct AttractFade(pub bool);
+
+/// Whether attract mode is on, and how long the app must be idle before it starts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct AttractSettings {
+    enabled: bool,
+    idle_minutes: u32,
+}
+
+impl Default for AttractSettings {
+    fn default() -> Self {
+        AttractSettings {
+            enabled: false,
+            idle_minutes: 5,
+        }
+    }
+}
+
+impl AttractSettings {
+    /// Return the settings chosen in an earlier session, or the default settings.
+    fn stored() -> AttractSettings {
+        storage::load(ATTRACT_KEY)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Remember the settings for later sessions.
+    fn store(&self) {
+        storage::store(
+            ATTRACT_KEY,
+            &serde_json::to_string(self).expect("attract settings should serialize"),
+        );
+    }
+}
+
+/// Return the cells of the next show: a symmetric soup or a library pattern, centered in the universe.
+fn next_show(random: &mut impl RandomSource, universe: &Universe) -> Vec<(u32, u32)> {
+    let cells = match random.gen_range(LIBRARY.len() as u32 + 1) as usize {
+        idx if idx < LIBRARY.len() => LIBRARY[idx].cells(),
+        _ => symmetric_soup(random, SOUP_SIZE, SOUP_SIZE),
+    };
+
+    centered(cells, universe)
+}
+
+/// Controls to turn attract mode on and off and choose the idle time, and the loop that runs it.
+#[component]
+pub fn AttractMode(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let last_input = use_shared_state::<LastInput>(cx).unwrap();
+    let fade = use_shared_state::<AttractFade>(cx).unwrap();
+    let settings = use_state(cx, AttractSettings::stored);
+
+    use_future(cx, (), |_| {
+        to_owned![controls, universe, last_input, fade, settings];
+        async move {
+            let mut random = SeededRandom::new(random_seed());
+            // The state of the app before attract mode started, and when it started.
+            let mut showing = None;
+            let mut next_show_ms = 0.0;
+
+            loop {
+                sleep_ms(POLL_MS).await;
+                let now = now_ms();
+                let last = last_input.read().0;
+
+                match &showing {
+                    None => {
+                        let settings = *settings.current();
+                        let idle_ms = settings.idle_minutes as f64 * 60_000.0;
+                        if settings.enabled && now - last >= idle_ms {
+                            showing = Some((controls.snapshot(), now));
+                            next_show_ms = now;
+                        }
+                    }
+                    Some((_, started)) if last > *started => {
+                        if let Some((snapshot, _)) = showing.take() {
+                            controls.restore(&snapshot);
+                        }
+                        *fade.write() = AttractFade(false);
+                    }
+                    Some(_) if now >= next_show_ms => {
+                        *fade.write() = AttractFade(true);
+                        sleep_ms(FADE_MS).await;
+                        let cells = next_show(&mut random, &universe.read());
+                        controls.dispatch(Command::Load(cells));
+                        controls.dispatch(Command::Start);
+                        *fade.write() = AttractFade(false);
+                        next_show_ms = now_ms() + SHOW_MS;
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    });
+
+    let current = *settings.get();
+    let update = move |changed: AttractSettings| {
+        changed.store();
+        settings.set(changed);
+    };
+    let idle_choices = IDLE_MINUTES.into_iter().map(|minutes| {
+        rsx! {
+            option { key: "{minutes}", value: "{minutes}", selected: minutes == current.idle_minutes, "{minutes} min" }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: current.enabled,
+                    onchange: move |event| update(AttractSettings { enabled: event.value == "true", ..current }),
+                }
+                "Attract mode after idle for"
+            }
+            select {
+                onchange: move |event| {
+                    if let Ok(idle_minutes) = event.value.parse() {
+                        update(AttractSettings { idle_minutes, ..current });
+                    }
+                },
+                idle_choices
+            }
+        }
+    }
+}
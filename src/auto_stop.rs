@@ -0,0 +1,110 @@
+//! Stopping the universe once its population has settled, which catches boring ash much sooner than
+//! waiting for the universe to repeat exactly, especially on large boards.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::universe::Universe,
+};
+
+const DEFAULT_TOLERANCE: usize = 5;
+const DEFAULT_GENERATIONS: u64 = 200;
+
+/// Follows the population from generation to generation, to notice it staying level.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct PopulationWatch {
+    generation: u64,
+    /// The population the later populations are compared with.
+    level: usize,
+    /// The generation the population has stayed near the level since.
+    since: u64,
+}
+
+impl PopulationWatch {
+    /// Observe the universe at a new generation, returning true if the population has stayed within
+    /// tolerance cells of the level for the given number of generations.
+    ///
+    /// The watch starts over when the generation goes back, such as when the universe is cleared, and
+    /// after it has returned true, so a restarted universe runs a while longer.
+    fn observe(&mut self, universe: &Universe, tolerance: usize, generations: u64) -> bool {
+        let generation = universe.generation();
+        let population = universe.population();
+
+        if generation < self.generation || population.abs_diff(self.level) > tolerance {
+            self.level = population;
+            self.since = generation;
+        }
+        self.generation = generation;
+
+        let settled = generation - self.since >= generations;
+        if settled {
+            self.since = generation;
+        }
+        settled
+    }
+}
+
+/// A checkbox to stop the universe when its population stays within ±K cells for N generations, and the
+/// K and N.
+#[component]
+pub fn AutoStop(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let enabled = use_state(cx, || false);
+    let tolerance = use_state(cx, || DEFAULT_TOLERANCE);
+    let generations = use_state(cx, || DEFAULT_GENERATIONS);
+    let watch = use_ref(cx, PopulationWatch::default);
+
+    let generation = universe.read().generation();
+    use_effect(cx, (&generation,), |_| {
+        to_owned![controls, universe, enabled, tolerance, generations, watch];
+        async move {
+            let settled = watch.with_mut(|watch| {
+                watch.observe(
+                    &universe.read(),
+                    *tolerance.current(),
+                    *generations.current(),
+                )
+            });
+            if settled && *enabled.current() && controls.is_running() {
+                controls.dispatch(Command::Stop);
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "4px",
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: *enabled.get(),
+                    onchange: move |event| enabled.set(event.value == "true"),
+                }
+                "Stop when the population stays within ±"
+            }
+            input {
+                r#type: "number",
+                min: 0,
+                value: "{tolerance}",
+                oninput: move |event| {
+                    if let Ok(cells) = event.value.parse() {
+                        tolerance.set(cells);
+                    }
+                },
+            }
+            span { "cells for" }
+            input {
+                r#type: "number",
+                min: 1,
+                value: "{generations}",
+                oninput: move |event| {
+                    if let Ok(count) = event.value.parse::<u64>() {
+                        generations.set(count.max(1));
+                    }
+                },
+            }
+            span { "generations" }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Crash-safe autosave for the desktop app.
+//!
+//! While the app runs, a marker file exists in the temp directory and a snapshot of the app is written
+//! next to it periodically.  The marker is removed when the window is closed, so if it still exists at
+//! startup, the previous run did not shut down cleanly and the user is offered its last autosave.
+
+use std::{fs, path::PathBuf, time::Duration};
+
+use dioxus::prelude::*;
+use dioxus_desktop::{
+    tao::event::{Event, WindowEvent},
+    use_wry_event_handler,
+};
+
+use crate::{command::use_controls, snapshot::Snapshot};
+
+/// How often the app is autosaved.
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+fn autosave_path() -> PathBuf {
+    std::env::temp_dir().join("game_of_life_autosave.json")
+}
+
+fn running_marker_path() -> PathBuf {
+    std::env::temp_dir().join("game_of_life.running")
+}
+
+/// Return the last autosave, if the previous run did not shut down cleanly.
+fn unclean_shutdown_autosave() -> Option<Snapshot> {
+    if !running_marker_path().exists() {
+        return None;
+    }
+
+    Snapshot::from_json(&fs::read_to_string(autosave_path()).ok()?).ok()
+}
+
+/// Replace the autosave with the given json.
+///
+/// The json is written to a separate file and then renamed, so a crash while writing cannot corrupt the
+/// previous autosave.
+fn write_autosave(json: &str) {
+    let path = autosave_path();
+    let partial_path = path.with_extension("json.partial");
+
+    if let Err(error) =
+        fs::write(&partial_path, json).and_then(|_| fs::rename(&partial_path, &path))
+    {
+        println!("Could not autosave: {error}");
+    }
+}
+
+/// Autosave the app periodically, and offer to restore the last autosave after an unclean shutdown.
+#[component]
+pub fn Autosave(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    // The autosave of a run that did not shut down cleanly, until it is restored or dismissed.
+    let recovered = use_state(cx, || {
+        let recovered = unclean_shutdown_autosave();
+
+        if let Err(error) = fs::write(running_marker_path(), "") {
+            println!("Could not create autosave marker: {error}");
+        }
+        recovered
+    });
+
+    use_future(cx, (), |_| {
+        to_owned![controls];
+        async move {
+            let mut last_saved = String::new();
+
+            loop {
+                tokio::time::sleep(AUTOSAVE_INTERVAL).await;
+
+                let json = controls.snapshot().to_json();
+                if json != last_saved {
+                    write_autosave(&json);
+                    last_saved = json;
+                }
+            }
+        }
+    });
+
+    // A clean shutdown needs neither the marker nor the autosave.
+    use_wry_event_handler(cx, |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::CloseRequested,
+            ..
+        } = event
+        {
+            let _ = fs::remove_file(running_marker_path());
+            let _ = fs::remove_file(autosave_path());
+        }
+    });
+
+    let generation = recovered.get().as_ref()?.generation();
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            span { "The last session did not shut down cleanly." }
+            button {
+                onclick: move |_| {
+                    if let Some(snapshot) = recovered.get() {
+                        controls.restore(snapshot);
+                    }
+                    recovered.set(None);
+                },
+                "Restore autosave (generation {generation})"
+            }
+            button { onclick: move |_| recovered.set(None), "Dismiss" }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Compares the speed of the tick engines.
+//!
+//! The same seeded universes are run through every engine, the results are checked against the naive
+//! engine, and the speed of each engine is reported relative to it.  The universe shown can also be timed
+//! with the chosen engine, to compare engine changes on the web and desktop builds.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::use_controls,
+    game_of_life::{engine::all_engines, universe::Universe},
+    platform::now_ms,
+};
+
+/// Seeds of the random universes each engine is run on.
+const BENCHMARK_SEEDS: [u64; 3] = [1, 2, 3];
+/// Generations each universe is advanced.
+const BENCHMARK_TICKS: u32 = 200;
+/// Generations the universe shown is advanced when timing the chosen engine.
+const UNIVERSE_BENCHMARK_TICKS: u64 = 1_000;
+
+/// The result of running one engine.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EngineResult {
+    pub name: &'static str,
+    pub ticks_per_second: f64,
+    /// True if the engine produced the same universes as the naive engine for every seed.
+    pub matches_reference: bool,
+}
+
+/// How fast the chosen engine advanced the universe.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UniverseBenchmark {
+    pub engine: &'static str,
+    pub ticks: u64,
+    pub elapsed_ms: f64,
+}
+
+impl UniverseBenchmark {
+    pub fn ticks_per_second(&self) -> f64 {
+        ticks_per_second(self.ticks as f64, self.elapsed_ms)
+    }
+
+    pub fn microseconds_per_tick(&self) -> f64 {
+        1_000_000.0 / self.ticks_per_second()
+    }
+}
+
+/// Return the speed of advancing the given ticks in the given milliseconds, in ticks each second.
+fn ticks_per_second(ticks: f64, elapsed_ms: f64) -> f64 {
+    ticks / (elapsed_ms / 1000.0).max(f64::EPSILON)
+}
+
+/// Run every engine on universes randomized with the given seeds, and compare their results and speed.
+pub fn compare_engines(seeds: &[u64], ticks: u32) -> Vec<EngineResult> {
+    let starts: Vec<Universe> = seeds
+        .iter()
+        .map(|seed| {
+            let mut universe = Universe::new();
+            universe.random_with_seed(*seed);
+            universe
+        })
+        .collect();
+    let expected_hashes: Vec<u64> = starts
+        .iter()
+        .map(|start| {
+            let mut universe = start.clone();
+            for _ in 0..ticks {
+                universe.tick();
+            }
+            universe.board_hash()
+        })
+        .collect();
+
+    all_engines()
+        .into_iter()
+        .map(|mut engine| {
+            let mut elapsed_ms = 0.0;
+            let mut matches_reference = true;
+
+            for (start, expected_hash) in starts.iter().zip(expected_hashes.iter()) {
+                engine.load(start);
+
+                let started = now_ms();
+                for _ in 0..ticks {
+                    engine.tick();
+                }
+                elapsed_ms += now_ms() - started;
+
+                matches_reference &= engine.to_universe().board_hash() == *expected_hash;
+            }
+
+            let total_ticks = (ticks as usize * seeds.len()) as f64;
+            EngineResult {
+                name: engine.name(),
+                ticks_per_second: ticks_per_second(total_ticks, elapsed_ms),
+                matches_reference,
+            }
+        })
+        .collect()
+}
+
+/// A button that compares the engines, and a table of the results.
+#[component]
+pub fn EngineComparison(cx: Scope) -> Element {
+    let results = use_state(cx, Vec::<EngineResult>::new);
+
+    // The first result is from the naive engine, which the others are compared to.
+    let reference = results
+        .first()
+        .map_or(1.0, |result| result.ticks_per_second);
+    let rows = results.iter().map(|result| {
+        let ticks_per_second = result.ticks_per_second.round();
+        let relative = result.ticks_per_second / reference;
+        let correct = if result.matches_reference {
+            "yes"
+        } else {
+            "NO"
+        };
+
+        rsx! {
+            tr { key: "{result.name}",
+                td { "{result.name}" }
+                td { text_align: "right", "{ticks_per_second}" }
+                td { text_align: "right", "{relative:.2}x" }
+                td { "{correct}" }
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", flex_direction: "column", align_items: "center",
+            button {
+                onclick: move |_| results.set(compare_engines(&BENCHMARK_SEEDS, BENCHMARK_TICKS)),
+                "Compare engines"
+            }
+            if !results.is_empty() {
+                rsx! {
+                    table { font_family: "monospace",
+                        tr {
+                            th { "engine" }
+                            th { "ticks/s" }
+                            th { "relative" }
+                            th { "correct" }
+                        }
+                        rows
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A button that advances a copy of the universe shown with the chosen engine as fast as it can, without
+/// drawing it, and reports the speed.
+#[component]
+pub fn UniverseBenchmarkButton(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let result = use_state(cx, || None::<UniverseBenchmark>);
+
+    let report = result.get().as_ref().map(|result| {
+        let ticks_per_second = result.ticks_per_second().round();
+        let microseconds_per_tick = result.microseconds_per_tick();
+        format!(
+            "{} ticks with the {} engine: {ticks_per_second} ticks/s, {microseconds_per_tick:.1} µs/tick",
+            result.ticks, result.engine
+        )
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            button {
+                title: "Advance a copy of the universe {UNIVERSE_BENCHMARK_TICKS} generations without drawing it",
+                onclick: move |_| {
+                    let (engine, elapsed_ms) = controls.benchmark(UNIVERSE_BENCHMARK_TICKS);
+                    result.set(Some(UniverseBenchmark {
+                        engine: engine.name(),
+                        ticks: UNIVERSE_BENCHMARK_TICKS,
+                        elapsed_ms,
+                    }));
+                },
+                "Benchmark"
+            }
+            if let Some(report) = report {
+                rsx! { span { font_family: "monospace", "{report}" } }
+            }
+        }
+    }
+}
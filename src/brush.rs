@@ -0,0 +1,169 @@
+//! Brushes for the draw and erase tools, which paint every cell under the brush around the cell under the
+//! mouse.  While drawing or erasing, the cells under the brush are outlined on the grid.
+
+use std::{fmt, str::FromStr};
+
+use dioxus::prelude::*;
+
+use crate::{
+    game_of_life::{
+        hex_cells_path,
+        universe::{Topology, Universe},
+        CellLayer, HoveredCell,
+    },
+    placement::Placement,
+    selection::Tool,
+};
+
+const BRUSH_COLOR: &str = "dodgerblue";
+/// The cells whose middles are within this many cells of the middle of the brush are under a circle brush.
+const CIRCLE_RADIUS: i64 = 3;
+
+/// The cells painted around the cell under the mouse.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Brush {
+    /// Only the cell under the mouse.
+    #[default]
+    Single,
+    /// The square of 3 by 3 cells around the cell under the mouse.
+    Square3,
+    /// The square of 5 by 5 cells around the cell under the mouse.
+    Square5,
+    /// A circle of cells around the cell under the mouse.
+    Circle,
+}
+
+impl Brush {
+    pub const ALL: [Brush; 4] = [Brush::Single, Brush::Square3, Brush::Square5, Brush::Circle];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Brush::Single => "1×1",
+            Brush::Square3 => "3×3",
+            Brush::Square5 => "5×5",
+            Brush::Circle => "circle",
+        }
+    }
+
+    /// Return the (row, col) offsets of the cells under the brush from the cell under the mouse.
+    pub fn offsets(&self) -> Vec<(i64, i64)> {
+        let reach = match self {
+            Brush::Single => 0,
+            Brush::Square3 => 1,
+            Brush::Square5 => 2,
+            Brush::Circle => CIRCLE_RADIUS,
+        };
+        let square = (-reach..=reach).flat_map(|row| (-reach..=reach).map(move |col| (row, col)));
+        match self {
+            // Rounded out a little, so the circle has no lone cells sticking out at the top, bottom and sides.
+            Brush::Circle => square
+                .filter(|(row, col)| row * row + col * col <= reach * reach + reach)
+                .collect(),
+            _ => square.collect(),
+        }
+    }
+}
+
+impl fmt::Display for Brush {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Brush::Single => "1x1",
+            Brush::Square3 => "3x3",
+            Brush::Square5 => "5x5",
+            Brush::Circle => "circle",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Brush {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Brush::ALL
+            .into_iter()
+            .find(|brush| brush.to_string() == s)
+            .ok_or(format!(
+                "unknown brush {s}: expected 1x1, 3x3, 5x5 or circle"
+            ))
+    }
+}
+
+/// The choice of brush, shown while the draw or erase tool is chosen.
+#[component]
+pub fn BrushPicker(cx: Scope) -> Element {
+    let tool = *use_shared_state::<Tool>(cx).unwrap().read();
+    let brush = use_shared_state::<Brush>(cx).unwrap();
+
+    if !matches!(tool, Tool::Draw | Tool::Erase) {
+        return None;
+    }
+    let current = *brush.read();
+    let options = Brush::ALL.into_iter().map(|choice| {
+        rsx! {
+            option { key: "{choice}", value: "{choice}", selected: choice == current, "{choice.label()}" }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center",
+            label {
+                "Brush "
+                select {
+                    onchange: move |event| {
+                        if let Ok(choice) = event.value.parse() {
+                            *brush.write() = choice;
+                        }
+                    },
+                    options
+                }
+            }
+        }
+    }
+}
+
+/// The cells under the brush around the cell under the mouse, while drawing or erasing and not placing a
+/// pattern.
+#[component]
+pub fn BrushOutline(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let tool = *use_shared_state::<Tool>(cx).unwrap().read();
+    let brush = *use_shared_state::<Brush>(cx).unwrap().read();
+    let HoveredCell(hovered) = *use_shared_state::<HoveredCell>(cx).unwrap().read();
+    let placing = use_shared_state::<Placement>(cx)
+        .unwrap()
+        .read()
+        .0
+        .is_some();
+
+    let (row, col) = hovered?;
+    // Placing a pattern takes over clicks from the tools.
+    if placing || !matches!(tool, Tool::Draw | Tool::Erase) {
+        return None;
+    }
+    let universe = universe.read();
+    let cells: Vec<(i64, i64)> = universe
+        .region_cells(row, col, &brush.offsets())
+        .into_iter()
+        .map(|(row, col)| (col as i64, row as i64))
+        .collect();
+    let path = match universe.topology() {
+        Topology::Square => cells
+            .iter()
+            .map(|(x, y)| format!("M{x} {y}h1v1h-1z"))
+            .collect(),
+        Topology::Hex => hex_cells_path(&cells),
+    };
+
+    render! {
+        CellLayer {
+            path {
+                d: "{path}",
+                fill: BRUSH_COLOR,
+                fill_opacity: 0.3,
+                stroke: BRUSH_COLOR,
+                stroke_width: 0.1,
+            }
+        }
+    }
+}
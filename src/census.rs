@@ -0,0 +1,56 @@
+//! A panel counting the known objects (blocks, blinkers, gliders...) in the universe.
+
+use dioxus::prelude::*;
+
+use crate::game_of_life::{
+    census::{Census, Recognizer},
+    universe::Universe,
+};
+
+/// A button that takes a census of the universe, and a table of the objects found.
+#[component]
+pub fn CensusPanel(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let recognizer = cx.use_hook(Recognizer::new);
+    // The generation the census was taken at, and the census.
+    let census = use_state(cx, || None::<(u64, Census)>);
+
+    let results = census.get().as_ref().map(|(generation, census)| {
+        let rows = census.objects.iter().map(|(name, count)| {
+            rsx! {
+                tr { key: "{name}",
+                    td { "{name}" }
+                    td { text_align: "right", "{count}" }
+                }
+            }
+        });
+        let unrecognized = census.unrecognized;
+
+        rsx! {
+            table { font_family: "monospace",
+                tr {
+                    th { "generation {generation}" }
+                    th { "count" }
+                }
+                rows,
+                tr {
+                    td { "other" }
+                    td { text_align: "right", "{unrecognized}" }
+                }
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", flex_direction: "column", align_items: "center",
+            button {
+                onclick: move |_| {
+                    let universe = universe.read();
+                    census.set(Some((universe.generation(), recognizer.census(&universe))));
+                },
+                "Take census"
+            }
+            results
+        }
+    }
+}
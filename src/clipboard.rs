@@ -0,0 +1,178 @@
+//! Copying and pasting patterns through the system clipboard as text, so patterns move between the app,
+//! Golly, and text editors without saving files.
+//!
+//! With the grid focused, Ctrl+C (or Cmd+C) copies the selection as RLE, or the whole universe if nothing is
+//! selected, and Ctrl+V (or Cmd+V) replaces the universe with the pattern on the clipboard, centered.  The
+//! pattern pasted can be RLE, plaintext, or any other format pattern files are read in.
+//!
+//! On desktop, the clipboard is reached through arboard.  On the web, it is the browser's asynchronous
+//! clipboard, which is only available to secure (https or localhost) pages, and may ask to allow reading it.
+
+#[cfg(feature = "desktop")]
+use arboard::Clipboard;
+use dioxus::{html::input_data::keyboard_types::Key, prelude::*};
+
+use crate::{
+    command::{use_controls, Command, Controls},
+    game_of_life::{centered, rle::write_rle, universe::Universe},
+    pattern_files::read_pattern,
+    selection::Selection,
+};
+
+/// Copy or paste if the key is Ctrl+C or Ctrl+V, returning true if it was.
+///
+/// The clipboard may have to be waited for to paste, so pasting is finished by a task of the scope.
+pub fn clipboard_key(
+    cx: &ScopeState,
+    event: &KeyboardData,
+    controls: &Controls,
+    universe: &UseSharedState<Universe>,
+    selection: Selection,
+) -> bool {
+    let modifiers = event.modifiers();
+    if !modifiers.ctrl() && !modifiers.meta() {
+        return false;
+    }
+
+    match event.key() {
+        Key::Character(key) if key == "c" => copy(&universe.read(), selection),
+        Key::Character(key) if key == "v" => {
+            to_owned![controls, universe];
+            cx.spawn(async move { paste(&controls, &universe).await });
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Put the selection, or the whole universe, on the clipboard as RLE, unless it can't be written as RLE.
+fn copy(universe: &Universe, selection: Selection) {
+    let rle = match selection.0 {
+        Some(bounds) => {
+            // The selection as a universe of its own, so it is written without the rest of the universe.
+            let mut selected = Universe::with_size(bounds.width(), bounds.height());
+            selected.set_rule(universe.rule());
+            let cells: Vec<(u32, u32)> = selection
+                .living_cells(universe)
+                .into_iter()
+                .map(|(row, col)| (row - bounds.top, col - bounds.left))
+                .collect();
+            selected.set_cells(&cells);
+            write_rle(&selected, "")
+        }
+        None => write_rle(universe, ""),
+    };
+
+    match rle {
+        Ok(rle) => set_text(rle),
+        Err(error) => report(format!("Could not copy the universe: {error}")),
+    }
+}
+
+/// Put the text on the clipboard.
+#[cfg(feature = "desktop")]
+pub fn set_text(text: String) {
+    if let Err(error) = Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+        println!("Could not copy to the clipboard: {error}");
+    }
+}
+
+/// Put the text on the clipboard.
+#[cfg(feature = "web")]
+pub fn set_text(text: String) {
+    use wasm_bindgen_futures::JsFuture;
+
+    let written = crate::platform::window()
+        .navigator()
+        .clipboard()
+        .write_text(&text);
+    wasm_bindgen_futures::spawn_local(async move {
+        if JsFuture::from(written).await.is_err() {
+            console_log!("Could not copy to the clipboard: the browser didn't allow it");
+        }
+    });
+}
+
+/// Return the text on the clipboard.
+#[cfg(feature = "desktop")]
+async fn get_text() -> Result<String, String> {
+    Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|error| error.to_string())
+}
+
+/// Return the text on the clipboard.
+#[cfg(feature = "web")]
+async fn get_text() -> Result<String, String> {
+    use wasm_bindgen_futures::JsFuture;
+
+    let read = crate::platform::window()
+        .navigator()
+        .clipboard()
+        .read_text();
+    JsFuture::from(read)
+        .await
+        .ok()
+        .and_then(|text| text.as_string())
+        .ok_or("the browser didn't allow reading it".to_string())
+}
+
+/// Replace the universe with the pattern on the clipboard, centered.
+async fn paste(controls: &Controls, universe: &UseSharedState<Universe>) {
+    let text = match get_text().await {
+        Ok(text) => text,
+        Err(error) => {
+            report(format!("Could not paste from the clipboard: {error}"));
+            return;
+        }
+    };
+
+    let size = {
+        let universe = universe.read();
+        (universe.height(), universe.width())
+    };
+    match read_pattern(&text, size) {
+        Ok(cells) => {
+            let cells = centered(cells, &universe.read());
+            controls.dispatch(Command::Load(cells));
+        }
+        Err(error) => report(format!(
+            "Could not paste the clipboard as a pattern: {error}"
+        )),
+    }
+}
+
+/// Report a problem with the clipboard where the platform shows such messages.
+fn report(message: String) {
+    #[cfg(feature = "desktop")]
+    println!("{message}");
+    #[cfg(feature = "web")]
+    console_log!("{message}");
+}
+
+/// Buttons to copy the selection, or the whole universe, to the clipboard and to paste a pattern from it,
+/// as Ctrl+C and Ctrl+V do on the grid.
+#[component]
+pub fn ClipboardButtons(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let selection = use_shared_state::<Selection>(cx).unwrap();
+
+    render! {
+        div { display: "flex", justify_content: "center", gap: "8px",
+            button {
+                title: "Ctrl+C on the grid",
+                onclick: move |_| copy(&universe.read(), *selection.read()),
+                "Copy RLE to clipboard"
+            }
+            button {
+                title: "Ctrl+V on the grid",
+                onclick: move |_| {
+                    to_owned![controls, universe];
+                    cx.spawn(async move { paste(&controls, &universe).await });
+                },
+                "Paste from clipboard"
+            }
+        }
+    }
+}
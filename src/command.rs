@@ -0,0 +1,796 @@
+//! Commands that drive the simulation.
+//!
+//! Buttons, grid clicks and external controllers (such as the embed API) all change the simulation by
+//! dispatching a Command through Controls, so every way of driving the app behaves the same, and
+//! every change can be recorded and replayed.
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::Rc,
+    str::FromStr,
+};
+
+use dioxus::prelude::*;
+
+use crate::{
+    annotations::Annotations,
+    brush::Brush,
+    game_of_life::{
+        automaton::Automaton,
+        engine::{EngineChoice, UniverseEngine},
+        random::{random_seed, PlatformRandom},
+        rule::Rule,
+        universe::{Boundary, Topology, Universe, DEFAULT_DENSITY},
+    },
+    game_of_life::{Redraw, TicksPerFrame},
+    history::History,
+    memory::MemoryBudget,
+    platform::now_ms,
+    replay::{Recording, Replay, Session},
+    selection::Tool,
+    settings::MAX_UNIVERSE_SIZE,
+    snapshot::Snapshot,
+    speed::{StepsPerSecond, MAX_TICKS_PER_FRAME},
+    storage,
+    viewport::Viewport,
+};
+
+const ENGINE_KEY: &str = "engine.txt";
+/// The most time a frame makes up for at a chosen rate, so a frame after a long pause (such as a hidden tab)
+/// doesn't advance a great many generations at once.
+const MAX_PACED_MS: f64 = 250.0;
+
+/// The engine chosen to advance the universe, and the engine itself, or None for the naive engine.
+#[derive(Default)]
+struct SelectedEngine {
+    choice: EngineChoice,
+    engine: Option<Box<dyn UniverseEngine>>,
+    /// The universe the engine holds, as it was when the engine last advanced it, so the engine is only
+    /// loaded again after the universe changes some other way.
+    loaded: Option<Fingerprint>,
+}
+
+/// What tells universes apart: their (width, height), rule, boundary, generation and the hash of their cells.
+type Fingerprint = ((u32, u32), Rule, Boundary, u64, u64);
+
+fn fingerprint(universe: &Universe) -> Fingerprint {
+    (
+        (universe.width(), universe.height()),
+        universe.rule(),
+        universe.boundary(),
+        universe.generation(),
+        universe.zobrist_hash(),
+    )
+}
+
+/// An action that changes the running state or the contents of the universe.
+///
+/// Commands have a text form (e.g. "toggle 3 4"), used in replay files.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Start advancing the universe every animation frame.
+    Start,
+    /// Stop advancing the universe.
+    Stop,
+    /// Advance the universe one generation.
+    Step,
+    /// Randomly set all cells of the universe, with density percent of the cells alive on average.
+    ///
+    /// Dispatched as a Seed command with a new seed, so the result can be replayed.
+    Random { density: u8 },
+    /// Randomly set all cells of the universe from the given seed, with density percent of the cells alive
+    /// on average.
+    Seed { seed: u64, density: u8 },
+    /// Kill all cells in the universe.
+    Clear,
+    /// Toggle the cell at the given row and column.
+    Toggle { row: u32, col: u32 },
+    /// Set the cell at the given row and column to a state of an automaton with more than two states.
+    Paint { row: u32, col: u32, state: u8 },
+    /// Set the cells under the brush around the given row and column to a state, alive (1) or dead (0) for
+    /// Life.
+    Brush {
+        row: u32,
+        col: u32,
+        brush: Brush,
+        state: u8,
+    },
+    /// Put an ant on the cell at the given row and column, or take away the ant there, for Langton's Ant.
+    ToggleAnt { row: u32, col: u32 },
+    /// Clear the universe and set the given (row, col) cells alive.
+    Load(Vec<(u32, u32)>),
+    /// Set how many generations the universe advances each animation frame.
+    SetTicksPerFrame(u32),
+    /// Set the rule the universe advances by.
+    SetRule(Rule),
+    /// Set what lies past the edges of the universe.
+    SetBoundary(Boundary),
+    /// Set how the cells of the universe advance.
+    SetAutomaton(Automaton),
+    /// Set how the cells of the universe are arranged.
+    SetTopology(Topology),
+    /// Replace the universe with an empty universe of the given size, with the same rule, boundary, topology
+    /// and automaton.
+    Resize { width: u32, height: u32 },
+}
+
+impl Command {
+    /// Apply the command to the universe.
+    ///
+    /// Commands that only change how the simulation runs (such as Start and Stop) leave the universe unchanged.
+    pub fn apply(&self, universe: &mut Universe) {
+        match self {
+            Command::Step => universe.tick(),
+            Command::Random { density } => universe.random_from(&mut PlatformRandom, *density),
+            Command::Seed { seed, density } => {
+                universe.random_with_seed_and_density(*seed, *density)
+            }
+            Command::Clear => universe.clear(),
+            Command::Toggle { row, col } => universe.toggle_cell(*row, *col),
+            Command::Paint { row, col, state } => universe.set_state(*row, *col, *state),
+            Command::Brush {
+                row,
+                col,
+                brush,
+                state,
+            } => universe.set_region(*row, *col, &brush.offsets(), *state),
+            Command::ToggleAnt { row, col } => universe.toggle_ant(*row, *col),
+            Command::Load(cells) => {
+                universe.clear();
+                universe.set_cells(cells);
+            }
+            Command::SetRule(rule) => universe.set_rule(*rule),
+            Command::SetBoundary(boundary) => universe.set_boundary(*boundary),
+            Command::SetAutomaton(automaton) => universe.set_automaton(*automaton),
+            Command::SetTopology(topology) => universe.set_topology(*topology),
+            Command::Resize { width, height } => {
+                let mut resized = Universe::with_size(*width, *height);
+                resized.set_rule(universe.rule());
+                resized.set_boundary(universe.boundary());
+                resized.set_topology(universe.topology());
+                resized.set_automaton(universe.automaton());
+                *universe = resized;
+            }
+            Command::Start | Command::Stop | Command::SetTicksPerFrame(_) => {}
+        }
+    }
+
+    /// True if applying the command changes the universe.
+    fn changes_universe(&self) -> bool {
+        !matches!(
+            self,
+            Command::Start | Command::Stop | Command::SetTicksPerFrame(_)
+        )
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::Start => write!(f, "start"),
+            Command::Stop => write!(f, "stop"),
+            Command::Step => write!(f, "step"),
+            Command::Random { density } => write!(f, "random{}", density_argument(*density)),
+            Command::Seed { seed, density } => {
+                write!(f, "seed {seed}{}", density_argument(*density))
+            }
+            Command::Clear => write!(f, "clear"),
+            Command::Toggle { row, col } => write!(f, "toggle {row} {col}"),
+            Command::Paint { row, col, state } => write!(f, "paint {row} {col} {state}"),
+            Command::Brush {
+                row,
+                col,
+                brush,
+                state,
+            } => write!(f, "brush {row} {col} {state} {brush}"),
+            Command::ToggleAnt { row, col } => write!(f, "ant {row} {col}"),
+            Command::Load(cells) => {
+                write!(f, "load")?;
+                for (row, col) in cells {
+                    write!(f, " {row},{col}")?;
+                }
+                Ok(())
+            }
+            Command::SetTicksPerFrame(ticks) => write!(f, "speed {ticks}"),
+            Command::SetRule(rule) => write!(f, "rule {rule}"),
+            Command::SetBoundary(boundary) => write!(f, "boundary {boundary}"),
+            Command::SetAutomaton(automaton) => write!(f, "automaton {automaton}"),
+            Command::SetTopology(topology) => write!(f, "topology {topology}"),
+            Command::Resize { width, height } => write!(f, "resize {width} {height}"),
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let name = words.next().ok_or("empty command")?;
+        let mut number = || -> Result<u64, String> {
+            let word = words.next().ok_or(format!("{name}: missing argument"))?;
+            word.parse()
+                .map_err(|_| format!("{name}: bad number {word}"))
+        };
+
+        let command = match name {
+            "start" => Command::Start,
+            "stop" => Command::Stop,
+            "step" => Command::Step,
+            "random" => Command::Random {
+                density: parse_density(s, 1)?,
+            },
+            "seed" => Command::Seed {
+                seed: number()?,
+                density: parse_density(s, 2)?,
+            },
+            "clear" => Command::Clear,
+            "toggle" => Command::Toggle {
+                row: fit(name, number()?)?,
+                col: fit(name, number()?)?,
+            },
+            "paint" => Command::Paint {
+                row: fit(name, number()?)?,
+                col: fit(name, number()?)?,
+                state: fit(name, number()?)?,
+            },
+            "brush" => Command::Brush {
+                row: fit(name, number()?)?,
+                col: fit(name, number()?)?,
+                state: fit(name, number()?)?,
+                brush: words.next().ok_or("brush: missing argument")?.parse()?,
+            },
+            "ant" => Command::ToggleAnt {
+                row: fit(name, number()?)?,
+                col: fit(name, number()?)?,
+            },
+            "speed" => Command::SetTicksPerFrame(fit(name, number()?)?),
+            "rule" => Command::SetRule(words.next().ok_or("rule: missing argument")?.parse()?),
+            "boundary" => {
+                Command::SetBoundary(words.next().ok_or("boundary: missing argument")?.parse()?)
+            }
+            "automaton" => {
+                Command::SetAutomaton(words.next().ok_or("automaton: missing argument")?.parse()?)
+            }
+            "topology" => {
+                Command::SetTopology(words.next().ok_or("topology: missing argument")?.parse()?)
+            }
+            "resize" => {
+                let (width, height) = checked_size(number()?, number()?)?;
+                Command::Resize { width, height }
+            }
+            "load" => Command::Load(
+                s.split_whitespace()
+                    .skip(1)
+                    .map(|cell| {
+                        let (row, col) = cell
+                            .split_once(',')
+                            .ok_or(format!("load: bad cell {cell}"))?;
+                        match (row.parse(), col.parse()) {
+                            (Ok(row), Ok(col)) => Ok((row, col)),
+                            _ => Err(format!("load: bad cell {cell}")),
+                        }
+                    })
+                    .collect::<Result<_, String>>()?,
+            ),
+            _ => return Err(format!("unknown command: {name}")),
+        };
+        Ok(command)
+    }
+}
+
+/// Return the number argument of the named command as the type the command holds, or an error if it doesn't
+/// fit, so a replay file can't quietly replay a different command than the one it was recorded with.
+fn fit<T: TryFrom<u64>>(name: &str, number: u64) -> Result<T, String> {
+    T::try_from(number).map_err(|_| format!("{name}: {number} is out of range"))
+}
+
+/// Return the (width, height) of a universe given as numbers, or an error if it has more cells than the
+/// largest universe that can be chosen, so a replay file can't ask for more memory than the app has.
+///
+/// Only the number of cells is limited, since a universe with an infinite boundary grows past the largest
+/// width or height that can be chosen.
+pub fn checked_size(width: u64, height: u64) -> Result<(u32, u32), String> {
+    let max_cells = MAX_UNIVERSE_SIZE as u64 * MAX_UNIVERSE_SIZE as u64;
+    if width == 0 || height == 0 || width.saturating_mul(height) > max_cells {
+        return Err(format!(
+            "a universe can't be {width} by {height}: it can have 1 to {max_cells} cells"
+        ));
+    }
+    Ok((width as u32, height as u32))
+}
+
+/// Return the density argument of a random or seed command: nothing for the default density, so commands
+/// read the same as before densities could be chosen.
+fn density_argument(density: u8) -> String {
+    match density {
+        DEFAULT_DENSITY => String::new(),
+        density => format!(" {density}"),
+    }
+}
+
+/// Return the density given as the word of the command at index, or the default density if there is none.
+fn parse_density(s: &str, index: usize) -> Result<u8, String> {
+    match s.split_whitespace().nth(index) {
+        None => Ok(DEFAULT_DENSITY),
+        Some(word) => word
+            .parse()
+            .ok()
+            .filter(|density| *density <= 100)
+            .ok_or(format!(
+                "bad density {word}: expected a percent from 0 to 100"
+            )),
+    }
+}
+
+/// Handles to the state that commands act on.
+///
+/// Provided to all components by App via use_controls_provider(), and retrieved with use_controls().
+#[derive(Clone)]
+pub struct Controls {
+    frames_running: UseState<bool>,
+    universe: UseSharedState<Universe>,
+    redraw: UseSharedState<Redraw>,
+    ticks_per_frame: UseSharedState<TicksPerFrame>,
+    steps_per_second: UseSharedState<StepsPerSecond>,
+    session: UseSharedState<Session>,
+    annotations: UseSharedState<Annotations>,
+    budget: UseSharedState<MemoryBudget>,
+    viewport: UseSharedState<Viewport>,
+    tool: UseSharedState<Tool>,
+    /// The universe before each of its recent changes.
+    history: Rc<RefCell<History>>,
+    /// The seed and density the universe was last randomized from.
+    last_seed: Rc<Cell<Option<(u64, u8)>>>,
+    /// True while dispatching the commands of a batch after the first, which are stepped back together with
+    /// the first.
+    batched: Rc<Cell<bool>>,
+    /// Animation frames that arrived while paused, and were dropped without doing any work.
+    dropped_frames: Rc<Cell<u64>>,
+    /// At a chosen rate, the time of the last frame, and the fraction of a step it left to take.
+    /// None until the first frame after the universe starts.
+    pace: Rc<Cell<Option<(f64, f64)>>>,
+    engine: Rc<RefCell<SelectedEngine>>,
+}
+
+/// Create the Controls for the app and provide them to all child components.
+///
+/// Must be called after the Universe, Redraw, TicksPerFrame, StepsPerSecond, Session, Annotations,
+/// MemoryBudget, Viewport and Tool shared states are provided.
+pub fn use_controls_provider<'a>(
+    cx: &'a ScopeState,
+    frames_running: &UseState<bool>,
+) -> &'a Controls {
+    use_context_provider(cx, || Controls {
+        frames_running: frames_running.clone(),
+        universe: use_shared_state::<Universe>(cx).unwrap().clone(),
+        redraw: use_shared_state::<Redraw>(cx).unwrap().clone(),
+        ticks_per_frame: use_shared_state::<TicksPerFrame>(cx).unwrap().clone(),
+        steps_per_second: use_shared_state::<StepsPerSecond>(cx).unwrap().clone(),
+        session: use_shared_state::<Session>(cx).unwrap().clone(),
+        annotations: use_shared_state::<Annotations>(cx).unwrap().clone(),
+        budget: use_shared_state::<MemoryBudget>(cx).unwrap().clone(),
+        viewport: use_shared_state::<Viewport>(cx).unwrap().clone(),
+        tool: use_shared_state::<Tool>(cx).unwrap().clone(),
+        history: Rc::new(RefCell::new(History::stored())),
+        last_seed: Rc::default(),
+        batched: Rc::default(),
+        dropped_frames: Rc::default(),
+        pace: Rc::default(),
+        engine: Rc::new(RefCell::new(stored_engine())),
+    })
+}
+
+/// Return the engine chosen in an earlier session, or the naive engine.
+fn stored_engine() -> SelectedEngine {
+    storage::load(ENGINE_KEY)
+        .and_then(|name| EngineChoice::find(name.trim()))
+        .and_then(|choice| engine_for(choice).map(|engine| SelectedEngine::new(choice, engine)))
+        .unwrap_or_default()
+}
+
+impl SelectedEngine {
+    fn new(choice: EngineChoice, engine: Option<Box<dyn UniverseEngine>>) -> SelectedEngine {
+        SelectedEngine {
+            choice,
+            engine,
+            loaded: None,
+        }
+    }
+}
+
+/// Return the engine to advance the universe with, None for the naive engine, or None inside if the engine
+/// can't run here.
+fn engine_for(choice: EngineChoice) -> Option<Option<Box<dyn UniverseEngine>>> {
+    match choice {
+        EngineChoice::Naive => Some(None),
+        choice => choice.create().map(Some),
+    }
+}
+
+/// True if the chosen engine can advance the universe.
+///
+/// The engines only know life-like rules on square cells of a universe that doesn't grow, so other automata,
+/// hex grids and infinite universes are always advanced naively.
+fn engine_runs(universe: &Universe) -> bool {
+    universe.automaton() == Automaton::Life
+        && universe.topology() == Topology::Square
+        && universe.boundary() != Boundary::Infinite
+}
+
+/// Return the Controls provided by App.
+pub fn use_controls(cx: &ScopeState) -> &Controls {
+    use_context::<Controls>(cx).expect("controls should be provided by App")
+}
+
+impl Controls {
+    /// Apply the command, and signal a redraw if the universe changed.
+    ///
+    /// The command is added to the recording if the session is being recorded.  Commands that change the
+    /// universe end a replay, since the user has taken over.
+    pub fn dispatch(&self, command: Command) {
+        let command = match command {
+            Command::Random { density } => Command::Seed {
+                seed: random_seed(),
+                density,
+            },
+            command => command,
+        };
+
+        if !self.session.read().is_live() {
+            let generation = self.universe.read().generation();
+
+            self.session.with_mut(|session| match session {
+                Session::Recording(recording) => recording.push(generation, command.clone()),
+                Session::Replaying(_) if command.changes_universe() => *session = Session::Live,
+                _ => {}
+            });
+        }
+
+        match command {
+            Command::Start => self.frames_running.set(true),
+            Command::Stop => self.frames_running.set(false),
+            Command::SetTicksPerFrame(ticks) => self.ticks_per_frame.with_mut(|ticks_per_frame| {
+                *ticks_per_frame = TicksPerFrame(ticks.clamp(1, MAX_TICKS_PER_FRAME));
+            }),
+            command => {
+                if let Command::Seed { seed, density } = command {
+                    self.last_seed.set(Some((seed, density)));
+                }
+                self.change_universe(|universe| command.apply(universe))
+            }
+        }
+    }
+
+    /// Dispatch the commands one after another, as a single change to step back.
+    ///
+    /// Each command is recorded on its own, so the batch replays the same.
+    pub fn dispatch_all(&self, commands: Vec<Command>) {
+        for (index, command) in commands.into_iter().enumerate() {
+            self.batched.set(index > 0);
+            self.dispatch(command);
+        }
+        self.batched.set(false);
+    }
+
+    /// Dispatch the command as part of the last change, so it steps back with it, as the cells painted by
+    /// one drag of the mouse do.
+    pub fn dispatch_continuing(&self, command: Command) {
+        self.batched.set(true);
+        self.dispatch(command);
+        self.batched.set(false);
+    }
+
+    /// Return the seed and density the universe was last randomized from, so the soup can be made again.
+    pub fn last_seed(&self) -> Option<(u64, u8)> {
+        self.last_seed.get()
+    }
+
+    /// True if the universe is advancing every animation frame.
+    pub fn is_running(&self) -> bool {
+        *self.frames_running.current()
+    }
+
+    /// Return the number of animation frames that arrived while paused.
+    ///
+    /// No frames are requested while paused, so this only counts frames already requested when frames
+    /// stopped, and stays the same for as long as the app is paused.
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped_frames.get()
+    }
+
+    /// Advance the universe for a new animation frame: a step, or the steps owed since the last frame at a
+    /// chosen rate, each of the ticks per frame.
+    ///
+    /// When replaying, the universe is advanced by the replay instead, and frames stop when it is finished.
+    /// Frames that arrive while paused are dropped.
+    pub fn advance_frame(&self) {
+        if !self.is_running() {
+            self.dropped_frames.set(self.dropped_frames.get() + 1);
+            self.pace.set(None);
+            return;
+        }
+        let steps = match *self.steps_per_second.read() {
+            StepsPerSecond(Some(rate)) => self.paced_steps(rate),
+            StepsPerSecond(None) => 1,
+        };
+        let ticks = steps.saturating_mul(self.ticks_per_frame.read().0);
+        if ticks == 0 {
+            return;
+        }
+        let mut replay_finished = false;
+
+        self.change_universe(|universe| {
+            let mut session = self.session.write_silent();
+
+            match &mut *session {
+                Session::Replaying(replay) => {
+                    for _ in 0..ticks {
+                        if !replay.advance(universe) {
+                            replay_finished = true;
+                            break;
+                        }
+                    }
+                }
+                _ => self.advance(universe, ticks as u64),
+            }
+        });
+
+        if replay_finished {
+            self.session.with_mut(|session| *session = Session::Live);
+            self.frames_running.set(false);
+        }
+    }
+
+    /// Return the whole steps owed at the rate since the last frame, keeping the fraction left over for the
+    /// next frame.
+    ///
+    /// The first frame after the universe starts takes a step, so starting shows a change at once.
+    fn paced_steps(&self, rate: u32) -> u32 {
+        let now = now_ms();
+        let owed = match self.pace.get() {
+            Some((last, owed)) => owed + (now - last).min(MAX_PACED_MS) * rate as f64 / 1000.0,
+            None => 1.0,
+        };
+        let ticks = owed.floor();
+        self.pace.set(Some((now, owed - ticks)));
+        ticks as u32
+    }
+
+    /// Advance the universe the given number of generations with the chosen engine, or naively if the engine
+    /// can't advance it.
+    fn advance(&self, universe: &mut Universe, generations: u64) {
+        let mut selected = self.engine.borrow_mut();
+        let SelectedEngine { engine, loaded, .. } = &mut *selected;
+
+        match engine {
+            Some(engine) if engine_runs(universe) => {
+                if *loaded != Some(fingerprint(universe)) {
+                    engine.load(universe);
+                }
+                engine.advance(generations);
+                universe.advance_to(&engine.to_universe(), generations);
+                *loaded = Some(fingerprint(universe));
+            }
+            _ => {
+                for _ in 0..generations {
+                    universe.tick();
+                }
+            }
+        }
+    }
+
+    /// Advance a copy of the universe the given number of generations with the chosen engine, without drawing
+    /// it, and return the engine that advanced it and how many milliseconds it took.  The universe itself is
+    /// left as it is.
+    pub fn benchmark(&self, generations: u64) -> (EngineChoice, f64) {
+        let mut universe = self.universe.read().clone();
+        let engine = match engine_runs(&universe) {
+            true => self.engine(),
+            false => EngineChoice::Naive,
+        };
+
+        let started = now_ms();
+        self.advance(&mut universe, generations);
+        (engine, now_ms() - started)
+    }
+
+    /// Return the engine chosen to advance the universe.
+    pub fn engine(&self) -> EngineChoice {
+        self.engine.borrow().choice
+    }
+
+    /// Advance the universe with the given engine from now on, and remember the choice for later sessions.
+    ///
+    /// If the engine can't run here, the naive engine is used instead.  Returns the engine used.
+    pub fn set_engine(&self, choice: EngineChoice) -> EngineChoice {
+        let selected = engine_for(choice)
+            .map(|engine| SelectedEngine::new(choice, engine))
+            .unwrap_or_default();
+        let choice = selected.choice;
+
+        *self.engine.borrow_mut() = selected;
+        storage::store(ENGINE_KEY, choice.name());
+        choice
+    }
+
+    /// True if the universe can jump far ahead: with the hashlife engine, on a Life universe that wraps around
+    /// at its edges.  Any other universe advances a generation at a time, so a long jump would block the app
+    /// for minutes.
+    pub fn can_jump(&self) -> bool {
+        let universe = self.universe.read();
+        self.engine() == EngineChoice::HashLife
+            && engine_runs(&universe)
+            && universe.boundary() == Boundary::Wrap
+    }
+
+    /// Advance the universe the given number of generations at once, if it can jump (see can_jump()).
+    ///
+    /// With the hashlife engine, this can be millions of generations.  This ends any recording or replay,
+    /// since the jump isn't a command that can be replayed.
+    pub fn jump(&self, generations: u64) {
+        if !self.can_jump() {
+            return;
+        }
+        self.session.with_mut(|session| *session = Session::Live);
+        self.change_universe(|universe| self.advance(universe, generations));
+    }
+
+    /// Restore the universe as it was before its last change, returning false if there is no history left.
+    ///
+    /// This stops the universe, and ends any recording or replay, since stepping back isn't a command that
+    /// can be replayed.
+    pub fn step_back(&self) -> bool {
+        let Some(previous) = self.history.borrow_mut().pop(&mut self.budget.write()) else {
+            return false;
+        };
+
+        self.show_from_history(previous);
+        true
+    }
+
+    /// Show a universe from the history, stopping the universe and ending any recording or replay.
+    fn show_from_history(&self, shown: Universe) {
+        self.frames_running.set(false);
+        self.session.with_mut(|session| *session = Session::Live);
+        self.universe.with_mut(|universe| *universe = shown);
+        self.redraw.with_mut(|redraw| {
+            *redraw = Redraw::True;
+        });
+    }
+
+    /// Show the universe at the position in the history, or the present universe from the length of the
+    /// history on.
+    ///
+    /// Like stepping back, this stops the universe and ends any recording or replay.  The history is kept
+    /// until the universe is changed, so it can be scrubbed forwards again.
+    pub fn scrub_to(&self, position: usize) {
+        let shown = {
+            let universe = self.universe.read();
+            self.history.borrow_mut().scrub_to(position, &universe)
+        };
+        if let Some(shown) = shown {
+            self.show_from_history(shown);
+        }
+    }
+
+    /// Return where in the history the universe shown is from, or None if it is the present universe.
+    pub fn history_position(&self) -> Option<usize> {
+        self.history.borrow().position()
+    }
+
+    /// Return the number of universes in the history.
+    pub fn history_len(&self) -> usize {
+        self.history.borrow().len()
+    }
+
+    /// Return the most changes that can be stepped back.
+    pub fn history_length(&self) -> usize {
+        self.history.borrow().length()
+    }
+
+    /// Set the most changes that can be stepped back, and remember it for later sessions.
+    pub fn set_history_length(&self, length: usize) {
+        self.history
+            .borrow_mut()
+            .set_length(length, &mut self.budget.write());
+    }
+
+    /// Randomize the universe from a new seed and start recording every command dispatched after it.
+    pub fn start_recording(&self) {
+        let seed = random_seed();
+
+        // The recording starts from a fresh universe of the same size and settings, so it replays the same.
+        let recording = Recording::new(seed, &self.universe.read());
+        self.last_seed.set(Some((seed, DEFAULT_DENSITY)));
+        self.change_universe(|universe| *universe = recording.initial_universe());
+        self.session
+            .with_mut(|session| *session = Session::Recording(recording));
+    }
+
+    /// Stop recording, returning the recording if there was one.
+    pub fn stop_recording(&self) -> Option<Recording> {
+        let generation = self.universe.read().generation();
+        let mut stopped = None;
+
+        self.session.with_mut(|session| {
+            if let Session::Recording(recording) = std::mem::take(session) {
+                stopped = Some(recording.finish(generation));
+            }
+        });
+        stopped
+    }
+
+    /// Reset the universe to the start of the recording and run it.
+    pub fn start_replay(&self, recording: Recording) {
+        self.change_universe(|universe| *universe = recording.initial_universe());
+        self.session
+            .with_mut(|session| *session = Session::Replaying(Replay::new(recording)));
+        self.frames_running.set(true);
+    }
+
+    /// Stop replaying, leaving the universe as it is.
+    pub fn stop_replay(&self) {
+        self.session.with_mut(|session| *session = Session::Live);
+        self.frames_running.set(false);
+    }
+
+    /// Capture the complete state of the app.
+    pub fn snapshot(&self) -> Snapshot {
+        let TicksPerFrame(ticks) = *self.ticks_per_frame.read();
+
+        Snapshot::new(
+            &self.universe.read(),
+            ticks,
+            *self.frames_running.current(),
+            &self.annotations.read(),
+            *self.viewport.read(),
+            *self.tool.read(),
+        )
+    }
+
+    /// Restore the state of the app from a snapshot.
+    ///
+    /// This ends any recording or replay, since a restore can't be reproduced from commands.
+    pub fn restore(&self, snapshot: &Snapshot) {
+        self.session.with_mut(|session| *session = Session::Live);
+        self.change_universe(|universe| *universe = snapshot.universe());
+        self.ticks_per_frame.with_mut(|ticks_per_frame| {
+            *ticks_per_frame =
+                TicksPerFrame(snapshot.ticks_per_frame().clamp(1, MAX_TICKS_PER_FRAME));
+        });
+        self.frames_running.set(snapshot.running());
+        self.annotations
+            .with_mut(|annotations| *annotations = snapshot.annotations());
+        self.viewport.with_mut(|viewport| {
+            *viewport = snapshot
+                .viewport()
+                .unwrap_or_default()
+                .restored(&self.universe.read());
+        });
+        if let Some(tool) = snapshot.tool() {
+            self.tool.with_mut(|current| *current = tool);
+        }
+    }
+
+    /// Replace the universe with an empty universe of the given size, with the same rule, boundary, topology
+    /// and automaton.
+    pub fn resize(&self, width: u32, height: u32) {
+        self.dispatch(Command::Resize { width, height });
+    }
+
+    /// Modify the universe and set the redraw signal, saving the universe as it was to the history unless
+    /// the change is part of a batch.
+    fn change_universe(&self, change: impl FnOnce(&mut Universe)) {
+        if !self.batched.get() {
+            self.history
+                .borrow_mut()
+                .push(&self.universe.read(), &mut self.budget.write());
+        }
+        self.universe.with_mut(change);
+        self.redraw.with_mut(|redraw| {
+            *redraw = Redraw::True;
+        });
+    }
+}
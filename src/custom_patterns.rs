@@ -0,0 +1,188 @@
+//! Custom patterns: drawn in a small editor of their own, named, and saved into the pattern library, where
+//! they can be placed like the built-in patterns.  Custom patterns are kept in storage between sessions.
+
+use std::collections::BTreeSet;
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game_of_life::{
+        patterns::{Pattern, LIBRARY},
+        rle::write_rle,
+        universe::Universe,
+        SMALL_GRID_COLOR,
+    },
+    palette::Palette,
+    storage,
+};
+
+const CUSTOM_PATTERNS_KEY: &str = "custom_patterns.json";
+/// The rows and columns of the editor's grid.
+const EDITOR_CELLS: u32 = 16;
+/// The size of a cell of the editor, in pixels.
+const EDITOR_CELL_SIZE: i64 = 12;
+const DESCRIPTION: &str = "Drawn in the pattern editor.";
+
+/// A custom pattern as it is stored between sessions.
+#[derive(Serialize, Deserialize)]
+struct StoredPattern {
+    name: String,
+    rle: String,
+}
+
+/// The custom patterns, in the order they were saved.
+///
+/// Patterns of the library are static, so the text of each custom pattern is leaked to live as long as the
+/// app.  There are only as many as the user saves.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CustomPatterns(pub Vec<Pattern>);
+
+impl CustomPatterns {
+    /// Return the custom patterns saved in earlier sessions.
+    pub fn stored() -> CustomPatterns {
+        let stored: Vec<StoredPattern> = storage::load(CUSTOM_PATTERNS_KEY)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+
+        CustomPatterns(
+            stored
+                .into_iter()
+                .map(|pattern| custom_pattern(pattern.name, pattern.rle))
+                .collect(),
+        )
+    }
+
+    fn store(&self) {
+        let stored: Vec<StoredPattern> = self
+            .0
+            .iter()
+            .map(|pattern| StoredPattern {
+                name: pattern.name.to_string(),
+                rle: pattern.rle.to_string(),
+            })
+            .collect();
+        storage::store(
+            CUSTOM_PATTERNS_KEY,
+            &serde_json::to_string(&stored).expect("custom patterns should serialize"),
+        );
+    }
+
+    /// Save a pattern with the given name and (row, col) cells, replacing any custom pattern of that name.
+    fn save(&mut self, name: &str, cells: &BTreeSet<(u32, u32)>) {
+        let pattern = custom_pattern(name.to_string(), pattern_rle(cells));
+
+        match self.0.iter_mut().find(|saved| saved.name == name) {
+            Some(saved) => *saved = pattern,
+            None => self.0.push(pattern),
+        }
+        self.store();
+    }
+
+    /// Return the built-in patterns of the library followed by the custom patterns.
+    pub fn library(&self) -> Vec<Pattern> {
+        LIBRARY
+            .iter()
+            .copied()
+            .chain(self.0.iter().copied())
+            .collect()
+    }
+}
+
+fn custom_pattern(name: String, rle: String) -> Pattern {
+    Pattern {
+        name: Box::leak(name.into_boxed_str()),
+        description: DESCRIPTION,
+        rle: Box::leak(rle.into_boxed_str()),
+    }
+}
+
+/// Return the cells as RLE, with the top left corner of the cells at the top left of the pattern.
+fn pattern_rle(cells: &BTreeSet<(u32, u32)>) -> String {
+    let top = cells.iter().map(|(row, _)| *row).min().unwrap_or(0);
+    let left = cells.iter().map(|(_, col)| *col).min().unwrap_or(0);
+    let rows = cells
+        .iter()
+        .map(|(row, _)| row - top + 1)
+        .max()
+        .unwrap_or(1);
+    let cols = cells
+        .iter()
+        .map(|(_, col)| col - left + 1)
+        .max()
+        .unwrap_or(1);
+
+    let mut pattern = Universe::with_size(cols, rows);
+    let cells: Vec<(u32, u32)> = cells
+        .iter()
+        .map(|(row, col)| (row - top, col - left))
+        .collect();
+    pattern.set_cells(&cells);
+    write_rle(&pattern, "").expect("a new universe should run a life-like rule")
+}
+
+/// A small grid to draw a pattern on, and a name to save it to the pattern library under.
+#[component]
+pub fn PatternEditor(cx: Scope) -> Element {
+    let custom = use_shared_state::<CustomPatterns>(cx).unwrap();
+    let palette = *use_shared_state::<Palette>(cx).unwrap().read();
+    let cells = use_ref(cx, BTreeSet::<(u32, u32)>::new);
+    let name = use_state(cx, String::new);
+
+    let size = EDITOR_CELLS as i64 * EDITOR_CELL_SIZE;
+    let drawn = cells.read().clone();
+    let rects = (0..EDITOR_CELLS)
+        .flat_map(|row| (0..EDITOR_CELLS).map(move |col| (row, col)))
+        .map(|(row, col)| {
+            let fill = if drawn.contains(&(row, col)) {
+                palette.alive
+            } else {
+                palette.background
+            };
+
+            rsx! {
+                rect {
+                    key: "{row}-{col}",
+                    x: col as i64 * EDITOR_CELL_SIZE,
+                    y: row as i64 * EDITOR_CELL_SIZE,
+                    width: EDITOR_CELL_SIZE,
+                    height: EDITOR_CELL_SIZE,
+                    fill: fill,
+                    stroke: SMALL_GRID_COLOR,
+                    onclick: move |_| {
+                        cells.with_mut(|cells| {
+                            if !cells.remove(&(row, col)) {
+                                cells.insert((row, col));
+                            }
+                        });
+                    },
+                }
+            }
+        });
+    let can_save = !drawn.is_empty() && !name.trim().is_empty();
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            svg { width: size, height: size, rects }
+            div { display: "flex", flex_direction: "column", gap: "4px",
+                span { "Pattern editor" }
+                input {
+                    r#type: "text",
+                    placeholder: "name",
+                    value: "{name}",
+                    oninput: move |event| name.set(event.value.clone()),
+                }
+                button {
+                    disabled: !can_save,
+                    onclick: move |_| {
+                        custom.write().save(name.trim(), &cells.read());
+                        name.set(String::new());
+                        cells.write().clear();
+                    },
+                    "Save to library"
+                }
+                button { onclick: move |_| cells.write().clear(), "Clear" }
+            }
+        }
+    }
+}
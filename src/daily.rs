@@ -0,0 +1,115 @@
+//! Pattern of the day: a soup seeded from today's date, so everyone gets the same soup on the same day.
+//!
+//! The date is taken in UTC, so the soup changes at the same moment everywhere.  The seed is the date
+//! written as a number (20240131 for January 31st, 2024), and is shown so results can be compared.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::{
+        census::run_until_stable,
+        universe::{Universe, DEFAULT_DENSITY},
+    },
+    platform::unix_time_ms,
+};
+
+const MS_PER_DAY: f64 = 86_400_000.0;
+/// Generations the soup is run for before giving up on it settling.
+const STABLE_GENERATIONS: u64 = 10_000;
+
+/// Return the (year, month, day) of the given days since the Unix epoch.
+///
+/// From Howard Hinnant's civil_from_days: http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Today's date in UTC, and the seed of its pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct DailySeed {
+    year: i64,
+    month: u32,
+    day: u32,
+}
+
+impl DailySeed {
+    fn today() -> DailySeed {
+        let (year, month, day) = civil_from_days((unix_time_ms() / MS_PER_DAY).floor() as i64);
+        DailySeed { year, month, day }
+    }
+
+    fn seed(&self) -> u64 {
+        self.year as u64 * 10_000 + self.month as u64 * 100 + self.day as u64
+    }
+
+    fn date(&self) -> String {
+        format!("{}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Return a summary of how the universe settles, to share and compare with others.
+fn outcome(daily: DailySeed, universe: &Universe) -> String {
+    let mut settled = universe.clone();
+    let period = run_until_stable(&mut settled, STABLE_GENERATIONS);
+    let title = format!(
+        "Pattern of the day {} (seed {}, {})",
+        daily.date(),
+        daily.seed(),
+        universe.rule()
+    );
+    let population = settled.population();
+
+    match period {
+        Some(period) => format!(
+            "{title}: settled at generation {} with period {period} and {population} cells",
+            settled.generation()
+        ),
+        None => format!(
+            "{title}: still changing after {STABLE_GENERATIONS} generations, with {population} cells"
+        ),
+    }
+}
+
+/// A button to load the pattern of the day, and its outcome to share.
+#[component]
+pub fn PatternOfTheDay(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let shared = use_state(cx, || None::<String>);
+
+    let share = shared.get().as_ref().map(|text| {
+        rsx! {
+            input { r#type: "text", readonly: true, size: 80, value: "{text}" }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            button {
+                onclick: move |_| {
+                    let daily = DailySeed::today();
+                    controls.dispatch(Command::Seed { seed: daily.seed(), density: DEFAULT_DENSITY });
+                    shared.set(Some(outcome(daily, &universe.read())));
+                },
+                "Pattern of the day"
+            }
+            share
+        }
+    }
+}
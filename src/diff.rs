@@ -0,0 +1,158 @@
+//! Diff view: the difference between the universe and an earlier board, drawn over the grid.
+//!
+//! The earlier board is a bookmark of the universe, or a snapshot in a save slot.  Cells alive only now,
+//! cells alive only in the earlier board, and cells alive in both are each drawn in their own color.
+
+use std::collections::BTreeSet;
+
+use dioxus::prelude::*;
+
+use crate::{
+    game_of_life::{universe::Universe, CellLayer},
+    palette::Palette,
+    snapshot::{load_slot, SLOT_COUNT},
+};
+
+/// The board the universe is compared with, if the diff is shown.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiffBase(pub Option<Board>);
+
+/// The living cells of an earlier board, and where it came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board {
+    pub label: String,
+    pub cells: BTreeSet<(u32, u32)>,
+}
+
+impl Board {
+    fn of(universe: &Universe, label: String) -> Board {
+        Board {
+            label,
+            cells: universe
+                .get_living_cells()
+                .into_iter()
+                .map(|(x, y)| (y as u32, x as u32))
+                .collect(),
+        }
+    }
+}
+
+/// How a cell differs between the universe and the earlier board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Change {
+    /// Alive now, but not in the earlier board.
+    OnlyNow,
+    /// Alive in the earlier board, but not now.
+    OnlyBefore,
+    Both,
+}
+
+impl Change {
+    const ALL: [Change; 3] = [Change::OnlyNow, Change::OnlyBefore, Change::Both];
+
+    fn color(&self, palette: &Palette) -> &'static str {
+        match self {
+            Change::OnlyNow => palette.states[2],
+            Change::OnlyBefore => palette.states[1],
+            Change::Both => palette.trail,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Change::OnlyNow => "only now",
+            Change::OnlyBefore => "only before",
+            Change::Both => "both",
+        }
+    }
+}
+
+/// Return every cell alive now or in the earlier board, with how it changed.
+fn changes(now: &BTreeSet<(u32, u32)>, before: &BTreeSet<(u32, u32)>) -> Vec<((u32, u32), Change)> {
+    now.union(before)
+        .map(|cell| {
+            let change = match (now.contains(cell), before.contains(cell)) {
+                (true, false) => Change::OnlyNow,
+                (false, true) => Change::OnlyBefore,
+                _ => Change::Both,
+            };
+            (*cell, change)
+        })
+        .collect()
+}
+
+/// Controls to bookmark the universe, and to choose the board the diff is shown against.
+#[component]
+pub fn DiffControls(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let base = use_shared_state::<DiffBase>(cx).unwrap();
+    let palette = *use_shared_state::<Palette>(cx).unwrap().read();
+    // The board saved by the bookmark button.
+    let bookmark = use_ref(cx, || None::<Board>);
+
+    let showing = base.read().0.as_ref().map(|board| board.label.clone());
+    let slots = (1..=SLOT_COUNT).map(|slot| {
+        rsx! {
+            button {
+                key: "{slot}",
+                onclick: move |_| {
+                    base.write().0 = load_slot(slot)
+                        .map(|snapshot| Board::of(&snapshot.universe(), format!("slot {slot}")));
+                },
+                "Slot {slot}"
+            }
+        }
+    });
+    let legend = Change::ALL.into_iter().map(|change| {
+        rsx! {
+            span { key: "{change.label()}", color: change.color(&palette), "■ {change.label()}" }
+        }
+    });
+    let legend = showing.is_some().then(|| rsx! { legend });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            button {
+                onclick: move |_| {
+                    let universe = universe.read();
+                    bookmark.set(Some(Board::of(&universe, format!("bookmark at generation {}", universe.generation()))));
+                },
+                "Bookmark"
+            }
+            span { "Diff against:" }
+            button {
+                disabled: bookmark.read().is_none(),
+                onclick: move |_| base.write().0 = bookmark.read().clone(),
+                "Bookmark"
+            }
+            slots
+            button {
+                disabled: showing.is_none(),
+                onclick: move |_| base.write().0 = None,
+                "Hide diff"
+            }
+            legend
+        }
+    }
+}
+
+/// Draw the difference between the universe and the earlier board over the grid.
+#[component]
+pub fn DiffOverlay(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let base = use_shared_state::<DiffBase>(cx).unwrap();
+    let palette = *use_shared_state::<Palette>(cx).unwrap().read();
+
+    let base = base.read();
+    let before = &base.0.as_ref()?.cells;
+    let now = Board::of(&universe.read(), String::new()).cells;
+    let cells = changes(&now, before).into_iter().map(|((row, col), change)| {
+        rsx! { rect { x: col as i64, y: row as i64, width: 1, height: 1, fill: change.color(&palette) } }
+    });
+
+    render! {
+        CellLayer {
+            cells
+        }
+    }
+}
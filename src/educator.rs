@@ -0,0 +1,161 @@
+//! Educator mode: steps through the universe one cell at a time, explaining why each cell lives or dies
+//! in the next generation.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::universe::Universe,
+    game_of_life::CellLayer,
+};
+
+const FOCUS_COLOR: &str = "red";
+const NEIGHBOR_COLOR: &str = "orange";
+
+/// Walks through the cells of the universe that matter this generation: the living cells, and the dead
+/// cells with living neighbors.
+///
+/// Shared with the grid, which highlights the cell in focus and its neighbors.  Educator mode is on while
+/// the walkthrough is enabled.
+#[derive(Default)]
+pub struct Walkthrough {
+    enabled: bool,
+    /// (row, column) of the cell being explained.
+    focus: Option<(u32, u32)>,
+}
+
+impl Walkthrough {
+    /// Move the focus to the next cell (in rows) that is alive or has living neighbors.
+    ///
+    /// Returns false if there are no more cells to explain this generation.
+    fn advance(&mut self, universe: &Universe) -> bool {
+        let width = universe.width();
+        let start = match self.focus {
+            Some((row, col)) => row * width + col + 1,
+            None => 0,
+        };
+
+        self.focus = (start..width * universe.height())
+            .map(|idx| (idx / width, idx % width))
+            .find(|(row, col)| {
+                universe.is_alive(*row, *col) || universe.live_neighbor_count(*row, *col) > 0
+            });
+        self.focus.is_some()
+    }
+
+    /// Return why the cell in focus is alive or dead in the next generation.
+    fn explanation(&self, universe: &Universe) -> Option<String> {
+        let (row, col) = self.focus?;
+        let alive = universe.is_alive(row, col);
+        let live_neighbors = universe.live_neighbor_count(row, col);
+        let rule = universe.rule();
+
+        let outcome = match (alive, rule.next_alive(alive, live_neighbors)) {
+            (true, true) => "survives",
+            (true, false) if live_neighbors < 2 => "dies of underpopulation",
+            (true, false) => "dies of overpopulation",
+            (false, true) => "birth",
+            (false, false) => "stays dead",
+        };
+        let state = if alive { "alive" } else { "dead" };
+        let neighbors = if live_neighbors == 1 {
+            "neighbor"
+        } else {
+            "neighbors"
+        };
+
+        Some(format!(
+            "Row {row}, column {col} is {state} with {live_neighbors} live {neighbors} → {outcome} ({rule})"
+        ))
+    }
+}
+
+/// Toggles educator mode, and steps through the cells of each generation.
+#[component]
+pub fn EducatorControls(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let walkthrough = use_shared_state::<Walkthrough>(cx).unwrap();
+
+    let enabled = walkthrough.read().enabled;
+    let explanation = walkthrough.read().explanation(&universe.read());
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: enabled,
+                    onchange: move |event| {
+                        let enabled = event.value == "true";
+                        if enabled {
+                            controls.dispatch(Command::Stop);
+                        }
+                        *walkthrough.write() = Walkthrough { enabled, focus: None };
+                    }
+                }
+                "Educator mode"
+            }
+            if enabled {
+                rsx! {
+                    button {
+                        onclick: move |_| {
+                            let mut walkthrough = walkthrough.write();
+                            // After the last cell of a generation, advance to the next and start over.
+                            if !walkthrough.advance(&universe.read()) {
+                                controls.dispatch(Command::Step);
+                                walkthrough.advance(&universe.read());
+                            }
+                        },
+                        "Next cell"
+                    }
+                    span { font_family: "monospace",
+                        explanation.unwrap_or_else(|| "Press \"Next cell\" to explain the first cell.".to_string())
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Outline the cell in focus in the walkthrough, and its neighbors, filling the living neighbors.
+///
+/// Uses the same coordinates as GameOfLifeCells, so it can be drawn over them.
+#[component]
+pub fn WalkthroughHighlights(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let walkthrough = use_shared_state::<Walkthrough>(cx).unwrap();
+
+    let (row, col) = walkthrough.read().focus?;
+    let universe = universe.read();
+    let neighbors = universe
+        .neighbors(row, col)
+        .into_iter()
+        .map(|(neighbor_row, neighbor_col)| {
+            let opacity = if universe.is_alive(neighbor_row, neighbor_col) {
+                0.5
+            } else {
+                0.0
+            };
+
+            rsx! {
+                rect {
+                    x: neighbor_col as i64,
+                    y: neighbor_row as i64,
+                    width: 1,
+                    height: 1,
+                    fill: NEIGHBOR_COLOR,
+                    fill_opacity: opacity,
+                    stroke: NEIGHBOR_COLOR,
+                    stroke_width: 0.1
+                }
+            }
+        });
+
+    render! {
+        CellLayer {
+            neighbors.into_iter(),
+            rect { x: col as i64, y: row as i64, width: 1, height: 1, fill: "none", stroke: FOCUS_COLOR, stroke_width: 0.2 }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! postMessage API so a host page can control the simulation when the web build is embedded in an iframe.
+//!
+//! The host posts objects with a `type` field to the iframe's window:
+//! * `{type: "load", cells: [[row, col], ...]}`: clear the universe and set the given cells alive.
+//! * `{type: "start"}`, `{type: "stop"}`, `{type: "step"}`, `{type: "random"}`, `{type: "clear"}`
+//! * `{type: "random", density: percent}`: randomize with the given percent of cells alive (60 by default).
+//! * `{type: "speed", ticksPerFrame: n}`: advance the universe n generations each animation frame (at most
+//!   the largest fast-forward the speed controls offer).
+//! * `{type: "subscribe"}` / `{type: "unsubscribe"}`: start or stop receiving generation events.
+//!
+//! Subscribed hosts are sent `{type: "generation", generation: n}` each time the generation changes.
+
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::prelude::*;
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{
+    js_sys::{Array, Object, Reflect},
+    MessageEvent, Window,
+};
+
+use crate::{
+    command::{use_controls, Command, Controls},
+    game_of_life::universe::{Universe, DEFAULT_DENSITY},
+    platform::window,
+    speed::MAX_TICKS_PER_FRAME,
+};
+
+/// Host windows that subscribed to generation events, with the origin to post to.
+type Subscribers = Rc<RefCell<Vec<(Window, String)>>>;
+
+/// Holds the message listener, removing it from the window when dropped.
+struct MessageListener(Closure<dyn FnMut(MessageEvent)>);
+
+impl Drop for MessageListener {
+    fn drop(&mut self) {
+        let _ = window()
+            .remove_event_listener_with_callback("message", self.0.as_ref().unchecked_ref());
+    }
+}
+
+/// Listen for messages from a host page and dispatch them as commands.
+pub fn use_embed(cx: &ScopeState) {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let subscribers = cx.use_hook(Subscribers::default);
+
+    cx.use_hook(|| {
+        to_owned![controls, subscribers];
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            handle_message(&event, &controls, &subscribers);
+        });
+        window()
+            .add_event_listener_with_callback("message", on_message.as_ref().unchecked_ref())
+            .expect("should be able to listen for messages");
+
+        MessageListener(on_message)
+    });
+
+    // Tell subscribed hosts about each new generation.
+    let generation = universe.read().generation();
+    use_effect(cx, (&generation,), |(generation,)| {
+        to_owned![subscribers];
+        async move {
+            post_generation(&subscribers, generation);
+        }
+    });
+}
+
+/// Dispatch the command described by a message from the host.
+fn handle_message(event: &MessageEvent, controls: &Controls, subscribers: &Subscribers) {
+    let data = event.data();
+    let Some(message_type) = get(&data, "type").as_string() else {
+        return;
+    };
+
+    match message_type.as_str() {
+        "load" => controls.dispatch(Command::Load(parse_cells(&get(&data, "cells")))),
+        "start" => controls.dispatch(Command::Start),
+        "stop" => controls.dispatch(Command::Stop),
+        "step" => controls.dispatch(Command::Step),
+        "random" => controls.dispatch(Command::Random {
+            density: get(&data, "density")
+                .as_f64()
+                .map_or(DEFAULT_DENSITY, |density| density.clamp(0.0, 100.0) as u8),
+        }),
+        "clear" => controls.dispatch(Command::Clear),
+        "speed" => {
+            let ticks = get(&data, "ticksPerFrame").as_f64();
+            if let Some(ticks) = ticks.filter(|ticks| ticks.is_finite()) {
+                let ticks = ticks.clamp(1.0, MAX_TICKS_PER_FRAME as f64) as u32;
+                controls.dispatch(Command::SetTicksPerFrame(ticks));
+            }
+        }
+        "subscribe" | "unsubscribe" => {
+            let Some(host) = event
+                .source()
+                .and_then(|source| source.dyn_into::<Window>().ok())
+            else {
+                return;
+            };
+            let mut subscribers = subscribers.borrow_mut();
+
+            subscribers.retain(|(subscriber, _)| *subscriber != host);
+            if message_type == "subscribe" {
+                subscribers.push((host, event.origin()));
+            }
+        }
+        _ => console_log!("Ignoring unknown embed message type: {message_type}"),
+    }
+}
+
+/// Post a generation event to all subscribed hosts.
+fn post_generation(subscribers: &Subscribers, generation: u64) {
+    let subscribers = subscribers.borrow();
+    if subscribers.is_empty() {
+        return;
+    }
+
+    let message = Object::new();
+    let _ = Reflect::set(&message, &"type".into(), &"generation".into());
+    let _ = Reflect::set(&message, &"generation".into(), &(generation as f64).into());
+
+    for (host, origin) in subscribers.iter() {
+        let _ = host.post_message(&message, origin);
+    }
+}
+
+/// Convert an array of [row, col] arrays to cell coordinates, skipping malformed entries.
+fn parse_cells(value: &JsValue) -> Vec<(u32, u32)> {
+    let Some(cells) = value.dyn_ref::<Array>() else {
+        return Vec::new();
+    };
+
+    cells
+        .iter()
+        .filter_map(|cell| {
+            let cell = cell.dyn_into::<Array>().ok()?;
+            Some((cell.get(0).as_f64()? as u32, cell.get(1).as_f64()? as u32))
+        })
+        .collect()
+}
+
+/// Return the given property of a message, or undefined if it does not exist.
+fn get(target: &JsValue, key: &str) -> JsValue {
+    Reflect::get(target, &key.into()).unwrap_or(JsValue::UNDEFINED)
+}
@@ -0,0 +1,60 @@
+//! Saving and loading files.
+//!
+//! On the web, saved files are downloaded by the browser.  On desktop, they are written to the working directory.
+//! Files are loaded on both platforms from a file input element.
+
+use std::sync::Arc;
+
+use dioxus::html::FileEngine;
+
+/// Save the text to a file with the given name.
+pub fn save_text_file(file_name: &str, contents: &str) {
+    save_file(file_name, contents.as_bytes());
+}
+
+/// Save the bytes to a file with the given name.
+#[cfg(feature = "web")]
+pub fn save_file(file_name: &str, contents: &[u8]) {
+    use wasm_bindgen::JsCast;
+    use web_sys::{
+        js_sys::{Array, Uint8Array},
+        Blob, HtmlAnchorElement, Url,
+    };
+
+    use crate::platform::window;
+
+    let blob = Blob::new_with_u8_array_sequence(&Array::of1(&Uint8Array::from(contents)))
+        .expect("should be able to create a blob");
+    let url =
+        Url::create_object_url_with_blob(&blob).expect("should be able to create an object url");
+    let anchor = window()
+        .document()
+        .expect("window should have a document")
+        .create_element("a")
+        .expect("should be able to create an anchor")
+        .dyn_into::<HtmlAnchorElement>()
+        .expect("element should be an anchor");
+
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    let _ = Url::revoke_object_url(&url);
+}
+
+/// Save the bytes to a file with the given name.
+#[cfg(feature = "desktop")]
+pub fn save_file(file_name: &str, contents: &[u8]) {
+    match std::fs::write(file_name, contents) {
+        Ok(()) => println!("Saved {file_name}"),
+        Err(error) => println!("Could not save {file_name}: {error}"),
+    }
+}
+
+/// Read the first file chosen in a file input element as text.
+pub async fn read_chosen_file(files: Option<Arc<dyn FileEngine>>) -> Option<String> {
+    let files = files?;
+    let file_name = files.files().into_iter().next()?;
+
+    files.read_file_to_string(&file_name).await
+}
@@ -1,28 +1,39 @@
 //! Calculates the frames per second and places the text in the given id.
+//!
+//! Also shows the generations advanced per second, and the threads each tick runs on, which shows how much
+//! faster ticking on several threads is on large universes.
 
 use dioxus::prelude::*;
 
 use std::collections::VecDeque;
 
-#[cfg(feature = "web")]
-use crate::websys_utils::window;
-#[cfg(feature = "desktop")]
-use std::time::Instant;
+use crate::{command::use_controls, game_of_life::universe::Universe};
 
 /// Frames per second component that shows how quickly the app is rendering animation frames.
 ///
-/// The web version does not have access to std::time, so we use web_sys::Performace hooks instead.
+/// frame_time is the time of the latest animation frame from use_animation_frame(), so frames are timed
+/// when they are presented, not when this component gets to them.
 #[component]
-pub fn FramesPerSecond(cx: Scope, frame_id: i32) -> Element {
+pub fn FramesPerSecond(cx: Scope, frame_time: f64) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
     let frames_per_second = use_ref(cx, FramesPerSecond::new);
-    let fps_text = use_state(cx, || frames_per_second.read().text());
+    let fps_text = use_state(cx, || {
+        frames_per_second
+            .read()
+            .text(controls.dropped_frames(), universe.read().tick_threads())
+    });
 
-    use_effect(cx, (frame_id,), |(_frame_id,)| {
-        to_owned![frames_per_second, fps_text];
+    use_effect(cx, (frame_time,), |(frame_time,)| {
+        to_owned![controls, universe, frames_per_second, fps_text];
         async move {
+            let (generation, threads) = {
+                let universe = universe.read();
+                (universe.generation(), universe.tick_threads())
+            };
             frames_per_second.with_mut(|fps| {
-                fps.update_frame();
-                fps_text.modify(|_old_text| fps.text());
+                fps.update_frame(frame_time, generation);
+                fps_text.modify(|_old_text| fps.text(controls.dropped_frames(), threads));
             });
         }
     });
@@ -33,14 +44,13 @@ pub fn FramesPerSecond(cx: Scope, frame_id: i32) -> Element {
 }
 
 struct FramesPerSecond {
-    #[cfg(feature = "web")]
-    last_timeframe_stamp: f64,
-    #[cfg(feature = "desktop")]
-    last_timeframe_stamp: Instant,
-
+    /// The time of the last frame, or None before the first frame.
+    last_timeframe_stamp: Option<f64>,
     frames: VecDeque<f64>,
-    #[cfg(feature = "web")]
-    performance: web_sys::Performance,
+    /// The generation of the universe at the last frame.
+    last_generation: u64,
+    /// The generations advanced per second, from the frames that advanced the universe.
+    generations: VecDeque<f64>,
 }
 
 impl Default for FramesPerSecond {
@@ -50,33 +60,18 @@ impl Default for FramesPerSecond {
 }
 
 impl FramesPerSecond {
-    #[cfg(feature = "web")]
     pub fn new() -> FramesPerSecond {
-        let window = window();
-        let performance = window
-            .performance()
-            .expect("performance should be available");
-        let start = performance.now();
-
         FramesPerSecond {
-            last_timeframe_stamp: start,
+            last_timeframe_stamp: None,
             frames: VecDeque::new(),
-            performance,
+            last_generation: 0,
+            generations: VecDeque::new(),
         }
     }
 
-    #[cfg(feature = "desktop")]
-    pub fn new() -> FramesPerSecond {
-        let start = Instant::now();
-
-        FramesPerSecond {
-            last_timeframe_stamp: start,
-            frames: VecDeque::new(),
-        }
-    }
-
-    /// Display the current calculation for frames per second.
-    pub fn text(&self) -> String {
+    /// Display the current calculation for frames per second, the frames dropped while paused, the
+    /// generations per second, and the threads each tick runs on.
+    pub fn text(&self, dropped_frames: u64, tick_threads: usize) -> String {
         let mut sum = 0_f64;
         let mut min = f64::MAX;
         let mut max = f64::MIN;
@@ -95,6 +90,8 @@ impl FramesPerSecond {
         } else {
             0_f64
         };
+        let generations =
+            (self.generations.iter().sum::<f64>() / self.generations.len().max(1) as f64).round();
 
         format!(
             "\
@@ -103,35 +100,29 @@ Frames per second:
 avg of last 100 = {mean}
 min of last 100 = {min}
 max of last 100 = {max}
+ dropped paused = {dropped_frames}
+  generations/s = {generations}
+   tick threads = {tick_threads}
 "
         )
         .to_string()
     }
 
-    #[cfg(feature = "web")]
-    fn calc_delta(&self) -> (f64, f64) {
-        let now = self.performance.now();
-        let delta = now - self.last_timeframe_stamp;
-
-        (now, delta)
-    }
-
-    #[cfg(feature = "desktop")]
-    fn calc_delta(&self) -> (Instant, f64) {
-        let now = Instant::now();
-        let delta = now - self.last_timeframe_stamp;
-        let delta = delta.as_micros() as f64 / 1000_f64;
-
-        (now, delta)
-    }
-
-    /// Update the number of frames.
+    /// Update the number of frames, given the time of the frame in milliseconds and the generation of the
+    /// universe.
     ///
-    /// Call this every time a frame is presented.
-    pub fn update_frame(&mut self) {
-        let (now, delta) = self.calc_delta();
-
-        self.last_timeframe_stamp = now;
+    /// Call this every time a frame is presented.  The first frame only starts the timing, and a frame with
+    /// the same time as the last (such as the initial time of 0 from use_animation_frame()) is ignored.
+    pub fn update_frame(&mut self, frame_time: f64, generation: u64) {
+        let last = self.last_timeframe_stamp.replace(frame_time);
+        let last_generation = std::mem::replace(&mut self.last_generation, generation);
+
+        let Some(delta) = last.map(|last| frame_time - last) else {
+            return;
+        };
+        if delta <= 0_f64 {
+            return;
+        }
 
         let latest_fps = 1_f64 / delta * 1000_f64;
         self.frames.push_front(latest_fps);
@@ -139,5 +130,15 @@ max of last 100 = {max}
         if self.frames.len() > 100 {
             self.frames.pop_back();
         }
+
+        // Frames while paused, and frames after the universe was reset, say nothing about the speed.
+        if generation > last_generation {
+            let advanced = (generation - last_generation) as f64;
+            self.generations.push_front(advanced / delta * 1000_f64);
+
+            if self.generations.len() > 100 {
+                self.generations.pop_back();
+            }
+        }
     }
 }
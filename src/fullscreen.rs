@@ -0,0 +1,122 @@
+//! Borderless fullscreen on a chosen monitor, for showing the app on a screen of its own, such as at an
+//! exhibition.  The grid is fitted to the screen as it is to any window size.
+
+use dioxus::prelude::*;
+use dioxus_desktop::{
+    tao::{monitor::MonitorHandle, window::Fullscreen},
+    use_window, DesktopContext,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const FULLSCREEN_KEY: &str = "fullscreen.json";
+
+/// The monitor to go fullscreen on, and whether to go fullscreen at launch.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct FullscreenChoice {
+    /// The name of the monitor, or None for the monitor the window is on.
+    monitor: Option<String>,
+    at_launch: bool,
+}
+
+impl FullscreenChoice {
+    fn stored() -> FullscreenChoice {
+        storage::load(FULLSCREEN_KEY)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    fn store(&self) {
+        storage::store(
+            FULLSCREEN_KEY,
+            &serde_json::to_string(self).expect("fullscreen choice should serialize"),
+        );
+    }
+}
+
+/// Return the monitor with the given name, or the monitor the window is on if there is no such monitor.
+fn find_monitor(window: &DesktopContext, name: Option<&str>) -> Option<MonitorHandle> {
+    let window = window.webview.window();
+    window
+        .available_monitors()
+        .find(|monitor| name.is_some() && monitor.name().as_deref() == name)
+        .or_else(|| window.current_monitor())
+}
+
+/// Go borderless fullscreen on the named monitor, or leave fullscreen.
+fn set_fullscreen(window: &DesktopContext, on: bool, monitor: Option<&str>) {
+    let fullscreen = on.then(|| Fullscreen::Borderless(find_monitor(window, monitor)));
+    window.webview.window().set_fullscreen(fullscreen);
+}
+
+/// The monitor to go fullscreen on, a button to go fullscreen and back, and whether to go fullscreen
+/// at launch.
+#[component]
+pub fn FullscreenControls(cx: Scope) -> Element {
+    let window = use_window(cx);
+    let choice = use_state(cx, FullscreenChoice::stored);
+    let fullscreen = use_state(cx, || false);
+
+    use_effect(cx, (), |_| {
+        to_owned![window, choice, fullscreen];
+        async move {
+            if choice.get().at_launch {
+                set_fullscreen(&window, true, choice.get().monitor.as_deref());
+                fullscreen.set(true);
+            }
+        }
+    });
+
+    let names: Vec<String> = window
+        .webview
+        .window()
+        .available_monitors()
+        .filter_map(|monitor| monitor.name())
+        .collect();
+    let monitors = names.into_iter().map(|name| {
+        let selected = choice.get().monitor.as_deref() == Some(name.as_str());
+        rsx! {
+            option { key: "{name}", value: "{name}", selected: selected, "{name}" }
+        }
+    });
+
+    let update = move |updated: FullscreenChoice| {
+        updated.store();
+        choice.set(updated);
+    };
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            label {
+                "Fullscreen on "
+                select {
+                    onchange: move |event| {
+                        let monitor = (!event.value.is_empty()).then(|| event.value.clone());
+                        update(FullscreenChoice { monitor, ..choice.get().clone() });
+                    },
+                    option { value: "", selected: choice.get().monitor.is_none(), "this monitor" }
+                    monitors
+                }
+            }
+            button {
+                onclick: move |_| {
+                    let on = !*fullscreen.get();
+                    set_fullscreen(window, on, choice.get().monitor.as_deref());
+                    fullscreen.set(on);
+                },
+                if *fullscreen.get() { "Leave fullscreen" } else { "Fullscreen" }
+            }
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: choice.get().at_launch,
+                    onchange: move |event| {
+                        update(FullscreenChoice { at_launch: event.value == "true", ..choice.get().clone() });
+                    },
+                }
+                "At launch"
+            }
+        }
+    }
+}
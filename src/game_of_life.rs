@@ -2,39 +2,59 @@
 //!
 //! Adapted from the rust wasm tutorial: https://rustwasm.github.io/docs/book/game-of-life/introduction.html
 
-pub mod universe;
+pub mod engine;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+pub mod hashlife;
+pub mod random;
+pub mod sparse;
 
-use dioxus::prelude::*;
-use universe::{Universe, CELLS_PER_COL, CELLS_PER_ROW};
+#[allow(unused_imports)]
+pub use game_of_life_core::{
+    ant, automaton, census, life106, ltl, macrocell, motion, patterns, period, predecessor, rle,
+    rule, soup_search, transform, universe,
+};
 
-pub const GRID_ROWS: i64 = CELLS_PER_ROW as i64;
-pub const GRID_COLUMNS: i64 = CELLS_PER_COL as i64;
+use std::collections::HashSet;
 
-const SMALL_GRID_STROKE: f64 = 0.5;
-const BIG_GRID_STROKE: f64 = 1.0;
-// Cells need to be smaller than the grid so they don't cover it.  Since the grid stroke
-// straddles the virtual grid line, the adjustment is half the grid stroke size.
-const SMALL_GRID_STROKE_OFFSET: f64 = SMALL_GRID_STROKE / 2.0;
-const BIG_GRID_STROKE_OFFSET: f64 = BIG_GRID_STROKE / 2.0;
+use automaton::Automaton;
+use dioxus::{html::input_data::keyboard_types::Key, prelude::*};
+use universe::{Changes, Topology, Universe};
 
-pub const CELL_SIZE: i64 = 8; // px
+use crate::{
+    annotations::AnnotationOverlay,
+    brush::{Brush, BrushOutline},
+    clipboard::clipboard_key,
+    command::{use_controls, Command, Controls},
+    diff::DiffOverlay,
+    educator::WalkthroughHighlights,
+    layers::LayersOverlay,
+    palette::Palette,
+    performance::PerformanceMode,
+    placement::{stamp_pattern, Placement, PlacementGhost},
+    ruler::{Edge, Ruler},
+    seed::{Density, DensitySlider},
+    selection::{move_selection, Lifted, MoveDrag, Selection, SelectionOutline, Tool},
+    state_palette::PaintState,
+    theme::Appearance,
+    viewport::{Viewport, WHEEL_ZOOM_STEP, ZOOM_STEP},
+};
 
-pub const CELLS_WIDTH: f64 = (CELL_SIZE * GRID_COLUMNS) as f64;
-pub const CELLS_HEIGHT: f64 = (CELL_SIZE * GRID_ROWS) as f64;
+// Grid line widths of the standard theme.  Other themes may use other widths.
+pub const SMALL_GRID_STROKE: f64 = 0.5;
+pub const BIG_GRID_STROKE: f64 = 1.0;
 
-pub const GRID_WIDTH: f64 = CELLS_WIDTH + BIG_GRID_STROKE;
-pub const GRID_HEIGHT: f64 = CELLS_HEIGHT + BIG_GRID_STROKE;
+pub const CELL_SIZE: i64 = 8; // px
 
 /// We draw a big grid patten over the grid every BIG_GRID_MULTIPLIER cells.
-const BIG_GRID_MULTIPLIER: i64 = 8;
+pub const BIG_GRID_MULTIPLIER: i64 = 8;
 const BIG_GRID_SIZE: i64 = CELL_SIZE * BIG_GRID_MULTIPLIER;
 
-const SMALL_GRID_COLOR: &str = "#CCCCCC";
-const BIG_GRID_COLOR: &str = "gray";
-const ALIVE_CELL_COLOR: &str = "#000000";
+pub const SMALL_GRID_COLOR: &str = "#CCCCCC";
+pub const BIG_GRID_COLOR: &str = "gray";
 
 /// Redraw is a Property used to determine whether to redraw the cells.
-/// 
+///
 /// This needs to be an enum because to make it easier to use with use_shared_state_provider().
 #[derive(Eq, PartialEq)]
 pub enum Redraw {
@@ -48,6 +68,44 @@ impl Redraw {
     }
 }
 
+/// How the living cells are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CellRenderer {
+    /// A rect element for each cell.
+    #[default]
+    Rects,
+    /// A single path element drawing every cell, which has far fewer elements to update when many cells
+    /// are alive.
+    Path,
+}
+
+impl CellRenderer {
+    pub const ALL: [CellRenderer; 2] = [CellRenderer::Rects, CellRenderer::Path];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CellRenderer::Rects => "Rects",
+            CellRenderer::Path => "Path",
+        }
+    }
+
+    /// Return the renderer with the given name.
+    pub fn find(name: &str) -> Option<CellRenderer> {
+        CellRenderer::ALL
+            .into_iter()
+            .find(|renderer| renderer.name() == name)
+    }
+}
+
+/// The number of generations the universe advances each step: each animation frame, or at the steps each second
+/// chosen.  More than one fast-forwards, drawing the universe once after all of them.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct TicksPerFrame(pub u32);
+
+/// The (row, col) of the cell under the mouse on the grid, or None if the mouse isn't over the grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HoveredCell(pub Option<(u32, u32)>);
+
 /// This component draws the game of life grid, cells and buttons that can modify the universe of cells.
 ///
 /// frame_id represents each frame.  Each time the frame_id changes, the universe is advanced.
@@ -64,17 +122,41 @@ pub fn GameOfLife(cx: Scope<'a>, frame_id: i32) -> Element {
     let redraw = use_shared_state::<Redraw>(cx).unwrap();
     // List of the coordiantes of all currently living cells in the universe.
     let living_cells = use_ref(cx, || universe.read().get_living_cells());
+    let controls = use_controls(cx);
+    let palette = *use_shared_state::<Palette>(cx).unwrap().read();
+    let appearance = *use_shared_state::<Appearance>(cx).unwrap().read();
+    let tool = use_shared_state::<Tool>(cx).unwrap();
+    let selection = use_shared_state::<Selection>(cx).unwrap();
+    let viewport = use_shared_state::<Viewport>(cx).unwrap();
+    let density = use_shared_state::<Density>(cx).unwrap();
+    let hex = universe.read().topology() == Topology::Hex;
+    // Performance mode draws with the fastest renderer.
+    let renderer = if use_shared_state::<PerformanceMode>(cx)
+        .unwrap()
+        .read()
+        .is_on()
+    {
+        CellRenderer::Path
+    } else {
+        appearance.renderer
+    };
 
-    // Advance and redraw the universe when the frame_id is changed.
-    use_effect(cx, (frame_id,), |(_,)| {
-        to_owned![universe, redraw];
+    // Advance and redraw the universe when the frame_id is changed.  Frame 0 is the first render, not an
+    // animation frame.
+    use_effect(cx, (frame_id,), |(frame_id,)| {
+        to_owned![controls];
         async move {
-            universe.with_mut(|universe| {
-                universe.tick();
-            });
-            redraw.with_mut(|redraw| {
-                *redraw = Redraw::True;
-            });
+            if frame_id != 0 {
+                controls.advance_frame();
+            }
+        }
+    });
+
+    // Track the ages of cells only while cells are colored by age, since tracking them slows ticking.
+    use_effect(cx, (&appearance.color_by_age,), |(color_by_age,)| {
+        to_owned![universe];
+        async move {
+            universe.write().track_ages(color_by_age);
         }
     });
 
@@ -83,8 +165,18 @@ pub fn GameOfLife(cx: Scope<'a>, frame_id: i32) -> Element {
         to_owned![universe, living_cells];
         async move {
             if redraw.read().is_true() {
-                living_cells.with_mut(|living_cells| {
-                    *living_cells = universe.read().get_living_cells();
+                // Taking the changes doesn't change what the universe shows, so no one else needs to redraw.
+                // A universe that replaced the last one starts tracking ages here.
+                let changes = {
+                    let mut universe = universe.write_silent();
+                    universe.track_ages(appearance.color_by_age);
+                    universe.take_changes()
+                };
+                living_cells.with_mut(|living_cells| match changes {
+                    Changes::All => *living_cells = universe.read().get_living_cells(),
+                    Changes::Cells { births, deaths } => {
+                        apply_changes(living_cells, &births, &deaths)
+                    }
                 });
                 redraw.with_mut(|redraw| {
                     *redraw = Redraw::False;
@@ -93,71 +185,257 @@ pub fn GameOfLife(cx: Scope<'a>, frame_id: i32) -> Element {
         }
     });
 
+    // The age of each living cell, when cells are colored by age.
+    let ages: Vec<u16> = match appearance.color_by_age {
+        true => {
+            let universe = universe.read();
+            let ages = living_cells
+                .read()
+                .iter()
+                .map(|(x, y)| universe.age(*y as u32, *x as u32))
+                .collect();
+            ages
+        }
+        false => Vec::new(),
+    };
+
+    // The state of each living cell, when cells have more than two states.
+    let states: Vec<u8> = {
+        let universe = universe.read();
+        match universe.automaton().is_multi_state() {
+            true => living_cells
+                .read()
+                .iter()
+                .map(|(x, y)| universe.state(*y as u32, *x as u32))
+                .collect(),
+            false => Vec::new(),
+        }
+    };
+
+    // Cells need to be smaller than the grid so they don't cover it.  Since the grid stroke
+    // straddles the virtual grid line, the adjustment is half the grid stroke size.
+    let big_grid_stroke = appearance.theme.big_grid_stroke;
+    let offset = big_grid_stroke / 2.0;
+    let (cells_width, cells_height) = cells_size(&universe.read());
+    let (grid_width, grid_height) = (
+        cells_width + big_grid_stroke,
+        cells_height + big_grid_stroke,
+    );
+    // The grid is laid out for CELL_SIZE cells, and scaled up to the size of the cells of the theme.
+    let scale = appearance.scale();
+    // Zooming in shows part of the grid in the same space.
+    let view = *viewport.read();
+    let (view_x, view_y) = (view.left * CELL_SIZE as f64, view.top * CELL_SIZE as f64);
+    let (view_width, view_height) = (grid_width / view.zoom, grid_height / view.zoom);
+
+    // The gamepad cursor, on desktop.
+    #[cfg(feature = "desktop")]
+    let gamepad_cursor = render! { crate::gamepad::GamepadCursorOutline {} };
+    #[cfg(feature = "web")]
+    let gamepad_cursor = None::<VNode>;
+
+    // With rulers, the grid is the bottom right of a two by two layout, below and right of the rulers.
+    let rulers = appearance.rulers.then(|| {
+        rsx! {
+            div {}
+            Ruler { edge: Edge::Top }
+            Ruler { edge: Edge::Left }
+        }
+    });
+    let columns = if appearance.rulers {
+        "auto auto"
+    } else {
+        "auto"
+    };
+
     render! {
-        svg { width: GRID_WIDTH, height: GRID_HEIGHT,
-            g { transform: "translate({BIG_GRID_STROKE_OFFSET},{BIG_GRID_STROKE_OFFSET})",
-                GameOfLifeCells { live_cells: living_cells.read().clone() }
+        div { display: "grid", grid_template_columns: columns, justify_content: "center",
+            rulers
+            svg {
+                width: grid_width * scale,
+                height: grid_height * scale,
+                view_box: "{view_x} {view_y} {view_width} {view_height}",
+                // Focusable, so the arrow keys can nudge the selection with the move tool, and F can zoom to fit.
+                // Ctrl+C and Ctrl+V copy and paste patterns through the system clipboard.
+                tabindex: 0,
+                onkeydown: move |event| {
+                    if clipboard_key(cx, &event, controls, universe, *selection.read()) {
+                        return;
+                    }
+                    let offset = match event.key() {
+                        Key::Character(key) if key == "f" => {
+                            *viewport.write() = Viewport::fit(&universe.read());
+                            return;
+                        }
+                        Key::Character(key) if key == "+" || key == "=" || key == "-" => {
+                            let factor = if key == "-" { 1.0 / ZOOM_STEP } else { ZOOM_STEP };
+                            let (universe, view) = (universe.read(), *viewport.read());
+                            *viewport.write() = view.zoomed(factor, view.center(&universe), &universe);
+                            return;
+                        }
+                        Key::ArrowUp => (-1, 0),
+                        Key::ArrowDown => (1, 0),
+                        Key::ArrowLeft => (0, -1),
+                        Key::ArrowRight => (0, 1),
+                        _ => return,
+                    };
+                    if *tool.read() == Tool::Move && selection.read().0.is_some() {
+                        move_selection(controls, universe, selection, offset);
+                    }
+                },
+                rect { width: grid_width, height: grid_height, fill: palette.background }
+                g { transform: "translate({offset},{offset})",
+                    GameOfLifeCells { live_cells: living_cells.read().clone(), ages: ages, states: states, palette: palette, renderer: renderer, hex: hex }
+                }
+                GameOfLifeGrid {}
+                g { transform: "translate({offset},{offset})", AntMarkers {} }
+                g { transform: "translate({offset},{offset})", WalkthroughHighlights {} }
+                g { transform: "translate({offset},{offset})", DiffOverlay {} }
+                g { transform: "translate({offset},{offset})", LayersOverlay {} }
+                g { transform: "translate({offset},{offset})", SelectionOutline {} }
+                g { transform: "translate({offset},{offset})", BrushOutline {} }
+                g { transform: "translate({offset},{offset})", PlacementGhost {} }
+                g { transform: "translate({offset},{offset})", gamepad_cursor }
+                g { transform: "translate({offset},{offset})", AnnotationOverlay {} }
             }
-            GameOfLifeGrid {}
         }
         div { display: "flex", justify_content: "center",
-            button { onclick: move |_| { randomize_and_redraw(universe, redraw) }, "Random" }
-            button { onclick: move |_| { clear_and_redraw(universe, redraw) }, "Clear" }
+            button {
+                onclick: move |_| {
+                    let Density(density) = *density.read();
+                    controls.dispatch(Command::Random { density });
+                },
+                "Random"
+            }
+            DensitySlider {}
+            button { onclick: move |_| controls.dispatch(Command::Clear), "Clear" }
         }
     }
 }
 
-/// Randomize the universe and set the redraw signal.
-fn randomize_and_redraw(universe: &UseSharedState<Universe>, redraw: &UseSharedState<Redraw>) {
-    universe.with_mut(|universe| {
-        universe.random();
-    });
-    redraw.with_mut(|redraw| {
-        *redraw = Redraw::True;
-    });
+/// Update the (x, y) living cells, in column order, with the (row, col) cells born and the cells that died.
+fn apply_changes(living_cells: &mut Vec<(i64, i64)>, births: &[(u32, u32)], deaths: &[(u32, u32)]) {
+    let as_xy = |(row, col): &(u32, u32)| (*col as i64, *row as i64);
+
+    if !deaths.is_empty() {
+        let deaths: HashSet<(i64, i64)> = deaths.iter().map(as_xy).collect();
+        living_cells.retain(|cell| !deaths.contains(cell));
+    }
+    if !births.is_empty() {
+        living_cells.extend(births.iter().map(as_xy));
+        living_cells.sort_unstable();
+    }
 }
 
-/// Clear the universe and set the redraw signal.
-fn clear_and_redraw(universe: &UseSharedState<Universe>, redraw: &UseSharedState<Redraw>) {
-    universe.with_mut(|universe| {
-        universe.clear();
-    });
-    redraw.with_mut(|redraw| {
-        *redraw = Redraw::True;
-    });
+/// Return the (row, col) cells of a pattern moved to the center of the universe.
+///
+/// Patterns larger than the universe are placed at its top left corner, and cut off.
+pub fn centered(cells: Vec<(u32, u32)>, universe: &Universe) -> Vec<(u32, u32)> {
+    let rows = cells.iter().map(|(row, _)| row + 1).max().unwrap_or(0);
+    let cols = cells.iter().map(|(_, col)| col + 1).max().unwrap_or(0);
+    let top = universe.height().saturating_sub(rows) / 2;
+    let left = universe.width().saturating_sub(cols) / 2;
+
+    cells
+        .into_iter()
+        .map(|(row, col)| (row + top, col + left))
+        .filter(|(row, col)| *row < universe.height() && *col < universe.width())
+        .collect()
 }
 
-/// Determine where the click was on the grid and toggle the appropriate cell.
-fn click_grid(
-    event: Event<MouseData>,
-    universe: &UseSharedState<Universe>,
-    redraw: &UseSharedState<Redraw>,
-) {
+/// Return the (width, height) in pixels of the cells of the universe, before scaling by the theme.
+pub fn cells_size(universe: &Universe) -> (f64, f64) {
+    (
+        (CELL_SIZE * universe.width() as i64) as f64,
+        (CELL_SIZE * universe.height() as i64) as f64,
+    )
+}
+
+/// Determine the (row, column) of the cell under the mouse on the grid of the universe, drawn scaled up
+/// by the given scale and showing the viewport.
+fn grid_cell(
+    event: &Event<MouseData>,
+    scale: f64,
+    viewport: Viewport,
+    universe: &Universe,
+) -> (u32, u32) {
     // TODO: element_width/height should be from the bounding rect of the grid element, but I don't
     // yet have an easy way in the desktop version to get the grid element itself from the DOM.
     // When we need is the actual width and height of the element.
     //
     // This works for now because it assumes the rectangle of the grid is not scaled.
     // This would not be true if we scaled the element based on the size of the window for example.
-    let element_width = GRID_WIDTH;
-    let element_height = GRID_HEIGHT;
+    let (cells_width, cells_height) = cells_size(universe);
+    let (grid_width, grid_height) = (
+        cells_width + BIG_GRID_STROKE,
+        cells_height + BIG_GRID_STROKE,
+    );
+    let element_width = grid_width * scale;
+    let element_height = grid_height * scale;
 
-    let scale_x = GRID_WIDTH / element_width;
-    let scale_y = GRID_HEIGHT / element_height;
+    let scale_x = grid_width / viewport.zoom / element_width;
+    let scale_y = grid_height / viewport.zoom / element_height;
 
     let coords = event.element_coordinates();
-    let scaled_x = coords.x * scale_x;
-    let scaled_y = coords.y * scale_y;
+    let scaled_x = coords.x * scale_x + viewport.left * CELL_SIZE as f64;
+    let scaled_y = coords.y * scale_y + viewport.top * CELL_SIZE as f64;
+    let (x, y) = (scaled_x / CELL_SIZE as f64, scaled_y / CELL_SIZE as f64);
 
-    let col = (scaled_x / (CELL_SIZE as f64)).floor().min(GRID_HEIGHT) as u32;
-    let row = (scaled_y / (CELL_SIZE as f64)).floor().min(GRID_WIDTH) as u32;
+    let (row, col) = match universe.topology() {
+        Topology::Square => (y.floor() as i64, x.floor() as i64),
+        Topology::Hex => hex_cell_at(x, y),
+    };
+    // Clicks on the stroke past the last cell land in the last cell.
+    let last_col = universe.width().saturating_sub(1) as i64;
+    let last_row = universe.height().saturating_sub(1) as i64;
+    let (row, col) = (row.clamp(0, last_row) as u32, col.clamp(0, last_col) as u32);
 
-    universe.with_mut(|universe| {
-        universe.toggle_cell(row, col);
-    });
-    redraw.with_mut(|redraw| {
-        *redraw = Redraw::True;
-    });
+    (row, col)
+}
+
+/// Paint the cells under the brush around the (row, col) cell as part of a stroke of the draw or erase tool,
+/// unless they are already painted: the draw tool paints in the chosen paint state, which is alive for Life,
+/// and the erase tool empties cells.
+///
+/// The first cells the stroke changes are a change of their own, and the rest continue it, so the stroke
+/// steps back at once.
+fn paint_stroke(
+    controls: &Controls,
+    universe: &UseSharedState<Universe>,
+    stroke: &UseRef<Option<bool>>,
+    (row, col): (u32, u32),
+    (tool, brush, paint_state): (Tool, Brush, PaintState),
+) {
+    let (state, painted) = {
+        let universe = universe.read();
+        let state = match tool {
+            Tool::Draw => paint_state.state(&universe),
+            _ => 0,
+        };
+        let cells = universe.region_cells(row, col, &brush.offsets());
+        (
+            state,
+            cells
+                .iter()
+                .all(|(row, col)| universe.state(*row, *col) == state),
+        )
+    };
+    if painted {
+        return;
+    }
+    let command = match brush {
+        Brush::Single => Command::Paint { row, col, state },
+        brush => Command::Brush {
+            row,
+            col,
+            brush,
+            state,
+        },
+    };
+    match stroke.write_silent().replace(true) {
+        Some(true) => controls.dispatch_continuing(command),
+        _ => controls.dispatch(command),
+    }
 }
 
 /// Draw the grid lines that hold the cells in the game of life.
@@ -165,11 +443,11 @@ fn click_grid(
 /// The grid is drawn after the cells are, so that the grid lines
 /// are drawn over the cells, which looks a bit better than the reverse,
 /// since no grid line is ever obscured by cells.
-/// 
+///
 /// GameOfLifeGrid defines two SVG patterns:
 ///  * smallGrid, which is a square the size of a cell that draws thin grid lines
 ///  * bigGrid, which is the a square the size of N cells that draws a thicker grid line
-/// 
+///
 /// These patterns are actually just a top horizontal line and a left vertical line which
 /// when combined into a pattern become a grid: _|_|_| (like this but with an top instead of bottom line)
 ///                                             _|_|_|
@@ -180,70 +458,419 @@ fn click_grid(
 /// Each of the patterns nees to be shifted by half the stroke width so that the
 /// line is shown fully:
 /// * The big grid needs to be translated over by half the big grid stroke size so that
-///   the entire stroke is seen. 
+///   the entire stroke is seen.
 /// * the small grid also needs to translated over by half the small grid stroke size so that the
 ///   entire stroke is seen.  Now that it is seen, we can translate it by BIG_GRID_STROKE_OFFSET - SMALL_GRID_STROKE_OFFSET
 ///   to center it on the big grid.
-/// 
+///
 ///   Note that we can't just adjust it by BIG_GRID_OFFSET from the big grid, because while that will
 ///   put it in the right place, the entire pattern wont be in the view port, so there will be gaps.
-/// 
+///
 /// Also, this only works because the draw the big grid second covering up the small grid, which obscures
 /// the gaps of the small grid within the big grid.
 #[component]
 pub fn GameOfLifeGrid(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let appearance = *use_shared_state::<Appearance>(cx).unwrap().read();
     let universe = use_shared_state::<Universe>(cx).unwrap();
-    let redraw = use_shared_state::<Redraw>(cx).unwrap();
+    let tool = use_shared_state::<Tool>(cx).unwrap();
+    let paint_state = use_shared_state::<PaintState>(cx).unwrap();
+    let selection = use_shared_state::<Selection>(cx).unwrap();
+    let viewport = use_shared_state::<Viewport>(cx).unwrap();
+    let move_drag = use_shared_state::<MoveDrag>(cx).unwrap();
+    // The cell a selection is being dragged out from.
+    let drag_start = use_ref(cx, || None::<(u32, u32)>);
+    // The (x, y) page coordinates the view is being dragged from with the pan tool, and the view then.
+    let pan_start = use_ref(cx, || None::<((f64, f64), Viewport)>);
+    // While drawing or erasing, true once the stroke changed a cell, so the whole stroke steps back at once.
+    let stroke = use_ref(cx, || None::<bool>);
+    // The mouse wheel zooms around the cell under the mouse.
+    let hovered = use_shared_state::<HoveredCell>(cx).unwrap();
+    let brush = use_shared_state::<Brush>(cx).unwrap();
+    let placement = use_shared_state::<Placement>(cx).unwrap();
 
+    let theme = appearance.theme;
+    let scale = appearance.scale();
+    let view = *viewport.read();
+    let small_grid_offset = theme.small_grid_stroke / 2.0;
+    let big_grid_offset = theme.big_grid_stroke / 2.0;
     // Needed to center the small grid on the big grid
-    let small_adj = BIG_GRID_STROKE_OFFSET - SMALL_GRID_STROKE_OFFSET;
+    let small_adj = big_grid_offset - small_grid_offset;
+    // Grid lines are left out in performance mode, and for hexagonal cells they don't line up with, but the grid
+    // still takes the clicks.
+    let hex = universe.read().topology() == Topology::Hex;
+    let grid_fill = if hex
+        || use_shared_state::<PerformanceMode>(cx)
+            .unwrap()
+            .read()
+            .is_on()
+    {
+        "transparent"
+    } else {
+        "url(#bigAndSmallGrid)"
+    };
+    // The whole grid, even when zoomed in on part of it.
+    let (cells_width, cells_height) = cells_size(&universe.read());
+    let (grid_width, grid_height) = (
+        cells_width + theme.big_grid_stroke,
+        cells_height + theme.big_grid_stroke,
+    );
 
     render! {
-        svg { onclick: move |mouse_event| click_grid(mouse_event, universe, redraw),
+        svg {
+            width: grid_width,
+            height: grid_height,
+            onclick: move |mouse_event| {
+                // Placing a pattern takes over clicks from the tools.
+                if let Some(pattern) = placement.read().0 {
+                    let cell = grid_cell(&mouse_event, scale, view, &universe.read());
+                    stamp_pattern(controls, universe, &pattern, cell);
+                } else if *tool.read() == Tool::Toggle {
+                    let (row, col) = grid_cell(&mouse_event, scale, view, &universe.read());
+                    let command = {
+                        let universe = universe.read();
+                        match universe.automaton() {
+                            Automaton::LangtonsAnt => Command::ToggleAnt { row, col },
+                            automaton if automaton.is_multi_state() => {
+                                let state = paint_state.read().painted(universe.state(row, col), &universe);
+                                Command::Paint { row, col, state }
+                            }
+                            _ => Command::Toggle { row, col },
+                        }
+                    };
+                    controls.dispatch(command);
+                }
+            },
+            onmousedown: move |mouse_event| {
+                if placement.read().0.is_some() {
+                    return;
+                }
+                let cell = grid_cell(&mouse_event, scale, view, &universe.read());
+                match *tool.read() {
+                    Tool::Select => {
+                        drag_start.set(Some(cell));
+                        *selection.write() = Selection::between(cell, cell);
+                    }
+                    Tool::Move if selection.read().contains(cell) => {
+                        let cells = selection.read().living_cells(&universe.read());
+                        move_drag.write().0 = Some(Lifted { cells, grab: cell, offset: (0, 0) });
+                    }
+                    Tool::Pan => {
+                        let coords = mouse_event.page_coordinates();
+                        pan_start.set(Some(((coords.x, coords.y), view)));
+                    }
+                    tool @ (Tool::Draw | Tool::Erase) => {
+                        stroke.set(Some(false));
+                        paint_stroke(controls, universe, stroke, cell, (tool, *brush.read(), *paint_state.read()));
+                    }
+                    _ => {}
+                }
+            },
+            onmousemove: move |mouse_event| {
+                if let Some(((start_x, start_y), start_view)) = *pan_start.read() {
+                    // The view moves with the mouse, so the cell grabbed stays under it.
+                    let cell_pixels = CELL_SIZE as f64 * scale * start_view.zoom;
+                    let coords = mouse_event.page_coordinates();
+                    let moved = ((start_y - coords.y) / cell_pixels, (start_x - coords.x) / cell_pixels);
+                    *viewport.write() = start_view.panned(moved, &universe.read());
+                    return;
+                }
+                let cell = grid_cell(&mouse_event, scale, view, &universe.read());
+                if hovered.read().0 != Some(cell) {
+                    *hovered.write() = HoveredCell(Some(cell));
+                }
+                if stroke.read().is_some() {
+                    paint_stroke(controls, universe, stroke, cell, (*tool.read(), *brush.read(), *paint_state.read()));
+                }
+                if let Some(start) = *drag_start.read() {
+                    *selection.write() = Selection::between(start, cell);
+                }
+                let grab = move_drag.read().0.as_ref().map(|lifted| lifted.grab);
+                if let Some(grab) = grab {
+                    let offset = (cell.0 as i64 - grab.0 as i64, cell.1 as i64 - grab.1 as i64);
+                    let offset = selection.read().clamp_offset(offset, &universe.read());
+                    if let Some(lifted) = move_drag.write().0.as_mut() {
+                        lifted.offset = offset;
+                    }
+                }
+            },
+            onmouseup: move |_| {
+                drag_start.set(None);
+                pan_start.set(None);
+                stroke.set(None);
+                let lifted = move_drag.write().0.take();
+                if let Some(lifted) = lifted {
+                    move_selection(controls, universe, selection, lifted.offset);
+                }
+            },
+            onmouseleave: move |_| {
+                drag_start.set(None);
+                pan_start.set(None);
+                stroke.set(None);
+                *hovered.write() = HoveredCell(None);
+                if move_drag.read().0.is_some() {
+                    move_drag.write().0 = None;
+                }
+            },
+            // Zoom around the cell under the mouse, instead of scrolling the page.
+            prevent_default: "onwheel",
+            onwheel: move |wheel_event| {
+                let factor = match wheel_event.delta().strip_units().y {
+                    delta if delta < 0.0 => WHEEL_ZOOM_STEP,
+                    delta if delta > 0.0 => 1.0 / WHEEL_ZOOM_STEP,
+                    _ => return,
+                };
+                let (universe, view) = (universe.read(), *viewport.read());
+                let around = match hovered.read().0 {
+                    Some((row, col)) => (row as f64 + 0.5, col as f64 + 0.5),
+                    None => view.center(&universe),
+                };
+                *viewport.write() = view.zoomed(factor, around, &universe);
+            },
             defs {
                 pattern { id: "smallGrid", width: CELL_SIZE, height: CELL_SIZE, pattern_units: "userSpaceOnUse",
-                    g { transform: "translate({SMALL_GRID_STROKE_OFFSET},{SMALL_GRID_STROKE_OFFSET})",
+                    g { transform: "translate({small_grid_offset},{small_grid_offset})",
                         path {
                             d: "M {CELL_SIZE} 0 L 0 0 0 {CELL_SIZE}",
                             fill: "none",
-                            stroke: SMALL_GRID_COLOR,
-                            stroke_width: SMALL_GRID_STROKE
+                            stroke: theme.small_grid_color,
+                            stroke_width: theme.small_grid_stroke
                         }
                     }
                 }
                 pattern { id: "bigAndSmallGrid", width: BIG_GRID_SIZE, height: BIG_GRID_SIZE, pattern_units: "userSpaceOnUse",
-                    g { transform: "translate({BIG_GRID_STROKE_OFFSET},{BIG_GRID_STROKE_OFFSET})",
+                    g { transform: "translate({big_grid_offset},{big_grid_offset})",
                         path {
                             d: "M {BIG_GRID_SIZE} 0 L 0 0 0 {BIG_GRID_SIZE}",
                             fill: "none",
-                            stroke: BIG_GRID_COLOR,
-                            stroke_width: BIG_GRID_STROKE
+                            stroke: theme.big_grid_color,
+                            stroke_width: theme.big_grid_stroke
                         }
                     }
                     g { transform: "translate({small_adj},{small_adj})", rect { width: BIG_GRID_SIZE, height: BIG_GRID_SIZE, fill: "url(#smallGrid)" } }
                 }
             }
-            rect { width: "100%", height: "100%", fill: "url(#bigAndSmallGrid)" }
+            rect { width: "100%", height: "100%", fill: grid_fill }
         }
     }
 }
 
-/// Render all the given live cells.
+/// Render all the given live cells with the given renderer.
 ///
-/// We set the view_box to be the number of cells, and thus the local coordiantes, to be based on the number of cells.
-/// 
-/// Note that this is simple, but not likely to be efficient: the reactive framework must redraw the entire element when any
+/// The cells are drawn in a CellLayer, so the local coordiantes are based on the number of cells.
+///
+/// Note that drawing a rect per cell is simple, but not likely to be efficient: the reactive framework must redraw the entire element when any
 /// cell changes.  A more effecient approach would be to have heirarchical elements (GameOfLifeSector), which are only redrawn
 /// when a cell within them changes.  However, this would also require a more complicated data structure to represent the
 /// universe so that those sectors could be calculated effeciently.
+///
+/// The path renderer instead draws every cell with one element, so there is only one attribute to diff however many cells
+/// are alive.
+///
+/// Given the state of each cell, cells are colored by the state colors of the palette.  Otherwise, given the age
+/// of each cell, cells are colored along the age gradient of the palette, in AGE_SHADES shades.  Unused states and
+/// ages are empty.
+///
+/// Hexagonal cells are always drawn as paths, with odd rows offset half a cell to the right.
 #[component]
-pub fn GameOfLifeCells(cx: Scope, live_cells: Vec<(i64, i64)>) -> Element {
+pub fn GameOfLifeCells(
+    cx: Scope,
+    live_cells: Vec<(i64, i64)>,
+    ages: Vec<u16>,
+    states: Vec<u8>,
+    palette: Palette,
+    renderer: CellRenderer,
+    hex: bool,
+) -> Element {
+    if !states.is_empty() {
+        let colors = palette
+            .states
+            .iter()
+            .map(|color| color.to_string())
+            .collect();
+        let shades = states
+            .iter()
+            .map(|state| (*state as usize).clamp(1, palette.states.len()) - 1)
+            .collect();
+        return render! {
+            ShadedCells { live_cells: live_cells.clone(), shades: shades, colors: colors, renderer: *renderer, hex: *hex }
+        };
+    }
+    if !ages.is_empty() {
+        let colors = (0..AGE_SHADES)
+            .map(|shade| palette.age_color(shade as f64 / (AGE_SHADES - 1) as f64))
+            .collect();
+        let shades = ages.iter().map(|age| age_shade(*age)).collect();
+        return render! {
+            ShadedCells { live_cells: live_cells.clone(), shades: shades, colors: colors, renderer: *renderer, hex: *hex }
+        };
+    }
+    if *hex {
+        let path = hex_cells_path(live_cells);
+        return render! {
+            CellLayer { path { d: "{path}", fill: palette.alive } }
+        };
+    }
+    if *renderer == CellRenderer::Path {
+        let path = cells_path(live_cells);
+        return render! {
+            CellLayer { path { d: "{path}", fill: palette.alive } }
+        };
+    }
+
     let rendered_cells = live_cells
         .iter()
-        .map(|(x, y)| rsx! { GameOfLifeCell { x: *x, y: *y } });
+        .map(|(x, y)| rsx! { GameOfLifeCell { x: *x, y: *y, color: palette.alive.to_string() } });
+
+    render! {
+        CellLayer { rendered_cells }
+    }
+}
+
+/// The number of colors cells colored by age are drawn in.
+const AGE_SHADES: usize = 8;
+/// Cells alive for this many generations or more are drawn in the oldest color.
+const OLDEST_SHADED_AGE: u16 = 32;
+
+/// Return the shade a cell of the given age is drawn in, from 0 for cells just born to AGE_SHADES - 1.
+fn age_shade(age: u16) -> usize {
+    let age = age.clamp(1, OLDEST_SHADED_AGE) - 1;
+    age as usize * (AGE_SHADES - 1) / (OLDEST_SHADED_AGE - 1) as usize
+}
+
+/// The living cells colored by shade, given the index into colors of each cell's shade.
+#[component]
+fn ShadedCells(
+    cx: Scope,
+    live_cells: Vec<(i64, i64)>,
+    shades: Vec<usize>,
+    colors: Vec<String>,
+    renderer: CellRenderer,
+    hex: bool,
+) -> Element {
+    if *renderer == CellRenderer::Path || *hex {
+        // A path for each shade.
+        let mut shaded_cells = vec![Vec::new(); colors.len()];
+        for (cell, shade) in live_cells.iter().zip(shades) {
+            shaded_cells[*shade].push(*cell);
+        }
+        let paths = shaded_cells
+            .iter()
+            .zip(colors.iter())
+            .filter(|(cells, _)| !cells.is_empty())
+            .map(|(cells, color)| {
+                let path = if *hex {
+                    hex_cells_path(cells)
+                } else {
+                    cells_path(cells)
+                };
+                rsx! { path { d: "{path}", fill: "{color}" } }
+            });
+        return render! { CellLayer { paths } };
+    }
+
+    let rendered_cells = live_cells.iter().zip(shades).map(|((x, y), shade)| {
+        let color = colors[*shade].clone();
+        rsx! { GameOfLifeCell { x: *x, y: *y, color: color } }
+    });
 
     render! {
-        svg { view_box: "0 0 {CELLS_PER_COL} {CELLS_PER_ROW}", width: CELLS_WIDTH, height: CELLS_HEIGHT, rendered_cells }
+        CellLayer { rendered_cells }
+    }
+}
+
+/// Return the path data drawing a square for each (x, y) cell.
+pub fn cells_path(cells: &[(i64, i64)]) -> String {
+    let mut path = String::with_capacity(cells.len() * 16);
+    for (x, y) in cells {
+        path.push_str(&format!("M{x} {y}h1v1h-1z"));
+    }
+    path
+}
+
+/// Return the path data drawing a hexagon for each (x, y) cell of a hexagonal universe.
+///
+/// Each hexagon is as wide as a cell, with its points reaching a sixth of a cell into the rows above and below,
+/// so with odd rows offset half a cell to the right, the hexagons tile the universe.
+pub fn hex_cells_path(cells: &[(i64, i64)]) -> String {
+    let mut path = String::with_capacity(cells.len() * 64);
+    for (x, y) in cells {
+        let left = *x as f64 + (y % 2) as f64 / 2.0;
+        let top = *y as f64 - 1.0 / 6.0;
+        path.push_str(&format!(
+            "M{} {top:.4}l0.5 0.3333v0.6667l-0.5 0.3333l-0.5 -0.3333v-0.6667z",
+            left + 0.5
+        ));
+    }
+    path
+}
+
+/// Return the (row, col) of the hexagonal cell at (x, y) in cells, in a universe whose odd rows are offset half a
+/// cell to the right (see hex_cells_path).
+fn hex_cell_at(x: f64, y: f64) -> (i64, i64) {
+    let row = y.floor() as i64;
+    let offset = (row.rem_euclid(2)) as f64 / 2.0;
+    let col = (x - offset).floor() as i64;
+
+    // Within the row, the cell is a hexagon whose points reach into the rows above and below, so the corners of
+    // the row belong to the cells of the rows above and below.
+    let (across, down) = (x - offset - col as f64 - 0.5, y - row as f64 - 0.5);
+    let edge = 1.0 / 3.0 + (0.5 - across.abs()) * 2.0 / 3.0;
+    if down.abs() <= edge {
+        return (row, col);
+    }
+    // The cells above and below are to the left in an even row, and to the right in an odd row.
+    let neighbor_col = col + (across >= 0.0) as i64 - (offset == 0.0) as i64;
+    (row + down.signum() as i64, neighbor_col)
+}
+
+/// An svg over the cells of the universe, whose local coordinates are (column, row) cells.
+///
+/// Overlays drawn over the grid are drawn in a CellLayer, so they line up with the cells whatever the size
+/// of the universe.  It ignores the mouse, so clicks go through to the grid.
+#[component]
+pub fn CellLayer<'a>(cx: Scope<'a>, children: Element<'a>) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+
+    let universe = universe.read();
+    let (width, height) = (universe.width(), universe.height());
+    let (cells_width, cells_height) = cells_size(&universe);
+
+    render! {
+        svg {
+            view_box: "0 0 {width} {height}",
+            width: cells_width,
+            height: cells_height,
+            pointer_events: "none",
+            children
+        }
+    }
+}
+
+/// An arrow on the cell of each ant of Langton's Ant, pointing the way the ant faces.
+#[component]
+fn AntMarkers(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let palette = *use_shared_state::<Palette>(cx).unwrap().read();
+
+    let ants = universe.read().ants().to_vec();
+    if ants.is_empty() {
+        return None;
+    }
+    let markers = ants.into_iter().map(|ant| {
+        let (x, y) = (ant.col as f64 + 0.5, ant.row as f64 + 0.5);
+        let degrees = ant.heading.degrees();
+        rsx! {
+            polygon {
+                points: "{x} {y - 0.4} {x + 0.35} {y + 0.35} {x} {y + 0.15} {x - 0.35} {y + 0.35}",
+                transform: "rotate({degrees} {x} {y})",
+                fill: palette.states[1],
+            }
+        }
+    });
+
+    render! {
+        CellLayer { markers }
     }
 }
 
@@ -253,6 +880,6 @@ pub fn GameOfLifeCells(cx: Scope, live_cells: Vec<(i64, i64)>) -> Element {
 /// Thus the x and y coordiates are the row and col of the cell to be rendered, and the height and width are
 /// both 1.  So, we let SVG handle any scaling math.
 #[component]
-pub fn GameOfLifeCell(cx: Scope, x: i64, y: i64) -> Element {
-    render! { rect { x: *x, y: *y, width: 1, height: 1, fill: ALIVE_CELL_COLOR } }
-}
\ No newline at end of file
+pub fn GameOfLifeCell(cx: Scope, x: i64, y: i64, color: String) -> Element {
+    render! { rect { x: *x, y: *y, width: 1, height: 1, fill: "{color}" } }
+}
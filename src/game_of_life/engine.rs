@@ -0,0 +1,174 @@
+//! Interchangeable implementations of the game of life rules.
+//!
+//! Every engine must produce exactly the same generations as the naive Universe, which makes it the
+//! reference that other engines are checked against.
+
+use super::universe::Universe;
+
+/// An implementation of the game of life that can be loaded with a universe, advanced, and read back.
+pub trait UniverseEngine {
+    /// A short name for the engine, shown in comparisons.
+    fn name(&self) -> &'static str;
+
+    /// Replace the state of the engine with the given universe.
+    fn load(&mut self, universe: &Universe);
+
+    /// Advance one generation.
+    fn tick(&mut self);
+
+    /// Advance the given number of generations.
+    fn advance(&mut self, generations: u64) {
+        for _ in 0..generations {
+            self.tick();
+        }
+    }
+
+    /// Return the current state of the engine as a universe.
+    fn to_universe(&self) -> Universe;
+}
+
+/// The naive engine: every cell of the universe is visited on each tick.
+impl UniverseEngine for Universe {
+    fn name(&self) -> &'static str {
+        "naive"
+    }
+
+    fn load(&mut self, universe: &Universe) {
+        *self = universe.clone();
+    }
+
+    fn tick(&mut self) {
+        Universe::tick(self);
+    }
+
+    fn to_universe(&self) -> Universe {
+        self.clone()
+    }
+}
+
+/// The engines the app can advance the universe with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EngineChoice {
+    /// The universe advances itself.
+    #[default]
+    Naive,
+    Sparse,
+    HashLife,
+    #[cfg(feature = "gpu")]
+    Gpu,
+}
+
+impl EngineChoice {
+    /// Return every engine of this build, starting with the naive reference engine.
+    pub fn all() -> Vec<EngineChoice> {
+        vec![
+            EngineChoice::Naive,
+            EngineChoice::Sparse,
+            EngineChoice::HashLife,
+            #[cfg(feature = "gpu")]
+            EngineChoice::Gpu,
+        ]
+    }
+
+    /// Return the name of the engine, the same as UniverseEngine::name().
+    pub fn name(&self) -> &'static str {
+        match self {
+            EngineChoice::Naive => "naive",
+            EngineChoice::Sparse => "sparse",
+            EngineChoice::HashLife => "hashlife",
+            #[cfg(feature = "gpu")]
+            EngineChoice::Gpu => "gpu",
+        }
+    }
+
+    pub fn find(name: &str) -> Option<EngineChoice> {
+        EngineChoice::all()
+            .into_iter()
+            .find(|choice| choice.name() == name)
+    }
+
+    /// Return a new engine, or None if it can't run here.
+    pub fn create(&self) -> Option<Box<dyn UniverseEngine>> {
+        match self {
+            EngineChoice::Naive => Some(Box::new(Universe::new())),
+            EngineChoice::Sparse => Some(Box::new(super::sparse::SparseEngine::new())),
+            EngineChoice::HashLife => Some(Box::new(super::hashlife::HashLifeEngine::new())),
+            #[cfg(feature = "gpu")]
+            EngineChoice::Gpu => super::gpu::GpuEngine::new()
+                .map(|engine| Box::new(engine) as Box<dyn UniverseEngine>),
+        }
+    }
+}
+
+/// Return one instance of every available engine, starting with the naive reference engine.
+///
+/// The GPU engine is only available when there is a GPU to run it on.
+pub fn all_engines() -> Vec<Box<dyn UniverseEngine>> {
+    EngineChoice::all()
+        .iter()
+        .filter_map(EngineChoice::create)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_of_life::universe::Boundary;
+
+    /// Return a randomized universe of the given size and boundary.
+    fn random_universe(width: u32, height: u32, boundary: Boundary) -> Universe {
+        let mut universe = Universe::with_size(width, height);
+        universe.set_boundary(boundary);
+        universe.random_with_seed(width as u64 * 31 + height as u64);
+        universe
+    }
+
+    #[test]
+    fn every_engine_matches_naive() {
+        for boundary in [Boundary::Wrap, Boundary::Dead] {
+            for (width, height) in [(64, 64), (67, 45), (16, 100)] {
+                let start = random_universe(width, height, boundary);
+                let mut naive = start.clone();
+
+                let mut engines = all_engines();
+                for engine in engines.iter_mut() {
+                    engine.load(&start);
+                }
+                for generation in 1..=50 {
+                    naive.tick();
+                    for engine in engines.iter_mut() {
+                        engine.tick();
+                        assert!(
+                            engine.to_universe().same_cells(&naive),
+                            "{} differs from naive on {width}x{height} {boundary} at generation {generation}",
+                            engine.name()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_engine_advances_many_generations_at_once_like_naive() {
+        for boundary in [Boundary::Wrap, Boundary::Dead] {
+            let start = random_universe(64, 48, boundary);
+            let mut naive = start.clone();
+            for _ in 0..300 {
+                naive.tick();
+            }
+
+            for mut engine in all_engines() {
+                engine.load(&start);
+                engine.advance(300);
+                let advanced = engine.to_universe();
+                assert!(
+                    advanced.same_cells(&naive),
+                    "{} differs from naive on {boundary} after 300 generations",
+                    engine.name()
+                );
+                assert_eq!(advanced.generation(), 300, "{}", engine.name());
+            }
+        }
+    }
+}
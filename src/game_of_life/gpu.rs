@@ -0,0 +1,316 @@
+//! An engine that advances the universe with a compute shader on the GPU, for very large universes.
+//!
+//! The cells are kept in two buffers on the GPU.  Each tick reads the cells from one buffer and writes
+//! the next generation to the other, and then the buffers swap roles, so the cells only come back from
+//! the GPU when the universe is read.
+
+use super::{
+    engine::UniverseEngine,
+    universe::{Boundary, Universe},
+};
+
+/// Cells handled by each workgroup, along each side.
+const WORKGROUP_SIZE: u32 = 8;
+
+const SHADER: &str = r#"
+struct Size {
+    width: u32,
+    height: u32,
+    // 1 if the universe wraps around at its edges, 0 if the cells past the edges are dead.
+    wraps: u32,
+    unused: u32,
+}
+
+@group(0) @binding(0) var<uniform> size: Size;
+// Whether a cell is alive next generation, indexed by alive * 9 + living neighbors.
+@group(0) @binding(1) var<storage, read> rule: array<u32, 18>;
+@group(0) @binding(2) var<storage, read> current: array<u32>;
+@group(0) @binding(3) var<storage, read_write> next: array<u32>;
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let col = id.x;
+    let row = id.y;
+    if (col >= size.width || row >= size.height) {
+        return;
+    }
+
+    var neighbors = 0u;
+    for (var delta_row = 0u; delta_row < 3u; delta_row++) {
+        for (var delta_col = 0u; delta_col < 3u; delta_col++) {
+            if (delta_row == 1u && delta_col == 1u) {
+                continue;
+            }
+            // One more than the row and column of the neighbor, so the cells past the edges are 0 and
+            // height + 1 or width + 1.
+            let row_past = row + delta_row;
+            let col_past = col + delta_col;
+            let past_edge = row_past == 0u || row_past > size.height || col_past == 0u || col_past > size.width;
+            if (past_edge && size.wraps == 0u) {
+                continue;
+            }
+            let neighbor_row = (row_past + size.height - 1u) % size.height;
+            let neighbor_col = (col_past + size.width - 1u) % size.width;
+            neighbors += current[neighbor_row * size.width + neighbor_col];
+        }
+    }
+
+    let index = row * size.width + col;
+    next[index] = rule[current[index] * 9u + neighbors];
+}
+"#;
+
+/// The buffers of a universe of one size.
+struct Buffers {
+    width: u32,
+    height: u32,
+    size: wgpu::Buffer,
+    rule: wgpu::Buffer,
+    /// The two buffers of cells, which take turns holding the current generation.
+    cells: [wgpu::Buffer; 2],
+    /// Reads from the first buffer of cells and writes to the second, and the other way round.
+    bind_groups: [wgpu::BindGroup; 2],
+    /// Where cells are copied to be read back.
+    staging: wgpu::Buffer,
+}
+
+/// The GPU engine.
+pub struct GpuEngine {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    buffers: Option<Buffers>,
+    /// The index of the buffer of cells holding the current generation.
+    current: usize,
+    universe: Universe,
+}
+
+impl GpuEngine {
+    /// Return the engine, or None if there is no GPU to run it on.
+    pub fn new() -> Option<GpuEngine> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))?;
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("game of life"),
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::downlevel_defaults(),
+            },
+            None,
+        ))
+        .ok()?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("tick"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("tick"),
+            layout: None,
+            module: &module,
+            entry_point: "main",
+        });
+
+        Some(GpuEngine {
+            device,
+            queue,
+            pipeline,
+            buffers: None,
+            current: 0,
+            universe: Universe::new(),
+        })
+    }
+
+    /// Make buffers for a universe of the given size, unless the current buffers are that size.
+    fn resize(&mut self, width: u32, height: u32) {
+        let reusable = self
+            .buffers
+            .as_ref()
+            .is_some_and(|buffers| buffers.width == width && buffers.height == height);
+        if !reusable {
+            self.buffers = Some(self.create_buffers(width, height));
+        }
+    }
+
+    fn create_buffers(&self, width: u32, height: u32) -> Buffers {
+        let device = &self.device;
+        let cells_size = (width as u64 * height as u64 * 4).max(4);
+
+        let size = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("size"),
+            size: 4 * 4,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let rule = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("rule"),
+            size: 18 * 4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let cells = [0, 1].map(|_| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("cells"),
+                size: cells_size,
+                usage: wgpu::BufferUsages::STORAGE
+                    | wgpu::BufferUsages::COPY_SRC
+                    | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        });
+        let layout = self.pipeline.get_bind_group_layout(0);
+        let bind_groups = [(0, 1), (1, 0)].map(|(from, to): (usize, usize)| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("tick"),
+                layout: &layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: size.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: rule.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: cells[from].as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: cells[to].as_entire_binding(),
+                    },
+                ],
+            })
+        });
+        let staging = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("staging"),
+            size: cells_size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Buffers {
+            width,
+            height,
+            size,
+            rule,
+            cells,
+            bind_groups,
+            staging,
+        }
+    }
+}
+
+impl UniverseEngine for GpuEngine {
+    fn name(&self) -> &'static str {
+        "gpu"
+    }
+
+    fn load(&mut self, universe: &Universe) {
+        let rule = universe.rule();
+        let rule_table: Vec<u32> = [false, true]
+            .into_iter()
+            .flat_map(|alive| {
+                (0..=8).map(move |neighbors| rule.next_alive(alive, neighbors) as u32)
+            })
+            .collect();
+        let cells: Vec<u32> = (0..universe.height())
+            .flat_map(|row| (0..universe.width()).map(move |col| (row, col)))
+            .map(|(row, col)| universe.is_alive(row, col) as u32)
+            .collect();
+
+        self.universe = universe.clone();
+        self.current = 0;
+        self.resize(universe.width(), universe.height());
+        let buffers = self.buffers.as_ref().unwrap();
+        let wraps = (universe.boundary() == Boundary::Wrap) as u32;
+        self.queue.write_buffer(
+            &buffers.size,
+            0,
+            &words_to_bytes(&[buffers.width, buffers.height, wraps, 0]),
+        );
+        self.queue
+            .write_buffer(&buffers.rule, 0, &words_to_bytes(&rule_table));
+        self.queue
+            .write_buffer(&buffers.cells[0], 0, &words_to_bytes(&cells));
+    }
+
+    fn tick(&mut self) {
+        let Some(buffers) = self.buffers.as_ref() else {
+            return;
+        };
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("tick"),
+            });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("tick"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &buffers.bind_groups[self.current], &[]);
+            pass.dispatch_workgroups(
+                buffers.width.div_ceil(WORKGROUP_SIZE),
+                buffers.height.div_ceil(WORKGROUP_SIZE),
+                1,
+            );
+        }
+        self.queue.submit(Some(encoder.finish()));
+
+        self.current = 1 - self.current;
+        self.universe.set_generation(self.universe.generation() + 1);
+    }
+
+    fn to_universe(&self) -> Universe {
+        let mut universe = self.universe.clone();
+        let Some(buffers) = self.buffers.as_ref() else {
+            return universe;
+        };
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("read"),
+            });
+        encoder.copy_buffer_to_buffer(
+            &buffers.cells[self.current],
+            0,
+            &buffers.staging,
+            0,
+            buffers.staging.size(),
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = buffers.staging.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        self.device.poll(wgpu::Maintain::Wait);
+        let living: Vec<(u32, u32)> = slice
+            .get_mapped_range()
+            .chunks_exact(4)
+            .take((buffers.width * buffers.height) as usize)
+            .enumerate()
+            .filter(|(_, word)| word[0] != 0)
+            .map(|(idx, _)| (idx as u32 / buffers.width, idx as u32 % buffers.width))
+            .collect();
+        buffers.staging.unmap();
+
+        let generation = universe.generation();
+        universe.clear();
+        universe.set_cells(&living);
+        universe.set_generation(generation);
+        universe
+    }
+}
+
+/// Return the words as the little endian bytes the GPU reads.
+fn words_to_bytes(words: &[u32]) -> Vec<u8> {
+    words.iter().flat_map(|word| word.to_le_bytes()).collect()
+}
@@ -0,0 +1,431 @@
+//! An engine that keeps the universe as a quadtree of shared nodes and remembers how each node advances,
+//! so repetitive patterns can be advanced millions of generations at once (Gosper's HashLife).
+//!
+//! A node of level k is a square of 2^k cells on each side, made of four nodes of level k - 1.  Equal
+//! nodes are stored once.  The result of a node of level k is its center, of level k - 1, advanced up to
+//! 2^(k - 2) generations, which depends on nothing outside the node, and is remembered.
+//!
+//! A universe that wraps around at its edges is advanced as an infinite plane tiled with copies of it,
+//! any power of two generations at a time.  With dead edges, cells past the edges must be killed every
+//! generation, so the universe is advanced one generation at a time and is no faster than the other
+//! engines.
+//!
+//! See https://conwaylife.com/wiki/HashLife
+
+use std::collections::HashMap;
+
+use super::{
+    engine::UniverseEngine,
+    rule::Rule,
+    universe::{Boundary, Universe},
+};
+
+/// The index of a node in the store.
+type NodeId = u32;
+
+/// The dead and living cells: the nodes of level 0.
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+
+/// The most generations advanced at once is 2^MAX_STEP_LEVEL.
+const MAX_STEP_LEVEL: u32 = 48;
+/// The number of nodes kept before the store is emptied, to bound its memory.  A step of more than one
+/// generation that would need more nodes is given up part way, and taken again as smaller steps.
+const MAX_NODES: usize = 1 << 22;
+
+#[derive(Clone, Copy)]
+struct Node {
+    level: u32,
+    /// The north west, north east, south west and south east quarters.  Unused for cells.
+    children: [NodeId; 4],
+    population: u64,
+}
+
+/// The nodes, and what is remembered about them.
+struct Store {
+    nodes: Vec<Node>,
+    /// The node made of each four quarters.
+    joined: HashMap<[NodeId; 4], NodeId>,
+    /// The result of each node advanced 2^step generations, by (node, step).
+    results: HashMap<(NodeId, u32), NodeId>,
+    /// The node of dead cells of each level.
+    empty: Vec<NodeId>,
+    rule: Rule,
+}
+
+impl Store {
+    fn new(rule: Rule) -> Store {
+        let cell = |population| Node {
+            level: 0,
+            children: [DEAD; 4],
+            population,
+        };
+
+        Store {
+            nodes: vec![cell(0), cell(1)],
+            joined: HashMap::new(),
+            results: HashMap::new(),
+            empty: vec![DEAD],
+            rule,
+        }
+    }
+
+    fn node(&self, id: NodeId) -> Node {
+        self.nodes[id as usize]
+    }
+
+    /// True if the store holds more nodes than are kept.
+    fn is_full(&self) -> bool {
+        self.nodes.len() > MAX_NODES
+    }
+
+    /// Return the node of the four quarters, which are of the same level.
+    fn join(&mut self, children: [NodeId; 4]) -> NodeId {
+        if let Some(id) = self.joined.get(&children) {
+            return *id;
+        }
+        let level = self.node(children[0]).level + 1;
+        let population = children.iter().fold(0_u64, |sum, child| {
+            sum.saturating_add(self.node(*child).population)
+        });
+        let id = self.nodes.len() as NodeId;
+
+        self.nodes.push(Node {
+            level,
+            children,
+            population,
+        });
+        self.joined.insert(children, id);
+        id
+    }
+
+    /// Return the node of dead cells of the given level.
+    fn empty(&mut self, level: u32) -> NodeId {
+        while self.empty.len() <= level as usize {
+            let smaller = *self.empty.last().unwrap();
+            let larger = self.join([smaller; 4]);
+            self.empty.push(larger);
+        }
+        self.empty[level as usize]
+    }
+
+    /// Return the center of a node of level 2 or more: the node of level - 1 in its middle.
+    fn center(&mut self, id: NodeId) -> NodeId {
+        let [nw, ne, sw, se] = self
+            .node(id)
+            .children
+            .map(|child| self.node(child).children);
+        self.join([nw[3], ne[2], sw[1], se[0]])
+    }
+
+    /// Return the center of the node of level k >= 2, advanced 2^step generations, for step <= k - 2.
+    fn result(&mut self, id: NodeId, step: u32) -> NodeId {
+        if let Some(result) = self.results.get(&(id, step)) {
+            return *result;
+        }
+        let node = self.node(id);
+        // Give up on a full store, with a node of the right level so the steps above still fit together.
+        // A single generation needs few enough nodes to always be worked out.
+        if step > 0 && self.is_full() {
+            return self.empty(node.level - 1);
+        }
+        let result = if node.level == 2 {
+            self.advance_level_2(id)
+        } else {
+            self.advance(node, step)
+        };
+
+        self.results.insert((id, step), result);
+        result
+    }
+
+    /// Return the center of the node of level 2 (4 by 4 cells) one generation later, by counting neighbors.
+    fn advance_level_2(&mut self, id: NodeId) -> NodeId {
+        let mut cells = [[false; 4]; 4];
+        for (quarter, child) in self.node(id).children.into_iter().enumerate() {
+            for (cell, grandchild) in self.node(child).children.into_iter().enumerate() {
+                let row = quarter / 2 * 2 + cell / 2;
+                let col = quarter % 2 * 2 + cell % 2;
+                cells[row][col] = grandchild == ALIVE;
+            }
+        }
+
+        let next = [(1, 1), (1, 2), (2, 1), (2, 2)].map(|(row, col): (usize, usize)| {
+            // The living cells of the 3 by 3 square around the cell, less the cell itself.
+            let square = cells[row - 1..=row + 1]
+                .iter()
+                .flat_map(|cells| &cells[col - 1..=col + 1])
+                .filter(|alive| **alive)
+                .count();
+            let neighbors = (square - cells[row][col] as usize) as u8;
+            if self.rule.next_alive(cells[row][col], neighbors) {
+                ALIVE
+            } else {
+                DEAD
+            }
+        });
+        self.join(next)
+    }
+
+    /// Return the center of a node of level k >= 3 advanced 2^step generations, for step <= k - 2.
+    ///
+    /// The center is covered by nine overlapping nodes of level k - 1, which are advanced (or, for
+    /// smaller steps, only centered) to nine nodes of level k - 2.  Those are joined into four nodes of
+    /// level k - 1, whose results are joined into the center.
+    fn advance(&mut self, node: Node, step: u32) -> NodeId {
+        let [a, b, c, d] = node.children;
+        let [_, a_ne, a_sw, a_se] = self.node(a).children;
+        let [b_nw, _, b_sw, b_se] = self.node(b).children;
+        let [c_nw, c_ne, _, c_se] = self.node(c).children;
+        let [d_nw, d_ne, d_sw, _] = self.node(d).children;
+
+        let nine = [
+            a,
+            self.join([a_ne, b_nw, a_se, b_sw]),
+            b,
+            self.join([a_sw, a_se, c_nw, c_ne]),
+            self.join([a_se, b_sw, c_ne, d_nw]),
+            self.join([b_sw, b_se, d_nw, d_ne]),
+            c,
+            self.join([c_ne, d_nw, c_se, d_sw]),
+            d,
+        ];
+        let full_step = step == node.level - 2;
+        let inner_step = if full_step { step - 1 } else { step };
+        let m = nine.map(|id| {
+            if full_step {
+                self.result(id, node.level - 3)
+            } else {
+                self.center(id)
+            }
+        });
+
+        let quarters = [
+            [m[0], m[1], m[3], m[4]],
+            [m[1], m[2], m[4], m[5]],
+            [m[3], m[4], m[6], m[7]],
+            [m[4], m[5], m[7], m[8]],
+        ]
+        .map(|quarter| {
+            let joined = self.join(quarter);
+            self.result(joined, inner_step)
+        });
+        self.join(quarters)
+    }
+
+    /// Call visit with the (row, col) of every living cell of the node, whose top left cell is at (row, col),
+    /// that is in the rows and columns before (height, width).
+    fn living_cells(
+        &self,
+        id: NodeId,
+        (row, col): (u64, u64),
+        (height, width): (u64, u64),
+        visit: &mut impl FnMut(u64, u64),
+    ) {
+        let node = self.node(id);
+        if node.population == 0 || row >= height || col >= width {
+            return;
+        }
+        if node.level == 0 {
+            visit(row, col);
+            return;
+        }
+        let half = 1 << (node.level - 1);
+        for (quarter, child) in node.children.into_iter().enumerate() {
+            let offset = ((quarter / 2) as u64 * half, (quarter % 2) as u64 * half);
+            self.living_cells(
+                child,
+                (row + offset.0, col + offset.1),
+                (height, width),
+                visit,
+            );
+        }
+    }
+}
+
+/// The HashLife engine.
+pub struct HashLifeEngine {
+    store: Store,
+    /// The universe the cells were loaded from, for its size, rule, boundary, and generation.
+    universe: Universe,
+    /// The (row, col) of the living cells.
+    cells: Vec<(u32, u32)>,
+}
+
+impl HashLifeEngine {
+    pub fn new() -> HashLifeEngine {
+        HashLifeEngine {
+            store: Store::new(Rule::default()),
+            universe: Universe::new(),
+            cells: Vec::new(),
+        }
+    }
+
+    /// Return the node of the given level whose top left cell is at (row, col) of the plane tiled with
+    /// copies of the universe.
+    ///
+    /// Nodes at the same place in their copy of the universe are the same, and are built once.
+    fn tiled(
+        &mut self,
+        level: u32,
+        (row, col): (u64, u64),
+        alive: &[bool],
+        built: &mut HashMap<(u32, u64, u64), NodeId>,
+    ) -> NodeId {
+        let (height, width) = (self.universe.height() as u64, self.universe.width() as u64);
+        let (row, col) = (row % height, col % width);
+        if level == 0 {
+            return if alive[(row * width + col) as usize] {
+                ALIVE
+            } else {
+                DEAD
+            };
+        }
+        if let Some(id) = built.get(&(level, row, col)) {
+            return *id;
+        }
+
+        let half = 1 << (level - 1);
+        let children = [(0, 0), (0, half), (half, 0), (half, half)]
+            .map(|offset| self.tiled(level - 1, (row + offset.0, col + offset.1), alive, built));
+        let id = self.store.join(children);
+        built.insert((level, row, col), id);
+        id
+    }
+
+    /// Return the node of the given level with the universe at (row, col), and dead cells everywhere else.
+    fn placed(
+        &mut self,
+        level: u32,
+        (row, col): (u64, u64),
+        origin: u64,
+        alive: &[bool],
+    ) -> NodeId {
+        let (height, width) = (self.universe.height() as u64, self.universe.width() as u64);
+        let side = 1 << level;
+        let outside = row + side <= origin
+            || col + side <= origin
+            || row >= origin + height
+            || col >= origin + width;
+        if outside {
+            return self.store.empty(level);
+        }
+        if level == 0 {
+            let index = (row - origin) * width + (col - origin);
+            return if alive[index as usize] { ALIVE } else { DEAD };
+        }
+
+        let half = 1 << (level - 1);
+        let children = [(0, 0), (0, half), (half, 0), (half, half)]
+            .map(|offset| self.placed(level - 1, (row + offset.0, col + offset.1), origin, alive));
+        self.store.join(children)
+    }
+
+    /// Return the cells advanced 2^step generations, or None if the store filled up before they were worked
+    /// out.
+    fn advanced_by_power(&mut self, step: u32) -> Option<Vec<(u32, u32)>> {
+        let (height, width) = (self.universe.height(), self.universe.width());
+        let mut alive = vec![false; (width * height) as usize];
+        for (row, col) in self.cells.iter() {
+            alive[(*row * width + *col) as usize] = true;
+        }
+
+        // The result is the middle half of the root, which must hold the whole universe, and the root must
+        // be at least two levels above the step.
+        let level = (width.max(height).next_power_of_two().trailing_zeros() + 1)
+            .max(step + 2)
+            .max(3);
+        let origin = 1_u64 << (level - 2);
+        let root = match self.universe.boundary() {
+            Boundary::Wrap => self.tiled(level, (0, 0), &alive, &mut HashMap::new()),
+            Boundary::Dead | Boundary::Infinite => self.placed(level, (0, 0), origin, &alive),
+        };
+        let result = self.store.result(root, step);
+        if step > 0 && self.store.is_full() {
+            return None;
+        }
+
+        let (height, width) = (height as u64, width as u64);
+        let mut cells = Vec::new();
+        self.store
+            .living_cells(result, (0, 0), (height, width), &mut |row, col| {
+                // The result starts at the origin of the plane of copies, or of the placed universe.
+                let cell = match self.universe.boundary() {
+                    Boundary::Wrap => ((origin + row) % height, (origin + col) % width),
+                    Boundary::Dead | Boundary::Infinite => (row, col),
+                };
+                cells.push((cell.0 as u32, cell.1 as u32));
+            });
+        Some(cells)
+    }
+}
+
+impl Default for HashLifeEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UniverseEngine for HashLifeEngine {
+    fn name(&self) -> &'static str {
+        "hashlife"
+    }
+
+    fn load(&mut self, universe: &Universe) {
+        // What is remembered only holds for the rule it was worked out with.
+        if universe.rule() != self.store.rule || self.store.is_full() {
+            self.store = Store::new(universe.rule());
+        }
+        self.cells = universe
+            .get_living_cells()
+            .into_iter()
+            .map(|(x, y)| (y as u32, x as u32))
+            .collect();
+        self.universe = Universe::with_size(universe.width(), universe.height());
+        self.universe.set_rule(universe.rule());
+        self.universe.set_boundary(universe.boundary());
+        self.universe.set_generation(universe.generation());
+    }
+
+    fn tick(&mut self) {
+        self.advance(1);
+    }
+
+    fn advance(&mut self, generations: u64) {
+        if self.universe.width() == 0 || self.universe.height() == 0 {
+            return;
+        }
+        let mut remaining = generations;
+
+        while remaining > 0 {
+            if self.store.is_full() {
+                self.store = Store::new(self.universe.rule());
+            }
+            let mut step = match self.universe.boundary() {
+                Boundary::Wrap => remaining.ilog2().min(MAX_STEP_LEVEL),
+                Boundary::Dead | Boundary::Infinite => 0,
+            };
+            // A step that fills the store is taken again from an empty store as a smaller step, which needs
+            // fewer nodes, down to a single generation, which always fits.
+            self.cells = loop {
+                match self.advanced_by_power(step) {
+                    Some(cells) => break cells,
+                    None => {
+                        self.store = Store::new(self.universe.rule());
+                        step -= 1;
+                    }
+                }
+            };
+            remaining -= 1 << step;
+        }
+        self.universe
+            .set_generation(self.universe.generation() + generations);
+    }
+
+    fn to_universe(&self) -> Universe {
+        let mut universe = self.universe.clone();
+
+        universe.set_cells(&self.cells);
+        universe
+    }
+}
@@ -0,0 +1,42 @@
+//! The platform's sources of random numbers for randomizing the universe.
+
+pub use game_of_life_core::random::{symmetric_soup, RandomSource, SeededRandom};
+
+/// Random numbers from the browser's Math.random().
+#[cfg(feature = "web")]
+pub struct WebRandom;
+
+#[cfg(feature = "web")]
+impl RandomSource for WebRandom {
+    fn next_u64(&mut self) -> u64 {
+        use web_sys::js_sys::Math;
+
+        // Math.random() has only 53 bits of precision, so build the number from two 32 bit halves.
+        let half = || (Math::random() * (1_u64 << 32) as f64) as u64;
+        (half() << 32) | half()
+    }
+}
+
+/// Random numbers from the rand crate's thread local generator.
+#[cfg(feature = "desktop")]
+pub struct DesktopRandom;
+
+#[cfg(feature = "desktop")]
+impl RandomSource for DesktopRandom {
+    fn next_u64(&mut self) -> u64 {
+        rand::random()
+    }
+}
+
+/// The random source of the platform being built for.
+#[cfg(feature = "web")]
+pub use WebRandom as PlatformRandom;
+
+/// The random source of the platform being built for.
+#[cfg(feature = "desktop")]
+pub use DesktopRandom as PlatformRandom;
+
+/// Return a new seed from the platform's random number generator.
+pub fn random_seed() -> u64 {
+    PlatformRandom.next_u64()
+}
@@ -0,0 +1,140 @@
+//! An engine that only keeps the living cells, for large universes that are mostly empty.
+//!
+//! Each tick visits the living cells and the cells around them, instead of every cell of the universe, so
+//! its speed depends on the population rather than the size of the universe.
+
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    engine::UniverseEngine,
+    rule::Rule,
+    universe::{Boundary, Universe},
+};
+
+/// The sparse engine.
+pub struct SparseEngine {
+    /// The (row, col) of every living cell.
+    alive: HashSet<(i64, i64)>,
+    /// The universe the cells were loaded from, for its size, rule, boundary, and generation.
+    universe: Universe,
+}
+
+impl SparseEngine {
+    pub fn new() -> SparseEngine {
+        SparseEngine {
+            alive: HashSet::new(),
+            universe: Universe::new(),
+        }
+    }
+
+    /// Return the (row, col) of the cell at the given offset from the cell at (row, col), or None if it is
+    /// past the edges of a universe with dead edges.
+    fn offset(
+        &self,
+        (row, col): (i64, i64),
+        (delta_row, delta_col): (i64, i64),
+    ) -> Option<(i64, i64)> {
+        let (height, width) = (self.universe.height() as i64, self.universe.width() as i64);
+        let (offset_row, offset_col) = (row + delta_row, col + delta_col);
+
+        match self.universe.boundary() {
+            Boundary::Wrap => Some((offset_row.rem_euclid(height), offset_col.rem_euclid(width))),
+            Boundary::Dead | Boundary::Infinite => {
+                let inside = (0..height).contains(&offset_row) && (0..width).contains(&offset_col);
+                inside.then_some((offset_row, offset_col))
+            }
+        }
+    }
+
+    /// Return the number of living cells around every cell with at least one living neighbor.
+    fn neighbor_counts(&self) -> HashMap<(i64, i64), u8> {
+        let mut counts = HashMap::with_capacity(self.alive.len() * 8);
+
+        for cell in self.alive.iter() {
+            for delta in NEIGHBOR_OFFSETS {
+                if let Some(neighbor) = self.offset(*cell, delta) {
+                    *counts.entry(neighbor).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+}
+
+impl Default for SparseEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The (row, col) offsets of the eight cells around a cell.
+const NEIGHBOR_OFFSETS: [(i64, i64); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// True if the rule brings dead cells with no living neighbors to life, so that every cell must be visited.
+fn births_from_nothing(rule: &Rule) -> bool {
+    rule.next_alive(false, 0)
+}
+
+impl UniverseEngine for SparseEngine {
+    fn name(&self) -> &'static str {
+        "sparse"
+    }
+
+    fn load(&mut self, universe: &Universe) {
+        self.alive = universe
+            .get_living_cells()
+            .into_iter()
+            .map(|(x, y)| (y, x))
+            .collect();
+        self.universe = Universe::with_size(universe.width(), universe.height());
+        self.universe.set_rule(universe.rule());
+        self.universe.set_boundary(universe.boundary());
+        self.universe.set_generation(universe.generation());
+    }
+
+    fn tick(&mut self) {
+        let rule = self.universe.rule();
+        let mut counts = self.neighbor_counts();
+
+        if births_from_nothing(&rule) {
+            let (height, width) = (self.universe.height() as i64, self.universe.width() as i64);
+            for row in 0..height {
+                for col in 0..width {
+                    counts.entry((row, col)).or_insert(0);
+                }
+            }
+        }
+        // Living cells with no living neighbors are not counted, but may survive.
+        for cell in self.alive.iter() {
+            counts.entry(*cell).or_insert(0);
+        }
+
+        self.alive = counts
+            .into_iter()
+            .filter(|(cell, count)| rule.next_alive(self.alive.contains(cell), *count))
+            .map(|(cell, _)| cell)
+            .collect();
+        self.universe.set_generation(self.universe.generation() + 1);
+    }
+
+    fn to_universe(&self) -> Universe {
+        let mut universe = self.universe.clone();
+        let cells: Vec<(u32, u32)> = self
+            .alive
+            .iter()
+            .map(|(row, col)| (*row as u32, *col as u32))
+            .collect();
+
+        universe.set_cells(&cells);
+        universe
+    }
+}
@@ -0,0 +1,134 @@
+//! Gamepad input on desktop, for running the app at kiosks and museum installations.
+//!
+//! The left stick scrolls the window, the d-pad moves a cell cursor, A toggles the cell under the cursor,
+//! Start runs or pauses, and the shoulder buttons change the speed.
+
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use gilrs::{Axis, Button, Event, EventType, Gilrs};
+
+use crate::{
+    attract::{note_input, LastInput},
+    command::{use_controls, Command, Controls},
+    game_of_life::{universe::Universe, CellLayer, TicksPerFrame},
+};
+
+/// How often the gamepads are read.
+const POLL_INTERVAL: Duration = Duration::from_millis(16);
+/// Stick positions closer to the center than this are ignored, since sticks rarely rest exactly centered.
+const STICK_DEAD_ZONE: f32 = 0.2;
+/// Pixels the window scrolls each poll with the stick pushed all the way.
+const SCROLL_SPEED: f32 = 20.0;
+const CURSOR_COLOR: &str = "#ff9800";
+
+/// The (row, column) of the cell under the gamepad cursor, once a gamepad has been used.
+#[derive(Default)]
+pub struct GamepadCursor(pub Option<(u32, u32)>);
+
+/// Read the connected gamepads for as long as the app runs.
+///
+/// Must be called after the Controls, GamepadCursor and LastInput are provided.
+pub fn use_gamepad(cx: &ScopeState) {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let ticks_per_frame = use_shared_state::<TicksPerFrame>(cx).unwrap();
+    let cursor = use_shared_state::<GamepadCursor>(cx).unwrap();
+    let last_input = use_shared_state::<LastInput>(cx).unwrap();
+    let create_eval = use_eval(cx);
+
+    use_future(cx, (), |_| {
+        to_owned![
+            controls,
+            universe,
+            ticks_per_frame,
+            cursor,
+            last_input,
+            create_eval
+        ];
+        async move {
+            let mut gilrs = match Gilrs::new() {
+                Ok(gilrs) => gilrs,
+                Err(error) => {
+                    println!("Gamepads are not available: {error}");
+                    return;
+                }
+            };
+
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+
+                while let Some(Event { event, .. }) = gilrs.next_event() {
+                    if let EventType::ButtonPressed(button, _) = event {
+                        note_input(&last_input);
+                        let TicksPerFrame(ticks) = *ticks_per_frame.read();
+                        let size = {
+                            let universe = universe.read();
+                            (universe.height(), universe.width())
+                        };
+                        press(button, &controls, &mut cursor.write().0, size, ticks);
+                    }
+                }
+
+                for (_, gamepad) in gilrs.gamepads() {
+                    let x = gamepad.value(Axis::LeftStickX);
+                    let y = gamepad.value(Axis::LeftStickY);
+
+                    if x.abs() > STICK_DEAD_ZONE || y.abs() > STICK_DEAD_ZONE {
+                        note_input(&last_input);
+                        // The stick's y axis points up, but the window's points down.
+                        let (dx, dy) = (x * SCROLL_SPEED, -y * SCROLL_SPEED);
+                        let _ = create_eval(&format!("window.scrollBy({dx}, {dy});"));
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Act on a pressed button.  The cursor starts in the middle of a universe of the given (rows, columns).
+fn press(
+    button: Button,
+    controls: &Controls,
+    cursor: &mut Option<(u32, u32)>,
+    (rows, cols): (u32, u32),
+    ticks: u32,
+) {
+    let (row, col) = cursor.unwrap_or((rows / 2, cols / 2));
+
+    match button {
+        Button::DPadUp => *cursor = Some(((row + rows - 1) % rows, col)),
+        Button::DPadDown => *cursor = Some(((row + 1) % rows, col)),
+        Button::DPadLeft => *cursor = Some((row, (col + cols - 1) % cols)),
+        Button::DPadRight => *cursor = Some((row, (col + 1) % cols)),
+        Button::South => {
+            // The first press only shows where the cursor is.
+            if cursor.is_some() {
+                controls.dispatch(Command::Toggle { row, col });
+            }
+            *cursor = Some((row, col));
+        }
+        Button::Start if controls.is_running() => controls.dispatch(Command::Stop),
+        Button::Start => controls.dispatch(Command::Start),
+        Button::LeftTrigger => {
+            controls.dispatch(Command::SetTicksPerFrame(ticks.saturating_sub(1)))
+        }
+        Button::RightTrigger => {
+            controls.dispatch(Command::SetTicksPerFrame(ticks.saturating_add(1)))
+        }
+        _ => {}
+    }
+}
+
+/// Outline the cell under the gamepad cursor.
+#[component]
+pub fn GamepadCursorOutline(cx: Scope) -> Element {
+    let cursor = use_shared_state::<GamepadCursor>(cx).unwrap();
+    let (row, col) = cursor.read().0?;
+
+    render! {
+        CellLayer {
+            rect { x: col as i64, y: row as i64, width: 1, height: 1, fill: "none", stroke: CURSOR_COLOR, stroke_width: 0.25 }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Exporting the universe as files Golly opens directly, so the app can be used to sketch patterns for Golly.
+//!
+//! Both formats record the rule, the size and boundary of the universe, and the generation, so Golly runs the
+//! pattern just as the app does.  Only life-like rules on square cells can be exported.  On desktop, files can be saved to a chosen folder, such as
+//! Golly's own pattern folder.
+//!
+//! The living cells can also be shown as RLE text cut down to the pattern, to paste into Golly or post
+//! online, or copied to the clipboard.
+
+use dioxus::prelude::*;
+
+use crate::{
+    game_of_life::{macrocell::write_macrocell, rle::write_rle, universe::Universe},
+    storage,
+};
+
+const FOLDER_KEY: &str = "golly_folder.txt";
+const OPEN_HINT: &str = "Open it in Golly with File > Open Pattern, or drop it on Golly's window.";
+
+/// A file format Golly reads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Rle,
+    Macrocell,
+}
+
+impl Format {
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Rle => "rle",
+            Format::Macrocell => "mc",
+        }
+    }
+
+    fn write(&self, universe: &Universe, name: &str) -> Result<String, String> {
+        match self {
+            Format::Rle => write_rle(universe, name),
+            Format::Macrocell => write_macrocell(universe),
+        }
+    }
+}
+
+/// Save the universe in the format, returning a message saying where it went.
+#[cfg(feature = "web")]
+fn export(universe: &Universe, format: Format, _folder: &str) -> String {
+    let name = format!("game_of_life_gen{}", universe.generation());
+    let file_name = format!("{name}.{}", format.extension());
+
+    match format.write(universe, &name) {
+        Ok(text) => {
+            crate::file_io::save_text_file(&file_name, &text);
+            format!("Downloaded {file_name}.  {OPEN_HINT}")
+        }
+        Err(error) => format!("Could not export: {error}"),
+    }
+}
+
+/// Save the universe in the format to the folder (or the working directory, if no folder is given),
+/// returning a message saying where it went.
+#[cfg(feature = "desktop")]
+fn export(universe: &Universe, format: Format, folder: &str) -> String {
+    let name = format!("game_of_life_gen{}", universe.generation());
+    let path = std::path::Path::new(folder.trim()).join(format!("{name}.{}", format.extension()));
+
+    let text = match format.write(universe, &name) {
+        Ok(text) => text,
+        Err(error) => return format!("Could not export: {error}"),
+    };
+    match std::fs::write(&path, text) {
+        Ok(()) => {
+            let path = std::fs::canonicalize(&path).unwrap_or(path);
+            format!("Saved {}.  {OPEN_HINT}", path.display())
+        }
+        Err(error) => format!("Could not save {}: {error}", path.display()),
+    }
+}
+
+/// Buttons to export the universe for Golly, and on desktop, the folder to save to.
+#[component]
+pub fn GollyExport(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let folder = use_state(cx, || storage::load(FOLDER_KEY).unwrap_or_default());
+    let status = use_state(cx, || None::<String>);
+    let show_rle = use_state(cx, || false);
+    // The living cells as RLE, kept up to date with the universe while shown.
+    let rle = show_rle.then(|| universe.read().to_rle());
+
+    #[cfg(feature = "desktop")]
+    let folder_input = render! {
+        label {
+            "Save to folder "
+            input {
+                r#type: "text",
+                placeholder: "working directory",
+                value: "{folder}",
+                oninput: move |event| {
+                    storage::store(FOLDER_KEY, &event.value);
+                    folder.set(event.value.clone());
+                },
+            }
+        }
+    };
+    #[cfg(feature = "web")]
+    let folder_input = None::<VNode>;
+
+    let copy_button = render! {
+        button {
+            onclick: move |_| crate::clipboard::set_text(universe.read().to_rle()),
+            "Copy"
+        }
+    };
+
+    let export_button = |format: Format, label: &'static str| {
+        rsx! {
+            button {
+                onclick: move |_| status.set(Some(export(&universe.read(), format, folder.get()))),
+                "{label}"
+            }
+        }
+    };
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            span { "Export for Golly:" }
+            export_button(Format::Rle, "RLE"),
+            export_button(Format::Macrocell, "Macrocell"),
+            folder_input
+            button {
+                onclick: move |_| show_rle.set(!show_rle.get()),
+                if **show_rle { "Hide RLE" } else { "Show RLE" }
+            }
+        }
+        status.get().as_ref().map(|status| rsx! {
+            div { display: "flex", justify_content: "center", "{status}" }
+        })
+        rle.map(|text| rsx! {
+            div { display: "flex", justify_content: "center", align_items: "start", gap: "8px",
+                textarea {
+                    readonly: true,
+                    rows: 8,
+                    cols: 72,
+                    font_family: "monospace",
+                    value: "{text}",
+                }
+                copy_button
+            }
+        })
+    }
+}
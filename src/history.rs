@@ -0,0 +1,179 @@
+//! The universe as it was before each of its recent changes, so it can be stepped back after an interesting
+//! pattern flashes by.
+//!
+//! The universe is saved before every change, whether it advanced or was edited, so stepping back restores
+//! exactly what was on the grid.  History is kept within the memory budget, and to the length chosen in the
+//! settings.  Once the history is that long, each universe saved is copied into the oldest one, so running
+//! doesn't allocate a universe each frame.
+//!
+//! While paused, the timeline scrubs through the history without losing it, until the universe is changed
+//! from the universe scrubbed to.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::use_controls,
+    game_of_life::universe::Universe,
+    memory::{BudgetedBuffer, MemoryBudget},
+    storage,
+};
+
+const HISTORY_KEY: &str = "history_length.txt";
+
+/// The numbers of universes that can be kept.  0 keeps no history.
+pub const HISTORY_LENGTHS: [usize; 4] = [0, 100, 1_000, 10_000];
+const DEFAULT_HISTORY_LENGTH: usize = 1_000;
+
+/// The universes before the latest changes, from oldest to newest.
+pub struct History {
+    /// The most universes kept.
+    length: usize,
+    universes: BudgetedBuffer<Universe>,
+    /// While scrubbing, where in the history the universe shown is from.
+    scrub: Option<Scrub>,
+}
+
+/// The universe shown from the history while scrubbing.
+struct Scrub {
+    /// The index of the universe shown in the history.
+    position: usize,
+    /// The universe when scrubbing started, which is after all universes in the history.
+    present: Universe,
+}
+
+impl History {
+    /// Return an empty history of the length chosen in an earlier session, or the default length.
+    pub fn stored() -> History {
+        let length = storage::load(HISTORY_KEY)
+            .and_then(|length| length.trim().parse().ok())
+            .unwrap_or(DEFAULT_HISTORY_LENGTH);
+
+        History {
+            length,
+            universes: BudgetedBuffer::new("history"),
+            scrub: None,
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Set the most universes kept, dropping the oldest ones over it, and remember it for later sessions.
+    pub fn set_length(&mut self, length: usize, budget: &mut MemoryBudget) {
+        self.settle(budget);
+        self.length = length;
+        self.universes.keep_newest(length, budget);
+        storage::store(HISTORY_KEY, &length.to_string());
+    }
+
+    /// Add the universe as it is before a change.
+    ///
+    /// If the universe was scrubbed to, the universes after it are dropped, since they are no longer what
+    /// follows it.
+    pub fn push(&mut self, universe: &Universe, budget: &mut MemoryBudget) {
+        self.settle(budget);
+        if self.length == 0 {
+            return;
+        }
+        self.universes.push_copy(universe, self.length, budget);
+        self.universes.keep_newest(self.length, budget);
+    }
+
+    /// Remove and return the universe before the latest change.
+    pub fn pop(&mut self, budget: &mut MemoryBudget) -> Option<Universe> {
+        self.settle(budget);
+        self.universes.pop_back(budget)
+    }
+
+    /// Return the number of universes kept.
+    pub fn len(&self) -> usize {
+        self.universes.len()
+    }
+
+    /// Return where in the history the universe shown is from, or None if it is the present universe.
+    pub fn position(&self) -> Option<usize> {
+        self.scrub.as_ref().map(|scrub| scrub.position)
+    }
+
+    /// Return the universe to show at the position in the history, given the universe shown now, or None
+    /// if the universe shown stays the same.
+    ///
+    /// Positions from the length of the history on are the present universe, from before scrubbing started.
+    pub fn scrub_to(&mut self, position: usize, shown: &Universe) -> Option<Universe> {
+        if position >= self.len() {
+            return self.scrub.take().map(|scrub| scrub.present);
+        }
+        let universe = self.universes.get(position)?.clone();
+
+        match &mut self.scrub {
+            Some(scrub) => scrub.position = position,
+            None => {
+                self.scrub = Some(Scrub {
+                    position,
+                    present: shown.clone(),
+                })
+            }
+        }
+        Some(universe)
+    }
+
+    /// Stop scrubbing, keeping the universe scrubbed to: drop it and the universes after it from the history,
+    /// since it is shown now.
+    fn settle(&mut self, budget: &mut MemoryBudget) {
+        if let Some(scrub) = self.scrub.take() {
+            self.universes.truncate(scrub.position, budget);
+        }
+    }
+}
+
+/// Restore the universe as it was before its last change.
+#[component]
+pub fn StepBackButton(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    // Read the universe so the button is enabled as soon as there is history.
+    let _universe = use_shared_state::<Universe>(cx).unwrap();
+
+    render! {
+        button {
+            disabled: controls.history_len() == 0,
+            onclick: move |_| {
+                controls.step_back();
+            },
+            "Step back"
+        }
+    }
+}
+
+/// A slider to scrub backwards and forwards through the history while paused.
+///
+/// The right end of the slider is the universe from before scrubbing started.
+#[component]
+pub fn Timeline(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let generation = use_shared_state::<Universe>(cx)
+        .unwrap()
+        .read()
+        .generation();
+
+    let end = controls.history_len();
+    let position = controls.history_position().unwrap_or(end);
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            input {
+                r#type: "range",
+                min: 0,
+                max: end as i64,
+                value: position as i64,
+                disabled: end == 0 || controls.is_running(),
+                oninput: move |event| {
+                    if let Ok(position) = event.value.parse() {
+                        controls.scrub_to(position);
+                    }
+                },
+            }
+            span { font_family: "monospace", "generation {generation} ({position}/{end})" }
+        }
+    }
+}
@@ -0,0 +1,320 @@
+//! Layers: patterns composed on separate named layers, and merged into the universe when ready.
+//!
+//! Layers aren't simulated, so a setup such as a glider synthesis can be built up a piece at a time, each
+//! piece on its own layer.  Each layer can be shown over the grid, locked against changes, and included in
+//! or left out of the merge, which sets the living cells of every included layer alive in the universe.
+//! Patterns can be rotated and flipped before they are placed, so they head the right way.
+
+use std::collections::BTreeSet;
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    custom_patterns::CustomPatterns,
+    game_of_life::{
+        patterns::{Pattern, LIBRARY},
+        transform::Transform,
+        universe::Universe,
+        CellLayer,
+    },
+    palette::Palette,
+    selection::{Selection, TRANSFORM_LABELS},
+};
+
+const LAYER_OPACITY: f64 = 0.5;
+
+/// A named set of cells, kept apart from the universe until merged into it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Layer {
+    pub name: String,
+    /// The (row, column) of the living cells of the layer.
+    pub cells: BTreeSet<(u32, u32)>,
+    /// Drawn over the grid.
+    pub visible: bool,
+    /// Cells can't be placed on or cleared from the layer.
+    pub locked: bool,
+    /// Included when layers are merged into the universe.
+    pub merged: bool,
+}
+
+impl Layer {
+    fn new(name: String) -> Layer {
+        Layer {
+            name,
+            cells: BTreeSet::new(),
+            visible: true,
+            locked: false,
+            merged: true,
+        }
+    }
+}
+
+/// The layers, and the one cells are placed on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Layers {
+    pub layers: Vec<Layer>,
+    pub active: Option<usize>,
+}
+
+impl Layers {
+    /// Add an empty layer with the given name, and make it the active layer.
+    pub fn add(&mut self, name: String) {
+        self.layers.push(Layer::new(name));
+        self.active = Some(self.layers.len() - 1);
+    }
+
+    /// Remove the layer at idx, unless it is locked.
+    pub fn remove(&mut self, idx: usize) {
+        if self.layers[idx].locked {
+            return;
+        }
+        self.layers.remove(idx);
+        self.active = match self.active {
+            Some(active) if active == idx => None,
+            Some(active) if active > idx => Some(active - 1),
+            active => active,
+        };
+    }
+
+    /// Return the active layer, unless there is none or it is locked.
+    pub fn editable(&mut self) -> Option<&mut Layer> {
+        let layer = self.layers.get_mut(self.active?)?;
+        (!layer.locked).then_some(layer)
+    }
+
+    /// Return the (row, column) of every living cell of the layers included in the merge.
+    pub fn merged_cells(&self) -> BTreeSet<(u32, u32)> {
+        self.layers
+            .iter()
+            .filter(|layer| layer.merged)
+            .flat_map(|layer| layer.cells.iter().cloned())
+            .collect()
+    }
+}
+
+/// Return the toggles that set the given cells alive in the universe, leaving the other cells as they are.
+pub fn merge_toggles(universe: &Universe, cells: &BTreeSet<(u32, u32)>) -> Vec<Command> {
+    cells
+        .iter()
+        .filter(|(row, col)| !universe.is_alive(*row, *col))
+        .map(|(row, col)| Command::Toggle {
+            row: *row,
+            col: *col,
+        })
+        .collect()
+}
+
+/// Return the cells of the pattern, rotated and flipped by the transforms in turn, with its top left corner
+/// at the (row, column) cell, leaving out any cells beyond the edges of the universe.
+fn placed_cells(
+    pattern: &Pattern,
+    transforms: &[Transform],
+    (row, col): (u32, u32),
+    universe: &Universe,
+) -> Vec<(u32, u32)> {
+    transforms
+        .iter()
+        .fold(pattern.cells(), |cells, transform| transform.cells(&cells))
+        .into_iter()
+        .map(|(cell_row, cell_col)| (cell_row + row, cell_col + col))
+        .filter(|(row, col)| *row < universe.height() && *col < universe.width())
+        .collect()
+}
+
+/// Controls to add and remove layers, place patterns on them, and merge them into the universe.
+#[component]
+pub fn LayersPanel(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let selection = use_shared_state::<Selection>(cx).unwrap();
+    let layers = use_shared_state::<Layers>(cx).unwrap();
+    let new_name = use_state(cx, String::new);
+    let pattern = use_state(cx, || LIBRARY[0]);
+    // The rotations and flips of the pattern, applied in turn when it is placed.
+    let transforms = use_state(cx, Vec::<Transform>::new);
+    // The built-in patterns, and those saved from the pattern editor.
+    let library = use_shared_state::<CustomPatterns>(cx)
+        .unwrap()
+        .read()
+        .library();
+
+    let current = layers.read().clone();
+    let editable = current
+        .active
+        .is_some_and(|active| !current.layers[active].locked);
+    let selected = selection.read().0;
+
+    let rows = current.layers.iter().enumerate().map(|(idx, layer)| {
+        let count = layer.cells.len();
+        let locked = layer.locked;
+
+        rsx! {
+            div { key: "{idx}", display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+                label {
+                    input {
+                        r#type: "radio",
+                        name: "active-layer",
+                        checked: current.active == Some(idx),
+                        onchange: move |_| layers.write().active = Some(idx),
+                    }
+                    "{layer.name} ({count} cells)"
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: layer.visible,
+                        onchange: move |event| layers.write().layers[idx].visible = event.value == "true",
+                    }
+                    "Visible"
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: layer.locked,
+                        onchange: move |event| layers.write().layers[idx].locked = event.value == "true",
+                    }
+                    "Locked"
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: layer.merged,
+                        onchange: move |event| layers.write().layers[idx].merged = event.value == "true",
+                    }
+                    "Merge"
+                }
+                button { disabled: locked, onclick: move |_| layers.write().remove(idx), "Delete" }
+            }
+        }
+    });
+
+    let transform_buttons = TRANSFORM_LABELS.into_iter().map(|(transform, label)| {
+        rsx! {
+            button {
+                onclick: move |_| transforms.make_mut().push(transform),
+                "{label}"
+            }
+        }
+    });
+
+    let patterns = library.iter().map(|choice| {
+        rsx! {
+            option { key: "{choice.name}", value: "{choice.name}", selected: choice.name == pattern.name, "{choice.name}" }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            span { "Layers:" }
+            input {
+                r#type: "text",
+                placeholder: "Layer {current.layers.len() + 1}",
+                value: "{new_name}",
+                oninput: move |event| new_name.set(event.value.clone()),
+            }
+            button {
+                onclick: move |_| {
+                    let mut layers = layers.write();
+                    let name = match new_name.trim() {
+                        "" => format!("Layer {}", layers.layers.len() + 1),
+                        name => name.to_string(),
+                    };
+                    layers.add(name);
+                    new_name.set(String::new());
+                },
+                "New layer"
+            }
+            button {
+                disabled: current.layers.is_empty(),
+                onclick: move |_| {
+                    let toggles = merge_toggles(&universe.read(), &layers.read().merged_cells());
+                    controls.dispatch_all(toggles);
+                },
+                "Merge into universe"
+            }
+        }
+        rows
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            select {
+                onchange: move |event| {
+                    if let Some(choice) = library.iter().find(|choice| choice.name == event.value) {
+                        pattern.set(*choice);
+                        transforms.set(Vec::new());
+                    }
+                },
+                patterns
+            }
+            transform_buttons
+            button {
+                disabled: transforms.is_empty(),
+                onclick: move |_| transforms.set(Vec::new()),
+                "Original orientation"
+            }
+            button {
+                disabled: !editable || selected.is_none(),
+                onclick: move |_| {
+                    let Some(bounds) = selected else {
+                        return;
+                    };
+                    let cells = placed_cells(
+                        pattern.get(),
+                        transforms.get(),
+                        (bounds.top, bounds.left),
+                        &universe.read(),
+                    );
+                    if let Some(layer) = layers.write().editable() {
+                        layer.cells.extend(cells);
+                    }
+                },
+                "Place at selection"
+            }
+            button {
+                disabled: !editable || selected.is_none(),
+                onclick: move |_| {
+                    let cells = selection.read().living_cells(&universe.read());
+                    if let Some(layer) = layers.write().editable() {
+                        layer.cells.extend(cells);
+                    }
+                },
+                "Copy selected cells"
+            }
+            button {
+                disabled: !editable,
+                onclick: move |_| {
+                    if let Some(layer) = layers.write().editable() {
+                        layer.cells.clear();
+                    }
+                },
+                "Clear layer"
+            }
+        }
+    }
+}
+
+/// Draw the cells of the visible layers over the grid, each layer in its own color.
+#[component]
+pub fn LayersOverlay(cx: Scope) -> Element {
+    let layers = use_shared_state::<Layers>(cx).unwrap();
+    let palette = *use_shared_state::<Palette>(cx).unwrap().read();
+
+    let layers = layers.read();
+    let cells = layers
+        .layers
+        .iter()
+        .enumerate()
+        .filter(|(_, layer)| layer.visible)
+        .flat_map(|(idx, layer)| {
+            // The first state color is usually the color of living cells, so it is left for them.
+            let color = palette.states[1 + idx % (palette.states.len() - 1)];
+            layer.cells.iter().map(move |(row, col)| {
+                rsx! { rect { x: *col as i64, y: *row as i64, width: 1, height: 1, fill: color, fill_opacity: LAYER_OPACITY } }
+            })
+        });
+
+    render! {
+        CellLayer {
+            cells
+        }
+    }
+}
@@ -2,21 +2,125 @@
 //!
 //! Adapted from the rust wasm tutorial: https://rustwasm.github.io/docs/book/game-of-life/introduction.html
 
-#[cfg(feature = "web")]
 #[macro_use]
-pub(crate) mod websys_utils;
+pub(crate) mod platform;
+
+#[cfg(feature = "web")]
+pub(crate) mod embed;
+
+#[cfg(feature = "desktop")]
+pub(crate) mod autosave;
+#[cfg(feature = "desktop")]
+pub(crate) mod fullscreen;
+#[cfg(feature = "desktop")]
+pub(crate) mod gamepad;
+#[cfg(feature = "desktop")]
+pub(crate) mod pattern_watch;
+#[cfg(feature = "desktop")]
+pub(crate) mod window_fit;
+#[cfg(feature = "desktop")]
+pub(crate) mod window_geometry;
+#[cfg(feature = "desktop")]
+pub(crate) mod window_title;
 
+pub(crate) mod analysis;
 pub(crate) mod animation;
+pub(crate) mod annotations;
+pub(crate) mod attract;
+pub(crate) mod auto_stop;
+pub(crate) mod benchmark;
+pub(crate) mod brush;
+pub(crate) mod census;
+pub(crate) mod clipboard;
+pub(crate) mod command;
+pub(crate) mod custom_patterns;
+pub(crate) mod daily;
+pub(crate) mod diff;
+pub(crate) mod educator;
+pub(crate) mod file_io;
 pub(crate) mod frames_per_second;
 pub(crate) mod game_of_life;
+pub(crate) mod golly;
+pub(crate) mod history;
+pub(crate) mod layers;
+pub(crate) mod memory;
+pub(crate) mod mutation;
+pub(crate) mod palette;
+pub(crate) mod pattern_files;
+pub(crate) mod performance;
+pub(crate) mod period;
+pub(crate) mod placement;
+pub(crate) mod predecessor;
+pub(crate) mod puzzle;
+pub(crate) mod replay;
+pub(crate) mod rule_compare;
+pub(crate) mod rule_picker;
+pub(crate) mod ruler;
+pub(crate) mod screenshot;
+pub(crate) mod seed;
+pub(crate) mod selection;
+pub(crate) mod settings;
+pub(crate) mod snapshot;
+pub(crate) mod sonification;
+pub(crate) mod soup;
+pub(crate) mod soup_search;
+pub(crate) mod speed;
+pub(crate) mod state_palette;
+pub(crate) mod storage;
+pub(crate) mod theme;
+pub(crate) mod time_series;
+pub(crate) mod tour;
+pub(crate) mod url_import;
+pub(crate) mod viewport;
 
 use dioxus::{html::GlobalAttributes, prelude::*};
 
 use crate::{
+    analysis::SelectionAnalysis,
     animation::use_animation_frame,
+    annotations::{AnnotationPanel, Annotations},
+    attract::{note_input, AttractFade, AttractMode, LastInput, FADE_MS},
+    auto_stop::AutoStop,
+    benchmark::{EngineComparison, UniverseBenchmarkButton},
+    brush::{Brush, BrushPicker},
+    census::CensusPanel,
+    clipboard::ClipboardButtons,
+    command::{use_controls, use_controls_provider, Command},
+    custom_patterns::{CustomPatterns, PatternEditor},
+    daily::PatternOfTheDay,
+    diff::{DiffBase, DiffControls},
+    educator::{EducatorControls, Walkthrough},
     frames_per_second::FramesPerSecond,
-    game_of_life::universe::Universe,
-    game_of_life::{GameOfLife, Redraw},
+    game_of_life::universe::{Stats, Universe},
+    game_of_life::{GameOfLife, HoveredCell, Redraw, TicksPerFrame},
+    golly::GollyExport,
+    history::{StepBackButton, Timeline},
+    layers::{Layers, LayersPanel},
+    memory::MemoryBudget,
+    mutation::MutationExplorer,
+    pattern_files::PatternFiles,
+    performance::{PerformanceMode, PerformanceToggle},
+    period::PeriodStatus,
+    placement::{Placement, PlacementPicker},
+    predecessor::PredecessorSearch,
+    puzzle::PuzzlePanel,
+    replay::{ReplayControls, Session},
+    rule_compare::RuleComparison,
+    rule_picker::RulePicker,
+    screenshot::ScreenshotTriggers,
+    seed::{Density, SeedInput},
+    selection::{CopiedRegion, MoveDrag, Selection, SelectionStats, Tool, ToolPicker},
+    settings::{stored_palette, SettingsPanel},
+    snapshot::SaveSlots,
+    sonification::Sonification,
+    soup::SoupCensus,
+    soup_search::SoupSearchPanel,
+    speed::{SpeedControls, StepsPerSecond},
+    state_palette::{PaintState, StatePalette},
+    theme::Appearance,
+    tour::TourControls,
+    url_import::UrlImport,
+    viewport::{Viewport, ZoomControls},
 };
 
 fn main() {
@@ -31,18 +135,25 @@ fn main() {
 #[cfg(feature = "desktop")]
 fn launch_desktop() {
     use dioxus_desktop::{tao::dpi::LogicalPosition, Config, PhysicalSize, WindowBuilder};
-    use game_of_life::{CELL_SIZE, GRID_HEIGHT, GRID_WIDTH};
-
-    // TODO: Now that the grid is an SVG, scale the grid when the window changes
-    let size = PhysicalSize::new(
-        GRID_WIDTH * 2.0 + (CELL_SIZE * 2) as f64 * 2.0,
-        GRID_HEIGHT * 2.0 + 400.0,
-    );
-    let position = LogicalPosition::new(10, 10);
-    let window = WindowBuilder::new()
-        .with_title("Game of Life")
-        .with_inner_size(size)
-        .with_position(position);
+    use game_of_life::{cells_size, universe::Universe, BIG_GRID_STROKE, CELL_SIZE};
+    use window_geometry::WindowGeometry;
+
+    let window = WindowBuilder::new().with_title(window_title::TITLE);
+    // The window opens as it was when the last session ended.  The first time, it fits the grid of a new
+    // universe.  After that, the grid is fitted to the window.
+    let window = match WindowGeometry::stored() {
+        Some(geometry) => geometry.apply(window),
+        None => {
+            let (cells_width, cells_height) = cells_size(&Universe::new());
+            let size = PhysicalSize::new(
+                (cells_width + BIG_GRID_STROKE) * 2.0 + (CELL_SIZE * 2) as f64 * 2.0,
+                (cells_height + BIG_GRID_STROKE) * 2.0 + 400.0,
+            );
+            window
+                .with_inner_size(size)
+                .with_position(LogicalPosition::new(10, 10))
+        }
+    };
 
     dioxus_desktop::launch_with_props(App, (), Config::new().with_window(window));
 }
@@ -50,38 +161,191 @@ fn launch_desktop() {
 /// Top component in the DOM.
 #[component]
 fn App(cx: Scope) -> Element {
-    let (frames_running, frame_id) = use_animation_frame(cx, false);
+    let (frames_running, frame_id, frame_time) = use_animation_frame(cx, false);
 
     use_shared_state_provider(cx, Universe::new); // State of all cells in the universe
     use_shared_state_provider(cx, || Redraw::False); // True if the universe needs to be redrawn
+    use_shared_state_provider(cx, || TicksPerFrame(1)); // Generations to advance each step, to fast-forward
+    use_shared_state_provider(cx, StepsPerSecond::default); // Steps to take each second, if chosen
+    use_shared_state_provider(cx, Session::default); // Whether the session is being recorded or replayed
+    use_shared_state_provider(cx, Walkthrough::default); // The cell being explained in educator mode
+    use_shared_state_provider(cx, Tool::default); // What clicking on the grid does
+    use_shared_state_provider(cx, Brush::default); // The cells the draw and erase tools paint at once
+    use_shared_state_provider(cx, HoveredCell::default); // The cell under the mouse on the grid
+    use_shared_state_provider(cx, Placement::default); // The library pattern being placed on the grid
+    use_shared_state_provider(cx, PaintState::default); // The state cells of multi-state automata are painted in
+    use_shared_state_provider(cx, Selection::default); // The cells selected on the grid
+    use_shared_state_provider(cx, MoveDrag::default); // The selected cells being moved
+    use_shared_state_provider(cx, CopiedRegion::default); // The selected cells last copied or cut
+    use_shared_state_provider(cx, Annotations::default); // Notes drawn over the grid
+    use_shared_state_provider(cx, DiffBase::default); // The board the diff view compares with
+    use_shared_state_provider(cx, Layers::default); // Patterns composed apart from the universe
+    use_shared_state_provider(cx, LastInput::default); // When the user last did anything
+    use_shared_state_provider(cx, AttractFade::default); // True while attract mode fades out the grid
+    use_shared_state_provider(cx, stored_palette); // The colors the universe is drawn with
+    use_shared_state_provider(cx, Appearance::stored); // The size of cells and how the grid is drawn
+    use_shared_state_provider(cx, Viewport::default); // The part of the universe shown on the grid
+    use_shared_state_provider(cx, PerformanceMode::default); // Drawing as little as possible, to run fast
+    use_shared_state_provider(cx, MemoryBudget::stored); // The memory history and statistics may use
+    use_shared_state_provider(cx, Density::stored); // The percent of cells alive in randomized universes
+    use_shared_state_provider(cx, CustomPatterns::stored); // Patterns saved from the pattern editor
+
+    #[cfg(feature = "desktop")]
+    use_shared_state_provider(cx, gamepad::GamepadCursor::default); // The cell under the gamepad cursor
+
+    let controls = use_controls_provider(cx, frames_running);
+
+    #[cfg(feature = "web")]
+    embed::use_embed(cx);
+
+    #[cfg(feature = "desktop")]
+    gamepad::use_gamepad(cx);
+
+    #[cfg(feature = "desktop")]
+    window_fit::use_window_fit(cx);
+
+    #[cfg(feature = "desktop")]
+    window_geometry::use_window_geometry(cx);
+
+    // Offer to restore the autosave of a session that crashed.
+    #[cfg(feature = "desktop")]
+    let autosave = render! { autosave::Autosave {} };
+    #[cfg(feature = "web")]
+    let autosave = None::<VNode>;
+
+    // Reload a pattern file whenever it is edited.
+    #[cfg(feature = "desktop")]
+    let pattern_watch = render! { pattern_watch::PatternWatch {} };
+    #[cfg(feature = "web")]
+    let pattern_watch = None::<VNode>;
+
+    // Fullscreen on a chosen monitor.
+    #[cfg(feature = "desktop")]
+    let fullscreen = render! { fullscreen::FullscreenControls {} };
+    #[cfg(feature = "web")]
+    let fullscreen = None::<VNode>;
+
+    // Show the status of the universe in the title of the window.
+    #[cfg(feature = "desktop")]
+    let window_title = render! { window_title::WindowTitle { frame_time: *frame_time.get() } };
+    #[cfg(feature = "web")]
+    let window_title = None::<VNode>;
+
+    // Any input keeps attract mode from starting, or ends it.
+    let last_input = use_shared_state::<LastInput>(cx).unwrap();
+    let grid_opacity = if use_shared_state::<AttractFade>(cx).unwrap().read().0 {
+        0
+    } else {
+        1
+    };
+    let grid_transition = if use_shared_state::<PerformanceMode>(cx)
+        .unwrap()
+        .read()
+        .is_on()
+    {
+        "none".to_string()
+    } else {
+        format!("opacity {FADE_MS}ms")
+    };
 
     render! {
-        h2 { display: "flex", justify_content: "center", font_family: "Helvetica", "Game of Life" }
-        div { display: "grid", justify_content: "center", GameOfLife { frame_id: *frame_id.get() } }
-        div { display: "flex", justify_content: "center",
-            button { onclick: move |_| { frames_running.set(true) }, "Start" }
-            button { onclick: move |_| { frames_running.set(false) }, "Stop" }
-            StepButton {}
+        div {
+            onmousemove: move |_| note_input(last_input),
+            onmousedown: move |_| note_input(last_input),
+            onkeydown: move |_| note_input(last_input),
+            onwheel: move |_| note_input(last_input),
+            h2 { display: "flex", justify_content: "center", font_family: "Helvetica", "Game of Life" }
+            autosave
+            window_title
+            div {
+                display: "grid",
+                justify_content: "center",
+                opacity: grid_opacity,
+                transition: "{grid_transition}",
+                GameOfLife { frame_id: *frame_id.get() }
+            }
+            Timeline {}
+            div { display: "flex", justify_content: "center",
+                button { onclick: move |_| controls.dispatch(Command::Start), "Start" }
+                button { onclick: move |_| controls.dispatch(Command::Stop), "Stop" }
+                StepBackButton {}
+                StepButton {}
+            }
+            SpeedControls {}
+            SeedInput {}
+            RulePicker {}
+            AutoStop {}
+            ToolPicker {}
+            SelectionStats {}
+            BrushPicker {}
+            PlacementPicker {}
+            StatePalette {}
+            ZoomControls {}
+            PerformanceToggle {}
+            fullscreen
+            LayersPanel {}
+            PatternEditor {}
+            AnnotationPanel {}
+            SelectionAnalysis {}
+            PredecessorSearch {}
+            EducatorControls {}
+            TourControls {}
+            PuzzlePanel {}
+            CensusPanel {}
+            SoupCensus {}
+            SoupSearchPanel {}
+            MutationExplorer {}
+            RuleComparison {}
+            PatternOfTheDay {}
+            ReplayControls {}
+            UrlImport {}
+            PatternFiles {}
+            ClipboardButtons {}
+            GollyExport {}
+            SaveSlots {}
+            DiffControls {}
+            ScreenshotTriggers {}
+            Sonification {}
+            pattern_watch
+            div { display: "flex", justify_content: "center", gap: "2em",
+                UniverseStats {}
+                FramesPerSecond { frame_time: *frame_time.get() }
+            }
+            div { display: "flex", justify_content: "center",
+                PeriodStatus {}
+            }
+            EngineComparison {}
+            UniverseBenchmarkButton {}
+            AttractMode {}
+            SettingsPanel {}
+        }
+    }
+}
+
+/// Show the generation of the universe, so a pattern seen at a generation can be found again, with its
+/// population and the cells born and the cells that died in the last tick.
+#[component]
+fn UniverseStats(cx: Scope) -> Element {
+    let Stats {
+        generation,
+        population,
+        births,
+        deaths,
+    } = use_shared_state::<Universe>(cx).unwrap().read().stats();
+
+    render! {
+        div { white_space: "pre", font_family: "monospace",
+            "Generation: {generation}\nPopulation: {population}\n    Births: {births}\n    Deaths: {deaths}"
         }
-        div { display: "flex", justify_content: "center", FramesPerSecond { frame_id: *frame_id.get() } }
     }
 }
 
 // Advance the universe one step when clicked.
 #[component]
 fn StepButton(cx: Scope) -> Element {
-    let universe = use_shared_state::<Universe>(cx).unwrap();
-    let redraw = use_shared_state::<Redraw>(cx).unwrap();
+    let controls = use_controls(cx);
 
     render! {
-        button { onclick: move |_| {
-                universe
-                    .with_mut(|universe| {
-                        universe.tick();
-                    });
-                redraw.with_mut(|redraw| { *redraw = Redraw::True })
-            },
-            "Step"
-        }
+        button { onclick: move |_| controls.dispatch(Command::Step), "Step" }
     }
 }
@@ -0,0 +1,206 @@
+//! A memory budget for the buffers that grow as the universe runs, such as history and statistics.
+//!
+//! Each buffer reports what it holds to the shared MemoryBudget.  When adding to a buffer would take the
+//! total over the cap, the buffer drops its oldest entries to make room, so long runs can't use up the
+//! memory of the browser or machine.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    mem::size_of,
+};
+
+use crate::{game_of_life::universe::Universe, storage};
+
+const BUDGET_KEY: &str = "memory_budget.txt";
+const MB: usize = 1024 * 1024;
+
+/// The caps that can be chosen, in megabytes.
+pub const BUDGET_CHOICES_MB: [usize; 4] = [16, 64, 256, 1024];
+const DEFAULT_BUDGET_MB: usize = 64;
+
+/// The cap on the memory of all buffers, and what each buffer holds now, in bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryBudget {
+    cap: usize,
+    used: BTreeMap<&'static str, usize>,
+}
+
+impl MemoryBudget {
+    /// Return the budget with the cap chosen in an earlier session, or the default cap.
+    pub fn stored() -> MemoryBudget {
+        let cap_mb = storage::load(BUDGET_KEY)
+            .and_then(|cap| cap.trim().parse().ok())
+            .unwrap_or(DEFAULT_BUDGET_MB);
+
+        MemoryBudget {
+            cap: cap_mb * MB,
+            used: BTreeMap::new(),
+        }
+    }
+
+    pub fn cap_mb(&self) -> usize {
+        self.cap / MB
+    }
+
+    /// Set the cap, and remember it for later sessions.
+    ///
+    /// Buffers over their share of a lower cap shrink the next time they are added to.
+    pub fn set_cap_mb(&mut self, cap_mb: usize) {
+        self.cap = cap_mb * MB;
+        storage::store(BUDGET_KEY, &cap_mb.to_string());
+    }
+
+    /// Return the bytes held by all buffers.
+    pub fn total(&self) -> usize {
+        self.used.values().sum()
+    }
+
+    /// Return the (name, bytes) held by each buffer that holds anything.
+    pub fn usage(&self) -> impl Iterator<Item = (&'static str, usize)> + '_ {
+        self.used
+            .iter()
+            .filter(|(_, bytes)| **bytes > 0)
+            .map(|(name, bytes)| (*name, *bytes))
+    }
+
+    /// Return the bytes the named buffer may hold, given what the other buffers hold.
+    fn allowance(&self, name: &'static str) -> usize {
+        let own = self.used.get(name).copied().unwrap_or(0);
+        self.cap.saturating_sub(self.total() - own)
+    }
+
+    fn set_used(&mut self, name: &'static str, bytes: usize) {
+        self.used.insert(name, bytes);
+    }
+}
+
+/// Return a size in bytes as megabytes, for showing.
+pub fn megabytes(bytes: usize) -> f64 {
+    bytes as f64 / MB as f64
+}
+
+/// Something whose memory can be estimated.
+pub trait Footprint {
+    /// Return roughly how many bytes the value holds, including what it points to.
+    fn footprint(&self) -> usize;
+}
+
+impl Footprint for Universe {
+    fn footprint(&self) -> usize {
+        size_of::<Universe>() + self.cell_bytes()
+    }
+}
+
+impl Footprint for u64 {
+    fn footprint(&self) -> usize {
+        size_of::<u64>()
+    }
+}
+
+impl Footprint for f64 {
+    fn footprint(&self) -> usize {
+        size_of::<f64>()
+    }
+}
+
+/// A first in, first out buffer kept within the memory budget by dropping its oldest entries.
+pub struct BudgetedBuffer<T> {
+    /// The name the buffer is shown by in the memory readout.
+    name: &'static str,
+    entries: VecDeque<T>,
+    bytes: usize,
+}
+
+impl<T: Footprint> BudgetedBuffer<T> {
+    pub fn new(name: &'static str) -> BudgetedBuffer<T> {
+        BudgetedBuffer {
+            name,
+            entries: VecDeque::new(),
+            bytes: 0,
+        }
+    }
+
+    /// Add the newest entry, first dropping the oldest entries until it fits in the budget.
+    ///
+    /// An entry larger than the whole allowance of the buffer is not kept.
+    pub fn push(&mut self, entry: T, budget: &mut MemoryBudget) {
+        let allowance = budget.allowance(self.name);
+        let size = entry.footprint();
+
+        while self.bytes + size > allowance {
+            if self.pop_front().is_none() {
+                break;
+            }
+        }
+        if self.bytes + size <= allowance {
+            self.bytes += size;
+            self.entries.push_back(entry);
+        }
+        budget.set_used(self.name, self.bytes);
+    }
+
+    /// Add a copy of the entry as the newest entry, reusing the memory of the oldest entry when the buffer
+    /// already holds count entries, so a full buffer doesn't allocate for each entry added.
+    pub fn push_copy(&mut self, entry: &T, count: usize, budget: &mut MemoryBudget)
+    where
+        T: Clone,
+    {
+        let recycled = match self.entries.len() >= count {
+            true => self.pop_front().map(|mut oldest| {
+                oldest.clone_from(entry);
+                oldest
+            }),
+            false => None,
+        };
+        self.push(recycled.unwrap_or_else(|| entry.clone()), budget);
+    }
+
+    /// Remove and return the newest entry.
+    pub fn pop_back(&mut self, budget: &mut MemoryBudget) -> Option<T> {
+        let entry = self.entries.pop_back()?;
+        self.bytes -= entry.footprint();
+        budget.set_used(self.name, self.bytes);
+        Some(entry)
+    }
+
+    /// Drop the newest entries until at most count are left.
+    pub fn truncate(&mut self, count: usize, budget: &mut MemoryBudget) {
+        while self.entries.len() > count {
+            self.pop_back(budget);
+        }
+    }
+
+    /// Drop the oldest entries until at most count are left.
+    pub fn keep_newest(&mut self, count: usize, budget: &mut MemoryBudget) {
+        while self.entries.len() > count {
+            self.pop_front();
+        }
+        budget.set_used(self.name, self.bytes);
+    }
+
+    /// Remove every entry.
+    pub fn clear(&mut self, budget: &mut MemoryBudget) {
+        self.entries.clear();
+        self.bytes = 0;
+        budget.set_used(self.name, 0);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return the entries from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.entries.iter()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.entries.get(index)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        let entry = self.entries.pop_front()?;
+        self.bytes -= entry.footprint();
+        Some(entry)
+    }
+}
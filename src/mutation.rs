@@ -0,0 +1,218 @@
+//! The mutation explorer: tries variants of the pattern with a few cells flipped, and ranks them, so
+//! interesting relatives of a pattern can be found by browsing rather than by hand.
+//!
+//! Each variant is run on its own copy of the universe without drawing.  Clicking a variant loads it.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::{
+        cells_path,
+        census::run_until_stable,
+        random::{PlatformRandom, RandomSource},
+        universe::Universe,
+    },
+    palette::Palette,
+};
+
+const DEFAULT_VARIANTS: u32 = 8;
+const DEFAULT_FLIPS: u32 = 3;
+const DEFAULT_GENERATIONS: u64 = 200;
+const MAX_VARIANTS: u32 = 32;
+/// The width of a thumbnail, in pixels.
+const THUMBNAIL_SIZE: f64 = 96.0;
+
+/// What variants are ranked by, highest first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Metric {
+    /// The generations until the variant repeats, such as by settling into still lifes and oscillators.
+    Lifespan,
+    /// The population after running the variant.
+    FinalPopulation,
+}
+
+impl Metric {
+    const ALL: [Metric; 2] = [Metric::Lifespan, Metric::FinalPopulation];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Metric::Lifespan => "lifespan",
+            Metric::FinalPopulation => "final population",
+        }
+    }
+
+    fn value(&self, variant: &Variant) -> u64 {
+        match self {
+            Metric::Lifespan => variant.lifespan,
+            Metric::FinalPopulation => variant.final_population as u64,
+        }
+    }
+}
+
+/// A mutated pattern, and how it ran.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Variant {
+    /// The (row, col) of the living cells of the variant.
+    cells: Vec<(u32, u32)>,
+    /// The generations until the variant repeated, or all the generations run if it didn't.
+    lifespan: u64,
+    final_population: usize,
+}
+
+impl Variant {
+    /// Return a copy of the universe with flips random cells toggled, run for the given generations.
+    ///
+    /// Cells are flipped in and around the pattern, so the mutations touch it, or anywhere in an empty universe.
+    fn mutate(
+        universe: &Universe,
+        flips: u32,
+        generations: u64,
+        random: &mut impl RandomSource,
+    ) -> Variant {
+        let mut mutated = universe.clone();
+        let (top, left, rows, cols) = match universe.bounding_box() {
+            Some(bounds) => (
+                bounds.top.saturating_sub(1),
+                bounds.left.saturating_sub(1),
+                bounds.height() + 2,
+                bounds.width() + 2,
+            ),
+            None => (0, 0, universe.height(), universe.width()),
+        };
+        for _ in 0..flips {
+            let row = (top + random.gen_range(rows)).min(universe.height() - 1);
+            let col = (left + random.gen_range(cols)).min(universe.width() - 1);
+            mutated.toggle_cell(row, col);
+        }
+        mutated.set_generation(0);
+        let cells = living_cells(&mutated);
+
+        let mut run = mutated.clone();
+        let lifespan = match run_until_stable(&mut run, generations) {
+            Some(_) => run.generation(),
+            None => generations,
+        };
+        while run.generation() < generations {
+            run.tick();
+        }
+
+        Variant {
+            cells,
+            lifespan,
+            final_population: run.population(),
+        }
+    }
+}
+
+fn living_cells(universe: &Universe) -> Vec<(u32, u32)> {
+    universe
+        .get_living_cells()
+        .into_iter()
+        .map(|(x, y)| (y as u32, x as u32))
+        .collect()
+}
+
+/// Settings for the variants to try, a button to try them, and thumbnails of the variants, ranked.
+#[component]
+pub fn MutationExplorer(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let palette = *use_shared_state::<Palette>(cx).unwrap().read();
+    let variant_count = use_state(cx, || DEFAULT_VARIANTS);
+    let flips = use_state(cx, || DEFAULT_FLIPS);
+    let generations = use_state(cx, || DEFAULT_GENERATIONS);
+    let metric = use_state(cx, || Metric::Lifespan);
+    let variants = use_state(cx, Vec::<Variant>::new);
+
+    let (width, height) = (universe.read().width(), universe.read().height());
+    let thumbnail_height = THUMBNAIL_SIZE * height as f64 / width as f64;
+
+    let mut ranked = variants.get().clone();
+    ranked.sort_by_key(|variant| std::cmp::Reverse(metric.value(variant)));
+    let thumbnails = ranked.into_iter().enumerate().map(|(rank, variant)| {
+        let value = metric.value(&variant);
+        let cells: Vec<(i64, i64)> = variant.cells.iter().map(|(row, col)| (*col as i64, *row as i64)).collect();
+        let path = cells_path(&cells);
+
+        rsx! {
+            div { key: "{rank}", display: "flex", flex_direction: "column", align_items: "center", cursor: "pointer",
+                onclick: move |_| controls.dispatch(Command::Load(variant.cells.clone())),
+                svg {
+                    width: THUMBNAIL_SIZE,
+                    height: thumbnail_height,
+                    view_box: "0 0 {width} {height}",
+                    rect { width: width as f64, height: height as f64, fill: palette.background }
+                    path { d: "{path}", fill: palette.alive }
+                }
+                span { font_family: "monospace", "#{rank + 1}: {value}" }
+            }
+        }
+    });
+    let metrics = Metric::ALL.into_iter().map(|choice| {
+        rsx! {
+            option { key: "{choice.label()}", value: "{choice.label()}", selected: choice == *metric.get(), "{choice.label()}" }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "4px",
+            span { "Mutate into" }
+            input {
+                r#type: "number",
+                min: 1,
+                max: MAX_VARIANTS as i64,
+                value: "{variant_count}",
+                oninput: move |event| {
+                    if let Ok(count) = event.value.parse::<u32>() {
+                        variant_count.set(count.clamp(1, MAX_VARIANTS));
+                    }
+                },
+            }
+            span { "variants of" }
+            input {
+                r#type: "number",
+                min: 1,
+                value: "{flips}",
+                oninput: move |event| {
+                    if let Ok(count) = event.value.parse::<u32>() {
+                        flips.set(count.max(1));
+                    }
+                },
+            }
+            span { "flipped cells, run for" }
+            input {
+                r#type: "number",
+                min: 1,
+                value: "{generations}",
+                oninput: move |event| {
+                    if let Ok(count) = event.value.parse::<u64>() {
+                        generations.set(count.max(1));
+                    }
+                },
+            }
+            span { "generations, ranked by" }
+            select {
+                onchange: move |event| {
+                    if let Some(choice) = Metric::ALL.into_iter().find(|choice| choice.label() == event.value) {
+                        metric.set(choice);
+                    }
+                },
+                metrics
+            }
+            button {
+                onclick: move |_| {
+                    let universe = universe.read();
+                    let mut random = PlatformRandom;
+                    variants.set(
+                        (0..*variant_count.get())
+                            .map(|_| Variant::mutate(&universe, *flips.get(), *generations.get(), &mut random))
+                            .collect(),
+                    );
+                },
+                "Explore"
+            }
+        }
+        div { display: "flex", justify_content: "center", flex_wrap: "wrap", gap: "8px", thumbnails }
+    }
+}
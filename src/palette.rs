@@ -0,0 +1,159 @@
+//! Color palettes for drawing the universe, including palettes that stay distinguishable with the common
+//! forms of color blindness.
+//!
+//! Every color a palette draws with is checked for contrast against its background, using the WCAG
+//! contrast ratio.
+
+/// The lowest contrast ratio allowed between any drawn color and the background: the WCAG minimum for
+/// graphical objects.
+pub const MIN_CONTRAST: f64 = 3.0;
+
+/// The colors used to draw the universe.  Colors are written as "#rrggbb".
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette {
+    pub name: &'static str,
+    pub background: &'static str,
+    pub alive: &'static str,
+    /// The colors of the youngest and oldest living cells, blended for the ages in between.
+    pub age_gradient: [&'static str; 2],
+    /// The color of cells that died recently.
+    pub trail: &'static str,
+    /// The colors of the living states of rules with more than two states.
+    pub states: [&'static str; 4],
+}
+
+pub const STANDARD: Palette = Palette {
+    name: "Standard",
+    background: "#ffffff",
+    alive: "#000000",
+    age_gradient: ["#000000", "#c62828"],
+    trail: "#767676",
+    states: ["#000000", "#c62828", "#2e7d32", "#1565c0"],
+};
+
+/// For red-green color blindness with weak green (the most common form): blue and vermillion.
+pub const DEUTERANOPIA: Palette = Palette {
+    name: "Deuteranopia",
+    background: "#ffffff",
+    alive: "#0072b2",
+    age_gradient: ["#0072b2", "#d55e00"],
+    trail: "#cc79a7",
+    states: ["#0072b2", "#d55e00", "#000000", "#cc79a7"],
+};
+
+/// For red-green color blindness with weak red, which makes reds look dark: blue and ochre.
+pub const PROTANOPIA: Palette = Palette {
+    name: "Protanopia",
+    background: "#ffffff",
+    alive: "#004488",
+    age_gradient: ["#004488", "#997700"],
+    trail: "#767676",
+    states: ["#004488", "#997700", "#000000", "#767676"],
+};
+
+/// For blue-yellow color blindness: red and teal.
+pub const TRITANOPIA: Palette = Palette {
+    name: "Tritanopia",
+    background: "#ffffff",
+    alive: "#b2182b",
+    age_gradient: ["#b2182b", "#007a7a"],
+    trail: "#aa4499",
+    states: ["#b2182b", "#007a7a", "#000000", "#aa4499"],
+};
+
+/// Bold colors on black, for low vision.
+pub const HIGH_CONTRAST: Palette = Palette {
+    name: "High contrast",
+    background: "#000000",
+    alive: "#ffff00",
+    age_gradient: ["#ffff00", "#00ffff"],
+    trail: "#ff00ff",
+    states: ["#ffff00", "#00ffff", "#ffffff", "#ff00ff"],
+};
+
+pub const PALETTES: [Palette; 5] = [
+    STANDARD,
+    DEUTERANOPIA,
+    PROTANOPIA,
+    TRITANOPIA,
+    HIGH_CONTRAST,
+];
+
+impl Default for Palette {
+    fn default() -> Self {
+        STANDARD
+    }
+}
+
+impl Palette {
+    /// Return the palette with the given name.
+    pub fn find(name: &str) -> Option<Palette> {
+        PALETTES.into_iter().find(|palette| palette.name == name)
+    }
+
+    /// Return the color of a living cell of the given age, from 0 (just born) to 1 (the oldest shown).
+    pub fn age_color(&self, age: f64) -> String {
+        let [young, old] = self.age_gradient.map(rgb);
+        let age = age.clamp(0.0, 1.0);
+        let blend =
+            |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * age).round() as u8;
+
+        format!(
+            "#{:02x}{:02x}{:02x}",
+            blend(young.0, old.0),
+            blend(young.1, old.1),
+            blend(young.2, old.2)
+        )
+    }
+
+    /// Return the lowest contrast ratio between the background and any color the palette draws with.
+    ///
+    /// The age gradient is checked at several ages, since blends can have less contrast than either end.
+    pub fn min_contrast(&self) -> f64 {
+        let ages = (0..=4).map(|step| self.age_color(step as f64 / 4.0));
+        let colors = [self.alive, self.trail]
+            .into_iter()
+            .chain(self.states)
+            .map(str::to_string)
+            .chain(ages);
+
+        colors
+            .map(|color| contrast_ratio(&color, self.background))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+/// Return the WCAG contrast ratio of two colors, from 1 (the same) to 21 (black on white).
+pub fn contrast_ratio(color: &str, other: &str) -> f64 {
+    let (lighter, darker) = {
+        let (a, b) = (luminance(color), luminance(other));
+        (a.max(b), a.min(b))
+    };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Return the WCAG relative luminance of a color.
+fn luminance(color: &str) -> f64 {
+    let linear = |channel: u8| {
+        let channel = channel as f64 / 255.0;
+        if channel <= 0.03928 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let (red, green, blue) = rgb(color);
+
+    0.2126 * linear(red) + 0.7152 * linear(green) + 0.0722 * linear(blue)
+}
+
+/// Return the red, green and blue of a "#rrggbb" color.  Malformed channels are 0.
+fn rgb(color: &str) -> (u8, u8, u8) {
+    let channel = |range| {
+        color
+            .get(range)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .unwrap_or(0)
+    };
+    (channel(1..3), channel(3..5), channel(5..7))
+}
@@ -0,0 +1,95 @@
+//! Loading patterns from files in the formats other programs write, and saving the universe as Life 1.06.
+//!
+//! Macrocell files saved from Golly's HashLife can hold patterns far larger than the universe, and a few
+//! characters of RLE can stand for billions of cells; only the top left of such a pattern, the size of the
+//! universe, is loaded.
+//!
+//! Life 1.06 lists one living cell per line, so a pattern saved after each change diffs cleanly.
+
+use std::str::FromStr;
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    file_io::{read_chosen_file, save_text_file},
+    game_of_life::{
+        life106::{is_life106, read_life106, write_life106},
+        macrocell::read_macrocell,
+        rle::read_rle,
+        universe::Universe,
+    },
+};
+
+/// Return the (row, col) of every living cell of a pattern written as Life 1.06, RLE, macrocell, or
+/// plaintext (see Universe).
+///
+/// Macrocell and RLE patterns can be far larger than the universe, so they are cut off to the (height, width)
+/// of the universe as they are read.
+pub fn read_pattern(text: &str, size: (u32, u32)) -> Result<Vec<(u32, u32)>, String> {
+    let is_rle = text
+        .lines()
+        .map(str::trim)
+        .any(|line| line.starts_with("x ") || line.starts_with("x="))
+        || text.trim_end().ends_with('!');
+
+    if text.trim_start().starts_with("[M2]") {
+        read_macrocell(text, size)
+    } else if is_life106(text) {
+        read_life106(text)
+    } else if is_rle {
+        read_rle(text, size)
+    } else {
+        let universe = Universe::from_str(text)?;
+
+        Ok(universe
+            .get_living_cells()
+            .into_iter()
+            .map(|(x, y)| (y as u32, x as u32))
+            .collect())
+    }
+}
+
+/// A file input to load a pattern into the universe, and a button to save the universe as Life 1.06.
+#[component]
+pub fn PatternFiles(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let error = use_state(cx, || None::<String>);
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            label { "Load pattern file: ",
+                input { r#type: "file", accept: ".rle,.lif,.life,.mc,.cells,.txt",
+                    onchange: move |event| {
+                        to_owned![controls, error];
+                        let files = event.files.clone();
+                        let size = {
+                            let universe = universe.read();
+                            (universe.height(), universe.width())
+                        };
+                        cx.spawn(async move {
+                            match read_chosen_file(files).await.map(|text| read_pattern(&text, size)) {
+                                Some(Ok(cells)) => {
+                                    error.set(None);
+                                    controls.dispatch(Command::Load(cells));
+                                }
+                                Some(Err(message)) => error.set(Some(message)),
+                                None => error.set(Some("Could not read the pattern file".to_string())),
+                            }
+                        });
+                    }
+                }
+            }
+            button {
+                onclick: move |_| {
+                    let universe = universe.read();
+                    let file_name = format!("game_of_life_gen{}.lif", universe.generation());
+                    save_text_file(&file_name, &write_life106(&universe));
+                },
+                "Save as Life 1.06"
+            }
+            error.get().as_ref().map(|message| rsx! { span { color: "red", "{message}" } })
+        }
+    }
+}
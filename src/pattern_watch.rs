@@ -0,0 +1,108 @@
+//! Watching a pattern file on desktop, so that it can be edited in a text editor while the universe follows along.
+
+use std::path::{Path, PathBuf};
+
+use dioxus::prelude::*;
+use notify::{RecursiveMode, Watcher};
+
+use crate::{
+    command::{use_controls, Command, Controls},
+    game_of_life::universe::Universe,
+    pattern_files::read_pattern,
+};
+
+/// Replace the universe with the pattern in the file.
+fn load_pattern_file(path: &Path, controls: &Controls, universe: &UseSharedState<Universe>) {
+    let size = {
+        let universe = universe.read();
+        (universe.height(), universe.width())
+    };
+    let cells = std::fs::read_to_string(path)
+        .map_err(|error| error.to_string())
+        .and_then(|text| read_pattern(&text, size));
+
+    match cells {
+        Ok(cells) => controls.dispatch(Command::Load(cells)),
+        Err(error) => println!("Could not load {}: {error}", path.display()),
+    }
+}
+
+/// Load the file and reload it each time it changes, until the returned future is dropped.
+async fn watch_pattern_file(path: PathBuf, controls: Controls, universe: UseSharedState<Universe>) {
+    // Events name the file by its full path.
+    let path = match std::fs::canonicalize(&path) {
+        Ok(path) => path,
+        Err(error) => {
+            println!("Could not watch {}: {error}", path.display());
+            return;
+        }
+    };
+    load_pattern_file(&path, &controls, &universe);
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            let _ = sender.send(event);
+        }
+    });
+    // Editors often save by replacing the file, so watch its directory rather than the file itself.
+    let watching = watcher.and_then(|mut watcher| {
+        watcher.watch(path.parent().unwrap_or(&path), RecursiveMode::NonRecursive)?;
+        Ok(watcher)
+    });
+    let _watcher = match watching {
+        Ok(watcher) => watcher,
+        Err(error) => {
+            println!("Could not watch {}: {error}", path.display());
+            return;
+        }
+    };
+
+    while let Some(event) = receiver.recv().await {
+        let changed = event.kind.is_create() || event.kind.is_modify();
+
+        if changed && event.paths.contains(&path) {
+            load_pattern_file(&path, &controls, &universe);
+        }
+    }
+}
+
+/// A text box for the path of a pattern file to watch, with buttons to start and stop watching it.
+#[component]
+pub fn PatternWatch(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let path = use_state(cx, String::new);
+    let watched = use_state(cx, || None::<PathBuf>);
+
+    // Restarting the future when the watched file changes drops the previous watcher.
+    use_future(cx, (watched,), |(watched,)| {
+        to_owned![controls, universe];
+        async move {
+            if let Some(watched) = watched.current().as_ref().clone() {
+                watch_pattern_file(watched, controls, universe).await;
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", gap: "8px",
+            input {
+                r#type: "text",
+                placeholder: "Pattern file (RLE, Life 1.06, macrocell or plaintext)",
+                value: "{path}",
+                oninput: move |event| path.set(event.value.clone()),
+            }
+            button {
+                disabled: path.is_empty(),
+                onclick: move |_| watched.set(Some(PathBuf::from(path.get()))),
+                "Watch"
+            }
+            button {
+                disabled: watched.is_none(),
+                onclick: move |_| watched.set(None),
+                "Stop watching"
+            }
+        }
+    }
+}
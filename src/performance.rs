@@ -0,0 +1,56 @@
+//! Performance mode: drawing as little as possible and advancing several generations each frame, to show
+//! how fast the engine runs, even on slow machines.
+//!
+//! Grid lines and the attract mode fade are left out, and the cells are drawn with the path renderer.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::TicksPerFrame,
+};
+
+/// Generations advanced each frame in performance mode.
+const PERFORMANCE_TICKS_PER_FRAME: u32 = 8;
+
+/// If performance mode is on, the generations per frame to go back to when it is turned off.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PerformanceMode(pub Option<u32>);
+
+impl PerformanceMode {
+    pub fn is_on(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+/// A button to turn performance mode on and off.
+#[component]
+pub fn PerformanceToggle(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let performance = use_shared_state::<PerformanceMode>(cx).unwrap();
+    let ticks_per_frame = use_shared_state::<TicksPerFrame>(cx).unwrap();
+
+    let on = performance.read().is_on();
+
+    render! {
+        div { display: "flex", justify_content: "center",
+            button {
+                onclick: move |_| {
+                    let restore = performance.read().0;
+                    match restore {
+                        Some(ticks) => {
+                            performance.write().0 = None;
+                            controls.dispatch(Command::SetTicksPerFrame(ticks));
+                        }
+                        None => {
+                            let TicksPerFrame(ticks) = *ticks_per_frame.read();
+                            performance.write().0 = Some(ticks);
+                            controls.dispatch(Command::SetTicksPerFrame(ticks.max(PERFORMANCE_TICKS_PER_FRAME)));
+                        }
+                    }
+                },
+                if on { "Leave performance mode" } else { "Performance mode" }
+            }
+        }
+    }
+}
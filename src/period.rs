@@ -0,0 +1,40 @@
+//! Showing when the universe has become periodic, and with what period.
+
+use dioxus::prelude::*;
+
+use crate::game_of_life::{
+    period::{PeriodDetector, Periodic},
+    universe::Universe,
+};
+
+/// "Stabilized with period N at generation G" once the universe repeats.
+#[component]
+pub fn PeriodStatus(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let detector = use_ref(cx, PeriodDetector::new);
+    let periodic = use_state(cx, || None::<Periodic>);
+
+    let generation = universe.read().generation();
+    let population = universe.read().population();
+    use_effect(cx, (&generation, &population), |_| {
+        to_owned![universe, detector, periodic];
+        async move {
+            let found = detector.with_mut(|detector| detector.observe(&universe.read()));
+            if found != *periodic.current() {
+                periodic.set(found);
+            }
+        }
+    });
+
+    let Periodic { period, generation } = (*periodic.get())?;
+    let kind = match period {
+        1 => " (still life)",
+        _ => "",
+    };
+
+    render! {
+        div { font_family: "monospace",
+            "Stabilized with period {period} at generation {generation}{kind}"
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Placing patterns of the library on the grid.
+//!
+//! While a pattern is being placed, a translucent ghost of it follows the mouse over the grid, and clicking
+//! stamps the pattern into the universe with its middle on the cell clicked.  Stamping only brings cells to
+//! life, so patterns can be placed next to each other without erasing one another.  Placing goes on until
+//! it is stopped, so a pattern can be stamped many times.
+
+use std::collections::BTreeSet;
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::Controls,
+    custom_patterns::CustomPatterns,
+    game_of_life::{patterns::Pattern, universe::Universe, CellLayer, HoveredCell},
+    layers::merge_toggles,
+    palette::Palette,
+};
+
+const GHOST_OPACITY: f64 = 0.4;
+
+/// The pattern being placed on the grid, or None when clicking the grid uses the tool chosen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Placement(pub Option<Pattern>);
+
+/// Return the (row, col) offsets of the living cells of the pattern from its middle cell.
+fn centered_offsets(pattern: &Pattern) -> Vec<(i64, i64)> {
+    let cells = pattern.cells();
+    let rows = cells.iter().map(|(row, _)| row + 1).max().unwrap_or(0) as i64;
+    let cols = cells.iter().map(|(_, col)| col + 1).max().unwrap_or(0) as i64;
+
+    cells
+        .into_iter()
+        .map(|(row, col)| (row as i64 - rows / 2, col as i64 - cols / 2))
+        .collect()
+}
+
+/// Return the cells the pattern covers with its middle on the (row, col) cell.  As with stamping, cells past
+/// the edges wrap around, or are left out if the edges are dead.
+fn placed_cells(pattern: &Pattern, (row, col): (u32, u32), universe: &Universe) -> Vec<(u32, u32)> {
+    universe.region_cells(row, col, &centered_offsets(pattern))
+}
+
+/// Stamp the pattern into the universe with its middle on the (row, col) cell, as one change to step back.
+pub fn stamp_pattern(
+    controls: &Controls,
+    universe: &UseSharedState<Universe>,
+    pattern: &Pattern,
+    cell: (u32, u32),
+) {
+    let toggles = {
+        let universe = universe.read();
+        let cells: BTreeSet<(u32, u32)> =
+            placed_cells(pattern, cell, &universe).into_iter().collect();
+        merge_toggles(&universe, &cells)
+    };
+    controls.dispatch_all(toggles);
+}
+
+/// The choice of a pattern of the library to place on the grid, and a button to stop placing it.
+#[component]
+pub fn PlacementPicker(cx: Scope) -> Element {
+    let placement = use_shared_state::<Placement>(cx).unwrap();
+    // The built-in patterns, and those saved from the pattern editor.
+    let library = use_shared_state::<CustomPatterns>(cx)
+        .unwrap()
+        .read()
+        .library();
+
+    let placing = placement.read().0;
+    let patterns = library.iter().map(|choice| {
+        let selected = placing.is_some_and(|placing| placing.name == choice.name);
+        rsx! {
+            option { key: "{choice.name}", value: "{choice.name}", selected: selected, "{choice.name}" }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            label {
+                "Place pattern "
+                select {
+                    onchange: move |event| {
+                        let choice = library.iter().find(|choice| choice.name == event.value).copied();
+                        *placement.write() = Placement(choice);
+                    },
+                    option { value: "", selected: placing.is_none(), "none" }
+                    patterns
+                }
+            }
+            button {
+                disabled: placing.is_none(),
+                onclick: move |_| *placement.write() = Placement(None),
+                "Stop placing"
+            }
+        }
+    }
+}
+
+/// A translucent ghost of the pattern being placed, where clicking the grid would stamp it.
+#[component]
+pub fn PlacementGhost(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let palette = *use_shared_state::<Palette>(cx).unwrap().read();
+    let Placement(placing) = *use_shared_state::<Placement>(cx).unwrap().read();
+    let HoveredCell(hovered) = *use_shared_state::<HoveredCell>(cx).unwrap().read();
+
+    let (pattern, cell) = (placing?, hovered?);
+    let cells = placed_cells(&pattern, cell, &universe.read());
+    let ghost = cells.into_iter().map(|(row, col)| {
+        rsx! {
+            rect {
+                key: "{row},{col}",
+                x: col as i64,
+                y: row as i64,
+                width: 1,
+                height: 1,
+                fill: palette.alive,
+                fill_opacity: GHOST_OPACITY,
+            }
+        }
+    });
+
+    render! {
+        CellLayer { ghost }
+    }
+}
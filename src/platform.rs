@@ -0,0 +1,122 @@
+//! Platform specific glue for time and animation frames, so that higher level code can be written once.
+//!
+//! On the web, these call the browser directly through web_sys.  On desktop, the clock is std::time, and
+//! animation frames are requested by JavaScript run in the webview with use_eval(), since dioxus desktop
+//! code is compiled natively and has no web_sys bindings.
+
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::*;
+
+#[cfg(feature = "web")]
+#[macro_export]
+macro_rules! console_log {
+    ($($t:tt)*) => (
+        web_sys::console::log_1(&format!($($t)*).into())
+    )
+}
+
+#[cfg(feature = "web")]
+pub fn window() -> web_sys::Window {
+    web_sys::window().expect("no global `window` exists")
+}
+
+/// Return a timestamp in milliseconds, for measuring how long something takes.
+#[cfg(feature = "web")]
+pub fn now_ms() -> f64 {
+    window()
+        .performance()
+        .expect("performance should be available")
+        .now()
+}
+
+/// Return a timestamp in milliseconds, for measuring how long something takes.
+#[cfg(feature = "desktop")]
+pub fn now_ms() -> f64 {
+    use std::{sync::OnceLock, time::Instant};
+
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+}
+
+/// Return the milliseconds since the Unix epoch, by the wall clock.
+#[cfg(feature = "web")]
+pub fn unix_time_ms() -> f64 {
+    web_sys::js_sys::Date::now()
+}
+
+/// Return the milliseconds since the Unix epoch, by the wall clock.
+#[cfg(feature = "desktop")]
+pub fn unix_time_ms() -> f64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |elapsed| elapsed.as_secs_f64() * 1000.0)
+}
+
+/// Wait for the given number of milliseconds.
+#[cfg(feature = "web")]
+pub async fn sleep_ms(ms: u32) {
+    gloo_timers::future::TimeoutFuture::new(ms).await;
+}
+
+/// Wait for the given number of milliseconds.
+#[cfg(feature = "desktop")]
+pub async fn sleep_ms(ms: u32) {
+    tokio::time::sleep(std::time::Duration::from_millis(ms as u64)).await;
+}
+
+/// Call the closure on the next animation frame, with the time of the frame in milliseconds.
+///
+/// Returns the id of the animation frame.
+#[cfg(feature = "web")]
+pub fn request_frame(f: &Closure<dyn FnMut(f64)>) -> i32 {
+    window()
+        .request_animation_frame(f.as_ref().unchecked_ref())
+        .expect("should register `requestAnimationFrame` OK")
+}
+
+/// Cancel a requested animation frame.
+#[cfg(feature = "web")]
+pub fn cancel_frame(animation_id: i32) {
+    window()
+        .cancel_animation_frame(animation_id)
+        .expect("Unable to cancel animation_frame")
+}
+
+#[cfg(feature = "desktop")]
+type EvalCreator =
+    std::rc::Rc<dyn Fn(&str) -> Result<dioxus::prelude::UseEval, dioxus::prelude::EvalError>>;
+
+/// Request animation frames continuously in the webview.
+///
+/// The returned eval receives [id, time] for each new animation frame: the id of the next frame requested,
+/// and the time of the frame in milliseconds, as given by requestAnimationFrame().  Each loop of frames is numbered, and a
+/// loop ends when a later loop starts or cancel_frame() is called, even if its latest frame id hasn't
+/// reached the app yet.
+#[cfg(feature = "desktop")]
+pub fn request_frame(create_eval: &EvalCreator) -> dioxus::prelude::UseEval {
+    create_eval(
+        r#"
+            const frameLoop = (window.gameOfLifeFrameLoop || 0) + 1;
+            window.gameOfLifeFrameLoop = frameLoop;
+            function gotFrame(frame_ms) {
+                if (window.gameOfLifeFrameLoop !== frameLoop) {
+                    return;
+                }
+                dioxus.send([window.requestAnimationFrame(gotFrame), frame_ms]);
+            }
+            gotFrame(performance.now());
+        "#,
+    )
+    .expect("should be able to request animation frames")
+}
+
+/// Cancel a requested animation frame, stopping the frames requested by request_frame().
+#[cfg(feature = "desktop")]
+pub fn cancel_frame(create_eval: &EvalCreator, animation_id: i32) {
+    create_eval(&format!(
+        "window.gameOfLifeFrameLoop = (window.gameOfLifeFrameLoop || 0) + 1; window.cancelAnimationFrame({animation_id});"
+    ))
+        .expect("should be able to cancel animation frames");
+}
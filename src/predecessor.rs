@@ -0,0 +1,127 @@
+//! An experimental search for a pattern that evolves into the selected cells some generations later.
+
+use std::collections::HashSet;
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::{
+        predecessor::{find_predecessor, Predecessor},
+        universe::{BoundingBox, Universe},
+    },
+    selection::Selection,
+};
+
+/// The most generations back that can be searched, since each generation makes the search much slower.
+const MAX_GENERATIONS_BACK: u32 = 3;
+/// Cell assignments tried before the search gives up.
+const SEARCH_BUDGET: u64 = 2_000_000;
+
+/// A search that was run, and what it found.
+struct SearchResult {
+    /// The bounds of the living cells searched from.
+    bounds: BoundingBox,
+    generations: u32,
+    predecessor: Predecessor,
+}
+
+/// Controls to search for a predecessor of the selection, and place it on the grid if one is found.
+#[component]
+pub fn PredecessorSearch(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let selection = use_shared_state::<Selection>(cx).unwrap();
+    let controls = use_controls(cx);
+    let generations = use_state(cx, || 1_u32);
+    let result = use_state(cx, || None::<SearchResult>);
+
+    let report = result.get().as_ref().map(|search| match &search.predecessor {
+        Predecessor::Found(cells) => {
+            let count = cells.len();
+            let back = search.generations;
+
+            rsx! {
+                span { font_family: "monospace", "Found a predecessor of {count} cells {back} generations back" }
+                button {
+                    onclick: move |_| {
+                        let toggles = placement_toggles(search, &universe.read());
+                        controls.dispatch_all(toggles);
+                    },
+                    "Place predecessor"
+                }
+            }
+        }
+        Predecessor::NotFound => rsx! {
+            span { font_family: "monospace", "No predecessor nearby: likely a Garden of Eden" }
+        },
+        Predecessor::GaveUp => rsx! {
+            span { font_family: "monospace", "Gave up after {SEARCH_BUDGET} steps" }
+        },
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            input {
+                r#type: "number",
+                min: 1,
+                max: MAX_GENERATIONS_BACK as i64,
+                value: "{generations}",
+                oninput: move |event| {
+                    if let Ok(back) = event.value.parse::<u32>() {
+                        generations.set(back.clamp(1, MAX_GENERATIONS_BACK));
+                    }
+                },
+            }
+            button {
+                disabled: selection.read().0.is_none(),
+                onclick: move |_| {
+                    let universe = universe.read();
+                    let cells = selection.read().living_cells(&universe);
+                    let bounds = BoundingBox {
+                        top: cells.iter().map(|(row, _)| *row).min().unwrap_or(0),
+                        left: cells.iter().map(|(_, col)| *col).min().unwrap_or(0),
+                        bottom: cells.iter().map(|(row, _)| *row).max().unwrap_or(0),
+                        right: cells.iter().map(|(_, col)| *col).max().unwrap_or(0),
+                    };
+
+                    result.set(Some(SearchResult {
+                        bounds,
+                        generations: *generations.get(),
+                        predecessor: find_predecessor(&cells, universe.rule(), *generations.get(), SEARCH_BUDGET),
+                    }));
+                },
+                "Find predecessor"
+            }
+            report
+        }
+    }
+}
+
+/// Return the toggles that replace the searched cells with the predecessor found.
+fn placement_toggles(search: &SearchResult, universe: &Universe) -> Vec<Command> {
+    let Predecessor::Found(cells) = &search.predecessor else {
+        return Vec::new();
+    };
+    let bounds = search.bounds;
+    let alive: HashSet<(i64, i64)> = cells
+        .iter()
+        .map(|(row, col)| (row + bounds.top as i64, col + bounds.left as i64))
+        .collect();
+
+    // The predecessor can extend one cell further than the searched cells for every generation back.
+    let margin = search.generations as i64;
+    let rows = (bounds.top as i64 - margin).max(0)
+        ..=(bounds.bottom as i64 + margin).min(universe.height() as i64 - 1);
+    let cols = (bounds.left as i64 - margin).max(0)
+        ..=(bounds.right as i64 + margin).min(universe.width() as i64 - 1);
+
+    rows.flat_map(|row| cols.clone().map(move |col| (row, col)))
+        .filter(|(row, col)| {
+            alive.contains(&(*row, *col)) != universe.is_alive(*row as u32, *col as u32)
+        })
+        .map(|(row, col)| Command::Toggle {
+            row: row as u32,
+            col: col as u32,
+        })
+        .collect()
+}
@@ -0,0 +1,242 @@
+//! Puzzle mode: reach a target board from a starting board by toggling a limited number of cells.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::{rle::read_rle, universe::Universe},
+    storage,
+};
+
+/// The storage key of the titles of the solved puzzles.
+const PROGRESS_KEY: &str = "puzzles_solved.json";
+
+/// A goal: toggle at most max_toggles cells of the start board so that it becomes exactly the target board
+/// within the given number of generations.
+struct Puzzle {
+    title: &'static str,
+    goal: &'static str,
+    /// The start board as RLE, with its top left corner at start_at (row, col).
+    start: &'static str,
+    start_at: (u32, u32),
+    /// The target board as RLE, with its top left corner at target_at (row, col).
+    target: &'static str,
+    target_at: (u32, u32),
+    generations: u64,
+    max_toggles: usize,
+}
+
+const PUZZLES: &[Puzzle] = &[
+    Puzzle {
+        title: "Stop the blinker",
+        goal: "Leave the board empty within 5 generations by toggling 1 cell.",
+        start: "3o!",
+        start_at: (30, 30),
+        target: "!",
+        target_at: (0, 0),
+        generations: 5,
+        max_toggles: 1,
+    },
+    Puzzle {
+        title: "Build a block",
+        goal: "Turn the pair of cells into a block within 5 generations by toggling 1 cell.",
+        start: "2o!",
+        start_at: (30, 30),
+        target: "2o$2o!",
+        target_at: (30, 30),
+        generations: 5,
+        max_toggles: 1,
+    },
+    Puzzle {
+        title: "Grow a beehive",
+        goal: "Turn the blinker into this beehive within 10 generations by toggling 1 cell.",
+        start: "3o!",
+        start_at: (30, 30),
+        target: "b2o$o2bo$b2o!",
+        target_at: (29, 30),
+        generations: 10,
+        max_toggles: 1,
+    },
+    Puzzle {
+        title: "Repair the glider",
+        goal: "Make a glider reach this spot within 20 generations by toggling at most 3 cells.",
+        start: "bo$2bo!",
+        start_at: (10, 10),
+        target: "bo$2bo$3o!",
+        target_at: (12, 12),
+        generations: 20,
+        max_toggles: 3,
+    },
+];
+
+impl Puzzle {
+    fn start_universe(&self) -> Universe {
+        board(self.start, self.start_at)
+    }
+
+    fn target_universe(&self) -> Universe {
+        board(self.target, self.target_at)
+    }
+
+    /// Check whether the universe solves the puzzle.
+    ///
+    /// Returns the generation at which the target is reached, or why the puzzle is not solved.
+    fn check(&self, universe: &Universe) -> Result<u64, String> {
+        let toggles = toggled_cells(&self.start_universe(), universe);
+        if toggles > self.max_toggles {
+            return Err(format!(
+                "You toggled {toggles} cells, but may toggle at most {}.",
+                self.max_toggles
+            ));
+        }
+
+        let target = self.target_universe();
+        let mut board = universe.clone();
+        for generation in 0..=self.generations {
+            if board.same_cells(&target) {
+                return Ok(generation);
+            }
+            board.tick();
+        }
+        Err(format!(
+            "The board does not reach the target within {} generations.",
+            self.generations
+        ))
+    }
+}
+
+/// Return the (row, col) of the living cells of the RLE pattern, with its top left corner at (row, col).
+fn place(rle: &str, (row, col): (u32, u32)) -> Vec<(u32, u32)> {
+    read_rle(rle, (u32::MAX, u32::MAX))
+        .expect("puzzle patterns should be valid RLE")
+        .into_iter()
+        .map(|(cell_row, cell_col)| (cell_row + row, cell_col + col))
+        .collect()
+}
+
+/// Return a universe with the cells of the RLE pattern, with its top left corner at (row, col).
+fn board(rle: &str, at: (u32, u32)) -> Universe {
+    let mut universe = Universe::new();
+
+    universe.set_cells(&place(rle, at));
+    universe
+}
+
+/// Return the number of cells that differ between the two universes.
+fn toggled_cells(from: &Universe, to: &Universe) -> usize {
+    from.cells()
+        .zip(to.cells())
+        .filter(|(from, to)| from != to)
+        .count()
+}
+
+/// Return the target of the puzzle as text, with `.` for dead cells and `O` for living ones.
+fn target_text(puzzle: &Puzzle) -> String {
+    let target = puzzle.target_universe();
+    let Some(bounds) = target.bounding_box() else {
+        return "(an empty board)".to_string();
+    };
+
+    (bounds.top..=bounds.bottom)
+        .map(|row| {
+            (bounds.left..=bounds.right)
+                .map(|col| if target.is_alive(row, col) { 'O' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn load_progress() -> Vec<String> {
+    storage::load(PROGRESS_KEY)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Lists the puzzles, and shows the goal and progress of the chosen one.
+///
+/// Each puzzle is unlocked by solving the one before it.
+#[component]
+pub fn PuzzlePanel(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    // The index of the chosen puzzle.
+    let chosen = use_state(cx, || None::<usize>);
+    let solved = use_state(cx, load_progress);
+    let message = use_state(cx, || None::<String>);
+
+    let is_solved = |puzzle: &Puzzle| solved.iter().any(|title| title == puzzle.title);
+    let unlocked = PUZZLES
+        .iter()
+        .position(|puzzle| !is_solved(puzzle))
+        .unwrap_or(PUZZLES.len());
+
+    let choices = PUZZLES.iter().enumerate().map(|(index, puzzle)| {
+        let mark = if is_solved(puzzle) { "✓ " } else { "" };
+
+        rsx! {
+            button {
+                key: "{puzzle.title}",
+                disabled: index > unlocked,
+                onclick: move |_| {
+                    chosen.set(Some(index));
+                    message.set(None);
+                    controls.dispatch(Command::Stop);
+                    controls.dispatch(Command::Load(place(PUZZLES[index].start, PUZZLES[index].start_at)));
+                },
+                "{mark}{puzzle.title}"
+            }
+        }
+    });
+
+    let details = chosen.get().map(|index| {
+        let puzzle = &PUZZLES[index];
+        let toggles = toggled_cells(&puzzle.start_universe(), &universe.read());
+        let target = target_text(puzzle);
+        let (target_row, target_col) = puzzle.target_at;
+
+        rsx! {
+            div { "{puzzle.goal}" }
+            div { "Target, at row {target_row}, column {target_col}:" }
+            pre { margin: "0", "{target}" }
+            div { "Cells toggled: {toggles} of {puzzle.max_toggles}" }
+            div { display: "flex", gap: "4px",
+                button {
+                    onclick: move |_| {
+                        let puzzle = &PUZZLES[index];
+                        match puzzle.check(&universe.read()) {
+                            Ok(generation) => {
+                                message.set(Some(format!("Solved at generation {generation}!")));
+                                if !is_solved(puzzle) {
+                                    let mut progress = solved.get().clone();
+                                    progress.push(puzzle.title.to_string());
+                                    storage::store(PROGRESS_KEY, &serde_json::to_string(&progress).expect("titles should serialize"));
+                                    solved.set(progress);
+                                }
+                            }
+                            Err(reason) => message.set(Some(reason)),
+                        }
+                    },
+                    "Check"
+                }
+                button {
+                    onclick: move |_| {
+                        chosen.set(None);
+                        message.set(None);
+                    },
+                    "Close"
+                }
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", flex_direction: "column", align_items: "center", gap: "4px",
+            div { display: "flex", gap: "4px", "Puzzles: ", choices }
+            details,
+            if let Some(message) = message.get() {
+                rsx! { div { font_weight: "bold", "{message}" } }
+            }
+        }
+    }
+}
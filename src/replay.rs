@@ -0,0 +1,434 @@
+//! Deterministic recording and replay of sessions.
+//!
+//! A recording holds the size and settings of the universe it started from, the seed that universe was
+//! randomized with, and every command dispatched after it tagged with the generation it was dispatched
+//! at.  Since randomization is seeded, re-applying the commands at the same generations reproduces the
+//! session exactly.
+
+use std::{fmt, str::FromStr};
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{checked_size, use_controls, Command},
+    file_io::{read_chosen_file, save_text_file},
+    game_of_life::{
+        automaton::Automaton,
+        rule::Rule,
+        universe::{Boundary, Topology, Universe},
+    },
+    time_series::{run_statistics, to_csv, to_json, CSV_FILE_NAME, JSON_FILE_NAME},
+};
+
+const REPLAY_FILE_NAME: &str = "game_of_life_replay.txt";
+
+/// Whether commands are being recorded, replayed, or neither.
+#[derive(Default)]
+pub enum Session {
+    #[default]
+    Live,
+    Recording(Recording),
+    Replaying(Replay),
+}
+
+impl Session {
+    pub fn is_live(&self) -> bool {
+        matches!(self, Session::Live)
+    }
+}
+
+/// A recorded session.
+///
+/// Saved as a seed line, lines for the size and settings of the universe, and a "generation command" line
+/// for each dispatched command:
+/// ```text
+/// seed 1234
+/// size 64 64
+/// rule B3/S23
+/// boundary wrap
+/// automaton life
+/// topology square
+/// 0 start
+/// 12 toggle 3 4
+/// 40 stop
+/// ```
+/// Recordings saved without the size and settings start from a universe of the standard size and settings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recording {
+    seed: u64,
+    /// The (width, height) of the universe.
+    size: (u32, u32),
+    rule: Rule,
+    boundary: Boundary,
+    automaton: Automaton,
+    topology: Topology,
+    commands: Vec<(u64, Command)>,
+}
+
+impl Recording {
+    /// Start a recording of a universe of the same size and settings as the given universe, randomized with
+    /// the given seed.
+    pub fn new(seed: u64, universe: &Universe) -> Recording {
+        Recording {
+            seed,
+            size: (universe.width(), universe.height()),
+            rule: universe.rule(),
+            boundary: universe.boundary(),
+            automaton: universe.automaton(),
+            topology: universe.topology(),
+            commands: Vec::new(),
+        }
+    }
+
+    /// Record a command dispatched when the universe was at the given generation.
+    pub fn push(&mut self, generation: u64, command: Command) {
+        self.commands.push((generation, command));
+    }
+
+    /// End the recording at the given generation, so a replay runs until then.
+    pub fn finish(mut self, generation: u64) -> Recording {
+        self.push(generation, Command::Stop);
+        self
+    }
+
+    /// Return the universe the recording starts from.
+    pub fn initial_universe(&self) -> Universe {
+        let (width, height) = self.size;
+        let mut universe = Universe::with_size(width, height);
+        universe.set_rule(self.rule);
+        universe.set_boundary(self.boundary);
+        universe.set_topology(self.topology);
+        universe.set_automaton(self.automaton);
+        universe.random_with_seed(self.seed);
+        universe
+    }
+}
+
+impl fmt::Display for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "seed {}", self.seed)?;
+        writeln!(f, "size {} {}", self.size.0, self.size.1)?;
+        writeln!(f, "rule {}", self.rule)?;
+        writeln!(f, "boundary {}", self.boundary)?;
+        writeln!(f, "automaton {}", self.automaton)?;
+        writeln!(f, "topology {}", self.topology)?;
+        for (generation, command) in self.commands.iter() {
+            writeln!(f, "{generation} {command}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Recording {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .peekable();
+
+        let seed = lines
+            .next()
+            .and_then(|line| line.strip_prefix("seed "))
+            .ok_or("a replay must start with a seed line")?;
+        let seed = seed.parse().map_err(|_| format!("bad seed: {seed}"))?;
+        let mut recording = Recording::new(seed, &Universe::new());
+
+        // The size and settings come before the commands, which start with their generation.
+        while let Some(line) = lines.next_if(|line| !line.starts_with(|c: char| c.is_ascii_digit()))
+        {
+            let (name, value) = line.split_once(' ').ok_or(format!("bad line: {line}"))?;
+            match name {
+                "size" => {
+                    let mut numbers = value
+                        .split_whitespace()
+                        .map(|number| number.parse().map_err(|_| format!("bad size: {line}")));
+                    let mut number = || numbers.next().unwrap_or(Err(format!("bad size: {line}")));
+                    recording.size = checked_size(number()?, number()?)?;
+                }
+                "rule" => recording.rule = value.parse()?,
+                "boundary" => recording.boundary = value.parse()?,
+                "automaton" => recording.automaton = value.parse()?,
+                "topology" => recording.topology = value.parse()?,
+                _ => return Err(format!("bad line: {line}")),
+            }
+        }
+
+        recording.commands = lines
+            .map(|line| {
+                let (generation, command) =
+                    line.split_once(' ').ok_or(format!("bad line: {line}"))?;
+                let generation = generation
+                    .parse()
+                    .map_err(|_| format!("bad generation: {line}"))?;
+
+                Ok((generation, command.parse()?))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(recording)
+    }
+}
+
+/// Re-executes a recording one generation at a time.
+pub struct Replay {
+    recording: Recording,
+    next: usize,
+}
+
+impl Replay {
+    pub fn new(recording: Recording) -> Replay {
+        Replay { recording, next: 0 }
+    }
+
+    /// Advance the universe one generation, first applying the commands recorded at the current generation.
+    ///
+    /// Returns false once every recorded command has been applied.
+    pub fn advance(&mut self, universe: &mut Universe) -> bool {
+        while let Some((generation, command)) = self.recording.commands.get(self.next) {
+            if *generation > universe.generation() {
+                universe.tick();
+                return true;
+            }
+
+            self.next += 1;
+            command.apply(universe);
+            if *command == Command::Step {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Buttons to record the session, and to replay a saved recording.
+///
+/// Once a recording has been saved or loaded, the statistics of each generation of its run can be exported.
+#[component]
+pub fn ReplayControls(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let session = use_shared_state::<Session>(cx).unwrap();
+    let error = use_state(cx, || None::<String>);
+    // The recording last saved or loaded.
+    let last_recording = use_state(cx, || None::<Recording>);
+
+    let buttons = match *session.read() {
+        Session::Live => rsx! {
+            button { onclick: move |_| controls.start_recording(), "Record" }
+            label { "Replay: ",
+                input { r#type: "file", accept: ".txt",
+                    onchange: move |event| {
+                        to_owned![controls, error, last_recording];
+                        let files = event.files.clone();
+                        cx.spawn(async move {
+                            match read_chosen_file(files).await.map(|text| text.parse::<Recording>()) {
+                                Some(Ok(recording)) => {
+                                    error.set(None);
+                                    last_recording.set(Some(recording.clone()));
+                                    controls.start_replay(recording);
+                                }
+                                Some(Err(message)) => error.set(Some(message)),
+                                None => error.set(Some("Could not read the replay file".to_string())),
+                            }
+                        });
+                    }
+                }
+            }
+        },
+        Session::Recording(_) => rsx! {
+            button { onclick: move |_| {
+                    if let Some(recording) = controls.stop_recording() {
+                        save_text_file(REPLAY_FILE_NAME, &recording.to_string());
+                        last_recording.set(Some(recording));
+                    }
+                },
+                "Save recording"
+            }
+        },
+        Session::Replaying(_) => rsx! {
+            button { onclick: move |_| controls.stop_replay(), "Stop replay" }
+        },
+    };
+
+    let statistics = last_recording.get().is_some().then(|| {
+        rsx! {
+            div { display: "flex", justify_content: "center", gap: "4px",
+                span { "Statistics of the recorded run:" }
+                button {
+                    onclick: move |_| {
+                        if let Some(recording) = last_recording.get() {
+                            save_text_file(CSV_FILE_NAME, &to_csv(&run_statistics(recording)));
+                        }
+                    },
+                    "CSV"
+                }
+                button {
+                    onclick: move |_| {
+                        if let Some(recording) = last_recording.get() {
+                            save_text_file(JSON_FILE_NAME, &to_json(&run_statistics(recording)));
+                        }
+                    },
+                    "JSON"
+                }
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", gap: "4px",
+            buttons,
+            if let Some(message) = error.get() {
+                rsx! { span { color: "red", "{message}" } }
+            }
+        }
+        statistics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::brush::Brush;
+
+    /// One of each command, with arguments that aren't their defaults.
+    fn every_command() -> Vec<Command> {
+        vec![
+            Command::Start,
+            Command::Stop,
+            Command::Step,
+            Command::Random { density: 60 },
+            Command::Random { density: 35 },
+            Command::Seed {
+                seed: u64::MAX,
+                density: 60,
+            },
+            Command::Seed {
+                seed: 7,
+                density: 100,
+            },
+            Command::Clear,
+            Command::Toggle { row: 3, col: 4 },
+            Command::Paint {
+                row: 5,
+                col: 6,
+                state: 2,
+            },
+            Command::Brush {
+                row: 7,
+                col: 8,
+                brush: Brush::Circle,
+                state: 0,
+            },
+            Command::ToggleAnt { row: 9, col: 10 },
+            Command::Load(vec![(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]),
+            Command::Load(Vec::new()),
+            Command::SetTicksPerFrame(16),
+            Command::SetRule("B36/S23".parse().unwrap()),
+            Command::SetBoundary(Boundary::Dead),
+            Command::SetAutomaton(Automaton::Wireworld),
+            Command::SetTopology(Topology::Hex),
+            Command::Resize {
+                width: 200,
+                height: 100,
+            },
+        ]
+    }
+
+    #[test]
+    fn commands_read_back_as_written() {
+        for command in every_command() {
+            assert_eq!(
+                command.to_string().parse(),
+                Ok(command.clone()),
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn recordings_read_back_as_written() {
+        let mut universe = Universe::with_size(48, 32);
+        universe.set_rule("B36/S23".parse().unwrap());
+        universe.set_boundary(Boundary::Infinite);
+        universe.set_topology(Topology::Hex);
+        universe.set_automaton(Automaton::BriansBrain);
+
+        let mut recording = Recording::new(1234, &universe);
+        for (generation, command) in every_command().into_iter().enumerate() {
+            recording.push(generation as u64 * 3, command);
+        }
+        let recording = recording.finish(100);
+
+        assert_eq!(recording.to_string().parse(), Ok(recording));
+    }
+
+    #[test]
+    fn recordings_without_settings_start_from_the_standard_universe() {
+        let recording: Recording = "seed 1234\n0 start\n12 toggle 3 4\n40 stop\n"
+            .parse()
+            .unwrap();
+
+        assert_eq!(recording, {
+            let mut recording = Recording::new(1234, &Universe::new());
+            recording.push(0, Command::Start);
+            recording.push(12, Command::Toggle { row: 3, col: 4 });
+            recording.finish(40)
+        });
+        let universe = recording.initial_universe();
+        let mut standard = Universe::new();
+        standard.random_with_seed(1234);
+        assert!(universe == standard);
+    }
+
+    #[test]
+    fn bad_recordings_are_refused() {
+        for text in [
+            "",
+            "0 start",
+            "seed x",
+            "seed 1\nsize 0 10",
+            "seed 1\nsize 10",
+            "seed 1\ncolor red",
+            "seed 1\n0 fly",
+            "seed 1\nx start",
+            "seed 1\n0 toggle 3",
+            "seed 1\n0 toggle 3 4294967296",
+            "seed 1\n0 paint 3 4 256",
+            "seed 1\n0 load 3;4",
+        ] {
+            assert!(text.parse::<Recording>().is_err(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn replays_reproduce_the_recorded_session() {
+        let mut live = Universe::with_size(16, 16);
+        live.random_with_seed(99);
+        let mut recording = Recording::new(99, &live);
+
+        for generation in 0..20 {
+            if generation % 5 == 0 {
+                let command = Command::Toggle {
+                    row: generation,
+                    col: generation / 2,
+                };
+                command.apply(&mut live);
+                recording.push(live.generation(), command);
+            }
+            live.tick();
+        }
+        let recording: Recording = recording
+            .finish(live.generation())
+            .to_string()
+            .parse()
+            .unwrap();
+
+        let mut replayed = recording.initial_universe();
+        let mut replay = Replay::new(recording);
+        while replay.advance(&mut replayed) {}
+        assert_eq!(replayed.generation(), live.generation());
+        assert!(replayed == live);
+    }
+}
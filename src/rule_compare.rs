@@ -0,0 +1,193 @@
+//! A/B rule comparison: two copies of the universe, each run with its own rule in lockstep, drawn side by
+//! side with a plot of how far they have diverged.  Shows how sensitive the dynamics are to the rule.
+
+use dioxus::prelude::*;
+
+use crate::{
+    game_of_life::{cells_path, cells_size, rule::Rule, universe::Universe},
+    memory::{BudgetedBuffer, MemoryBudget},
+    palette::Palette,
+    platform::sleep_ms,
+};
+
+/// The rule the second copy starts with: HighLife, which differs from Conway's life by a single birth count.
+const DEFAULT_RULE_B: &str = "B36/S23";
+/// Milliseconds between steps while the comparison runs.
+const STEP_MS: u32 = 100;
+/// How much smaller the boards are drawn than the main grid.
+const BOARD_SCALE: f64 = 0.5;
+const PLOT_WIDTH: f64 = 320.0;
+const PLOT_HEIGHT: f64 = 64.0;
+
+/// The two copies of the universe, and their divergence at each step.
+struct Comparison {
+    a: Universe,
+    b: Universe,
+    divergence: BudgetedBuffer<f64>,
+}
+
+impl Comparison {
+    fn new(
+        universe: &Universe,
+        rule_a: Rule,
+        rule_b: Rule,
+        budget: &mut MemoryBudget,
+    ) -> Comparison {
+        let (mut a, mut b) = (universe.clone(), universe.clone());
+        a.set_rule(rule_a);
+        b.set_rule(rule_b);
+
+        let mut divergence = BudgetedBuffer::new("rule comparison");
+        divergence.clear(budget);
+        divergence.push(0.0, budget);
+        Comparison { a, b, divergence }
+    }
+
+    /// Advance both copies one generation.
+    fn step(&mut self, budget: &mut MemoryBudget) {
+        self.a.tick();
+        self.b.tick();
+        self.divergence.push(divergence(&self.a, &self.b), budget);
+    }
+}
+
+/// Return the fraction of cells that differ between the universes, which are the same size.
+fn divergence(a: &Universe, b: &Universe) -> f64 {
+    let cells = a.cells().len().max(1);
+    let differing = a.cells().zip(b.cells()).filter(|(a, b)| a != b).count();
+    differing as f64 / cells as f64
+}
+
+/// A board of one copy of the universe, labelled with its rule and population.
+#[component]
+fn ComparedBoard(cx: Scope, universe: Universe) -> Element {
+    let palette = *use_shared_state::<Palette>(cx).unwrap().read();
+    let (width, height) = (universe.width(), universe.height());
+    let (board_width, board_height) = cells_size(universe);
+    let cells = cells_path(&universe.get_living_cells());
+    let rule = universe.rule();
+    let population = universe.population();
+
+    render! {
+        div { display: "flex", flex_direction: "column", align_items: "center",
+            svg {
+                width: board_width * BOARD_SCALE,
+                height: board_height * BOARD_SCALE,
+                view_box: "0 0 {width} {height}",
+                rect { width: width as f64, height: height as f64, fill: palette.background }
+                path { d: "{cells}", fill: palette.alive }
+            }
+            span { font_family: "monospace", "{rule}: {population} alive" }
+        }
+    }
+}
+
+/// Rules for the two copies, buttons to start, step and run the comparison, the two boards, and the plot
+/// of their divergence.
+#[component]
+pub fn RuleComparison(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let budget = use_shared_state::<MemoryBudget>(cx).unwrap();
+    let rule_a = use_state(cx, || universe.read().rule().to_string());
+    let rule_b = use_state(cx, || DEFAULT_RULE_B.to_string());
+    let error = use_state(cx, || None::<String>);
+    let comparison = use_ref(cx, || None::<Comparison>);
+    let running = use_state(cx, || false);
+
+    use_future(cx, (running.get(),), |(running,)| {
+        to_owned![comparison, budget];
+        async move {
+            // The future is replaced when running changes, so a running loop runs until then.
+            if !running {
+                return;
+            }
+            loop {
+                sleep_ms(STEP_MS).await;
+                comparison.with_mut(|comparison| {
+                    if let Some(comparison) = comparison {
+                        comparison.step(&mut budget.write());
+                    }
+                });
+            }
+        }
+    });
+
+    let start = move |_| match (rule_a.get().parse::<Rule>(), rule_b.get().parse::<Rule>()) {
+        (Ok(a), Ok(b)) => {
+            error.set(None);
+            *comparison.write() =
+                Some(Comparison::new(&universe.read(), a, b, &mut budget.write()));
+        }
+        (Err(message), _) | (_, Err(message)) => error.set(Some(message)),
+    };
+
+    let shown = comparison.read().is_some();
+    let results = comparison.read().as_ref().map(|comparison| {
+        let count = comparison.divergence.len();
+        let points: Vec<String> = comparison
+            .divergence
+            .iter()
+            .enumerate()
+            .map(|(step, divergence)| {
+                let x = step as f64 * PLOT_WIDTH / (count.max(2) - 1) as f64;
+                let y = PLOT_HEIGHT * (1.0 - divergence);
+                format!("{x:.1},{y:.1}")
+            })
+            .collect();
+        let points = points.join(" ");
+        let (a, b) = (comparison.a.clone(), comparison.b.clone());
+        let generation = a.generation();
+        let latest = comparison.divergence.iter().last().copied().unwrap_or(0.0) * 100.0;
+
+        rsx! {
+            div { display: "flex", justify_content: "center", gap: "16px",
+                ComparedBoard { universe: a }
+                ComparedBoard { universe: b }
+            }
+            div { display: "flex", flex_direction: "column", align_items: "center",
+                svg { width: PLOT_WIDTH, height: PLOT_HEIGHT, style: "border: 1px solid gray",
+                    polyline { points: "{points}", fill: "none", stroke: "crimson" }
+                }
+                span { font_family: "monospace", "{latest:.1}% of cells differ at generation {generation}" }
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "4px",
+            span { "Compare rules" }
+            input { r#type: "text", size: 10, value: "{rule_a}", oninput: move |event| rule_a.set(event.value.clone()) }
+            span { "and" }
+            input { r#type: "text", size: 10, value: "{rule_b}", oninput: move |event| rule_b.set(event.value.clone()) }
+            button { onclick: start, "Compare" }
+            button {
+                disabled: !shown,
+                onclick: move |_| {
+                    comparison.with_mut(|comparison| {
+                        if let Some(comparison) = comparison {
+                            comparison.step(&mut budget.write());
+                        }
+                    });
+                },
+                "Step"
+            }
+            button {
+                disabled: !shown,
+                onclick: move |_| running.set(!*running.get()),
+                if *running.get() { "Pause" } else { "Run" }
+            }
+            button {
+                disabled: !shown,
+                onclick: move |_| {
+                    running.set(false);
+                    if let Some(mut comparison) = comparison.write().take() {
+                        comparison.divergence.clear(&mut budget.write());
+                    }
+                },
+                "Close"
+            }
+            error.get().as_ref().map(|message| rsx! { span { color: "red", "{message}" } })
+        }
+        results
+    }
+}
@@ -0,0 +1,224 @@
+//! Choosing how the universe advances: by well known life-like rules or any rule in B/S notation, by Larger
+//! than Life rules, or as an automaton with more states such as Brian's Brain, on square or hexagonal cells,
+//! and with edges that wrap around or are dead.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::{
+        automaton::Automaton,
+        ltl::LtlRule,
+        rule::Rule,
+        universe::{Boundary, Topology, Universe},
+    },
+};
+
+/// Well known life-like rules, by name.
+const RULE_PRESETS: [(&str, &str); 7] = [
+    ("Conway's Life", "B3/S23"),
+    ("HighLife", "B36/S23"),
+    ("Seeds", "B2/S"),
+    ("Day & Night", "B3678/S34678"),
+    ("Life without Death", "B3/S012345678"),
+    ("Maze", "B3/S12345"),
+    ("2x2", "B36/S125"),
+];
+
+/// Well known Larger than Life rules, by name.
+const LTL_PRESETS: [(&str, &str); 4] = [
+    ("Bosco's Rule", "R5,C0,M1,S34..58,B34..45,NM"),
+    ("Majority", "R4,C0,M1,S41..81,B41..81,NM"),
+    ("Waffle", "R7,C0,M1,S100..200,B75..170,NM"),
+    ("Globe", "R8,C0,M0,S163..223,B74..252,NM"),
+];
+
+/// Return how a boundary is described in the choice of edges.
+fn boundary_label(boundary: Boundary) -> &'static str {
+    match boundary {
+        Boundary::Wrap => "wrap around",
+        Boundary::Dead => "are dead",
+        Boundary::Infinite => "grow forever",
+    }
+}
+
+/// Return how a topology is described in the choice of cells.
+fn topology_label(topology: Topology) -> &'static str {
+    match topology {
+        Topology::Square => "square",
+        Topology::Hex => "hexagonal",
+    }
+}
+
+/// A choice of the well known rules, a field for any other rule, a choice of automaton, a choice of square or
+/// hexagonal cells, and a choice of what lies past the edges.
+///
+/// Some rules, such as B2/S34, are only interesting on hexagonal cells, whose neighbors number six.
+///
+/// Rules only apply to Life and QuadLife, so they are hidden while another automaton is chosen.  Larger than Life
+/// has a choice of its own rules instead.
+#[component]
+pub fn RulePicker(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let custom = use_state(cx, String::new);
+    let error = use_state(cx, || None::<String>);
+
+    let current = universe.read().rule();
+    let current_boundary = universe.read().boundary();
+    let current_automaton = universe.read().automaton();
+    let current_topology = universe.read().topology();
+    let preset = RULE_PRESETS
+        .into_iter()
+        .find(|(_, rule)| rule.parse::<Rule>().ok() == Some(current));
+    let presets = RULE_PRESETS.into_iter().map(|(name, rule)| {
+        rsx! {
+            option { key: "{rule}", value: "{rule}", selected: preset.is_some_and(|(_, preset)| preset == rule), "{name} ({rule})" }
+        }
+    });
+    let automata = Automaton::ALL.into_iter().map(|automaton| {
+        rsx! {
+            option { key: "{automaton}", value: "{automaton}", selected: automaton.label() == current_automaton.label(), "{automaton.label()}" }
+        }
+    });
+    let topologies = Topology::ALL.into_iter().map(|topology| {
+        rsx! {
+            option { key: "{topology}", value: "{topology}", selected: topology == current_topology, "{topology_label(topology)}" }
+        }
+    });
+    let boundaries = Boundary::ALL.into_iter().map(|boundary| {
+        rsx! {
+            option { key: "{boundary}", value: "{boundary}", selected: boundary == current_boundary, "{boundary_label(boundary)}" }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            label {
+                "Automaton "
+                select {
+                    onchange: move |event| {
+                        if let Ok(automaton) = event.value.parse() {
+                            controls.dispatch(Command::SetAutomaton(automaton));
+                        }
+                    },
+                    automata
+                }
+            }
+            if current_automaton.uses_rule() {
+                rsx! {
+                    label {
+                        "Rule "
+                        select {
+                            onchange: move |event| {
+                                if let Ok(rule) = event.value.parse() {
+                                    controls.dispatch(Command::SetRule(rule));
+                                }
+                            },
+                            if preset.is_none() {
+                                rsx! { option { value: "", selected: true, "{current}" } }
+                            }
+                            presets
+                        }
+                    }
+                    input {
+                        r#type: "text",
+                        size: 12,
+                        placeholder: "B3/S23",
+                        value: "{custom}",
+                        oninput: move |event| custom.set(event.value.clone()),
+                    }
+                    button {
+                        onclick: move |_| match custom.get().parse::<Rule>() {
+                            Ok(rule) => {
+                                error.set(None);
+                                controls.dispatch(Command::SetRule(rule));
+                            }
+                            Err(message) => error.set(Some(message)),
+                        },
+                        "Use rule"
+                    }
+                    error.get().as_ref().map(|message| rsx! { span { color: "red", "{message}" } })
+                }
+            }
+            if let Automaton::LargerThanLife(rule) = current_automaton {
+                rsx! { LtlRulePicker { current: rule } }
+            }
+            label {
+                "Cells "
+                select {
+                    onchange: move |event| {
+                        if let Ok(topology) = event.value.parse() {
+                            controls.dispatch(Command::SetTopology(topology));
+                        }
+                    },
+                    topologies
+                }
+            }
+            label {
+                title: "Dead edges lose patterns that leave the universe, as on an infinite plane; growing edges let them fly on forever",
+                "Edges "
+                select {
+                    onchange: move |event| {
+                        if let Ok(boundary) = event.value.parse() {
+                            controls.dispatch(Command::SetBoundary(boundary));
+                        }
+                    },
+                    boundaries
+                }
+            }
+        }
+    }
+}
+
+/// A choice of the well known Larger than Life rules, and a field for any other rule in Golly's notation.
+#[component]
+fn LtlRulePicker(cx: Scope, current: LtlRule) -> Element {
+    let controls = use_controls(cx);
+    let custom = use_state(cx, String::new);
+    let error = use_state(cx, || None::<String>);
+
+    let preset = LTL_PRESETS
+        .into_iter()
+        .find(|(_, rule)| rule.parse::<LtlRule>().ok() == Some(*current));
+    let presets = LTL_PRESETS.into_iter().map(|(name, rule)| {
+        rsx! {
+            option { key: "{rule}", value: "{rule}", selected: preset.is_some_and(|(_, preset)| preset == rule), "{name}" }
+        }
+    });
+
+    render! {
+        label {
+            "Rule "
+            select {
+                onchange: move |event| {
+                    if let Ok(rule) = event.value.parse() {
+                        controls.dispatch(Command::SetAutomaton(Automaton::LargerThanLife(rule)));
+                    }
+                },
+                if preset.is_none() {
+                    rsx! { option { value: "", selected: true, "{current}" } }
+                }
+                presets
+            }
+        }
+        input {
+            r#type: "text",
+            size: 28,
+            placeholder: "R5,C0,M1,S34..58,B34..45,NM",
+            value: "{custom}",
+            oninput: move |event| custom.set(event.value.clone()),
+        }
+        button {
+            onclick: move |_| match custom.get().parse::<LtlRule>() {
+                Ok(rule) => {
+                    error.set(None);
+                    controls.dispatch(Command::SetAutomaton(Automaton::LargerThanLife(rule)));
+                }
+                Err(message) => error.set(Some(message)),
+            },
+            "Use rule"
+        }
+        error.get().as_ref().map(|message| rsx! { span { color: "red", "{message}" } })
+    }
+}
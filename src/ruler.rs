@@ -0,0 +1,86 @@
+//! Rulers along the top and left of the grid, numbering the cells every big grid block.
+//!
+//! The rulers are drawn at the scale and zoom of the grid, so the numbers stay lined up with the big grid
+//! lines whatever the size of the cells and however the grid is zoomed.  Coordinates shown elsewhere, such as in exported patterns, count
+//! from 0 the same way.
+
+use dioxus::prelude::*;
+
+use crate::{
+    game_of_life::{universe::Universe, BIG_GRID_MULTIPLIER, CELL_SIZE},
+    theme::Appearance,
+    viewport::Viewport,
+};
+
+/// The height of the top ruler and the width of the left ruler, in pixels.
+pub const RULER_SIZE: f64 = 28.0;
+const FONT_SIZE: f64 = 10.0;
+const RULER_COLOR: &str = "gray";
+
+/// Which edge of the grid a ruler runs along.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    /// Along the top, numbering the columns.
+    Top,
+    /// Down the left side, numbering the rows.
+    Left,
+}
+
+/// A ruler along one edge of the grid, with a tick and the index of the cell at each big grid line.
+#[component]
+pub fn Ruler(cx: Scope, edge: Edge) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let appearance = *use_shared_state::<Appearance>(cx).unwrap().read();
+    let viewport = *use_shared_state::<Viewport>(cx).unwrap().read();
+
+    let cells = match edge {
+        Edge::Top => universe.read().width(),
+        Edge::Left => universe.read().height(),
+    };
+    // The first cell shown on the grid.
+    let first = match edge {
+        Edge::Top => viewport.left,
+        Edge::Left => viewport.top,
+    };
+    let scale = appearance.scale();
+    let offset = appearance.theme.big_grid_stroke / 2.0;
+    let length = ((CELL_SIZE * cells as i64) as f64 + appearance.theme.big_grid_stroke) * scale;
+
+    let marks = (0..cells).step_by(BIG_GRID_MULTIPLIER as usize).filter_map(|index| {
+        // Where the big grid line before the cell is drawn, in pixels.
+        let at = ((index as f64 - first) * CELL_SIZE as f64 + offset) * scale * viewport.zoom;
+        if !(0.0..length).contains(&at) {
+            return None;
+        }
+        Some(match edge {
+            Edge::Top => rsx! {
+                g { key: "{index}",
+                    line { x1: at, y1: RULER_SIZE - 6.0, x2: at, y2: RULER_SIZE, stroke: RULER_COLOR }
+                    text { x: at + 2.0, y: RULER_SIZE - 4.0, font_size: FONT_SIZE, fill: RULER_COLOR, "{index}" }
+                }
+            },
+            Edge::Left => rsx! {
+                g { key: "{index}",
+                    line { x1: RULER_SIZE - 6.0, y1: at, x2: RULER_SIZE, y2: at, stroke: RULER_COLOR }
+                    text {
+                        x: RULER_SIZE - 4.0,
+                        y: at + FONT_SIZE + 1.0,
+                        font_size: FONT_SIZE,
+                        fill: RULER_COLOR,
+                        text_anchor: "end",
+                        "{index}"
+                    }
+                }
+            },
+        })
+    });
+
+    let (width, height) = match edge {
+        Edge::Top => (length, RULER_SIZE),
+        Edge::Left => (RULER_SIZE, length),
+    };
+
+    render! {
+        svg { width: width, height: height, font_family: "monospace", marks }
+    }
+}
@@ -0,0 +1,213 @@
+//! Screenshots of the universe, taken automatically when chosen events happen.
+//!
+//! Screenshots are PNG images with one CELL_SIZE square per cell, saved with the generation in the file name.
+
+use std::{
+    collections::{BTreeSet, VecDeque},
+    rc::Rc,
+};
+
+use dioxus::prelude::*;
+
+use crate::{
+    file_io::save_file,
+    game_of_life::{census::Recognizer, universe::Universe, CELL_SIZE},
+};
+
+/// Board hashes remembered to notice the universe settling, enough to see period 2 oscillators.
+const RECENT_GENERATIONS: usize = 2;
+
+/// An event that can trigger a screenshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Trigger {
+    /// The population reached a new high, saved once it starts to fall.
+    PopulationRecord,
+    /// The universe stopped changing, other than oscillating with period 2 or less.
+    Stabilization,
+    /// The last living cell died.
+    Extinction,
+    /// A known object (such as a glider) was recognized for the first time.
+    Recognized,
+}
+
+impl Trigger {
+    const ALL: [Trigger; 4] = [
+        Trigger::PopulationRecord,
+        Trigger::Stabilization,
+        Trigger::Extinction,
+        Trigger::Recognized,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Trigger::PopulationRecord => "Population record",
+            Trigger::Stabilization => "Stabilization",
+            Trigger::Extinction => "Extinction",
+            Trigger::Recognized => "New object recognized",
+        }
+    }
+}
+
+/// Follows the universe from generation to generation, noticing the events that trigger screenshots.
+#[derive(Default)]
+struct EventWatch {
+    generation: u64,
+    population: usize,
+    record: usize,
+    /// The universe at the population record, kept until the population falls from it.
+    record_universe: Option<Universe>,
+    recent_hashes: VecDeque<u64>,
+    stable: bool,
+    /// The names of the objects recognized so far.
+    recognized: BTreeSet<&'static str>,
+}
+
+impl EventWatch {
+    /// Observe the universe at a new generation, returning a name and universe to screenshot for every
+    /// enabled event that happened.
+    ///
+    /// The watch starts over when the generation goes back, such as when the universe is cleared.
+    fn observe(
+        &mut self,
+        universe: &Universe,
+        enabled: &BTreeSet<Trigger>,
+        recognizer: &Recognizer,
+    ) -> Vec<(String, Universe)> {
+        if universe.generation() < self.generation {
+            *self = EventWatch::default();
+        }
+
+        let mut screenshots = Vec::new();
+        let generation = universe.generation();
+        let population = universe.population();
+        let hash = universe.zobrist_hash();
+
+        // The starting population isn't a record, since it was set rather than reached.
+        if generation == 0 || population > self.record {
+            self.record = population;
+            self.record_universe = (generation > 0).then(|| universe.clone());
+        } else if population < self.record {
+            if let Some(record_universe) = self.record_universe.take() {
+                screenshots.push((
+                    Trigger::PopulationRecord,
+                    "population_record".to_string(),
+                    record_universe,
+                ));
+            }
+        }
+
+        let stable = self.recent_hashes.contains(&hash);
+        if stable && !self.stable && population > 0 {
+            screenshots.push((
+                Trigger::Stabilization,
+                "stable".to_string(),
+                universe.clone(),
+            ));
+        }
+        self.stable = stable;
+        self.recent_hashes.push_back(hash);
+        if self.recent_hashes.len() > RECENT_GENERATIONS {
+            self.recent_hashes.pop_front();
+        }
+
+        if population == 0 && self.population > 0 {
+            screenshots.push((Trigger::Extinction, "extinct".to_string(), universe.clone()));
+        }
+
+        // Recognizing objects is slow, so it is only done when needed.
+        if enabled.contains(&Trigger::Recognized) {
+            for name in recognizer.census(universe).objects.into_keys() {
+                if self.recognized.insert(name) && generation > 0 {
+                    let name = name.to_lowercase().replace(' ', "_");
+                    screenshots.push((Trigger::Recognized, name, universe.clone()));
+                }
+            }
+        }
+
+        self.generation = generation;
+        self.population = population;
+        screenshots
+            .into_iter()
+            .filter(|(trigger, _, _)| enabled.contains(trigger))
+            .map(|(_, name, universe)| (name, universe))
+            .collect()
+    }
+}
+
+/// Return a PNG image of the universe, with living cells in black on white.
+pub fn universe_png(universe: &Universe) -> Vec<u8> {
+    let scale = CELL_SIZE as u32;
+    let (width, height) = (universe.width() * scale, universe.height() * scale);
+    let mut pixels = vec![u8::MAX; (width * height) as usize];
+
+    for (x, y) in universe.get_living_cells() {
+        let (left, top) = (x as u32 * scale, y as u32 * scale);
+        for row in top..top + scale {
+            let start = (row * width + left) as usize;
+            pixels[start..start + scale as usize].fill(0);
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, width, height);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .expect("universe should encode as a png");
+    png
+}
+
+/// Checkboxes to choose the events that trigger a screenshot.
+#[component]
+pub fn ScreenshotTriggers(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let enabled = use_ref(cx, BTreeSet::<Trigger>::new);
+    let watch = use_ref(cx, EventWatch::default);
+    let recognizer = cx.use_hook(|| Rc::new(Recognizer::new()));
+
+    let generation = universe.read().generation();
+    use_effect(cx, (&generation,), |_| {
+        to_owned![universe, enabled, watch, recognizer];
+        async move {
+            let screenshots = watch
+                .with_mut(|watch| watch.observe(&universe.read(), &enabled.read(), &recognizer));
+
+            for (name, universe) in screenshots {
+                let file_name = format!("game_of_life_{name}_gen{}.png", universe.generation());
+                save_file(&file_name, &universe_png(&universe));
+            }
+        }
+    });
+
+    let checkboxes = Trigger::ALL.into_iter().map(|trigger| {
+        let checked = enabled.read().contains(&trigger);
+
+        rsx! {
+            label { key: "{trigger.label()}",
+                input {
+                    r#type: "checkbox",
+                    checked: checked,
+                    onchange: move |event| {
+                        enabled.with_mut(|enabled| {
+                            if event.value == "true" {
+                                enabled.insert(trigger);
+                            } else {
+                                enabled.remove(&trigger);
+                            }
+                        });
+                    },
+                }
+                "{trigger.label()}"
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", gap: "8px",
+            span { "Screenshot on:" }
+            checkboxes
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Randomizing the universe from a chosen seed, so an interesting soup can be shared and made again, and
+//! choosing how many of its cells are alive.
+//!
+//! Seeded randomization is the same on the web and on desktop, so a seed and density give the same soup
+//! everywhere.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::universe::{Universe, DEFAULT_DENSITY},
+    storage,
+};
+
+const DENSITY_KEY: &str = "density.txt";
+
+/// The percent of cells alive, on average, when the universe is randomized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Density(pub u8);
+
+impl Density {
+    /// Return the density chosen in an earlier session, or the default density.
+    pub fn stored() -> Density {
+        let density = storage::load(DENSITY_KEY)
+            .and_then(|density| density.trim().parse().ok())
+            .filter(|density| *density <= 100)
+            .unwrap_or(DEFAULT_DENSITY);
+        Density(density)
+    }
+
+    /// Remember the density for later sessions.
+    pub fn store(&self) {
+        storage::store(DENSITY_KEY, &self.0.to_string());
+    }
+}
+
+/// A slider choosing the density of randomized universes.
+///
+/// Sparse soups, with 5 to 15 percent of cells alive, behave very differently from dense ones.
+#[component]
+pub fn DensitySlider(cx: Scope) -> Element {
+    let density = use_shared_state::<Density>(cx).unwrap();
+    let Density(percent) = *density.read();
+
+    render! {
+        label { display: "flex", align_items: "center", gap: "4px",
+            "Density "
+            input {
+                r#type: "range",
+                min: 0,
+                max: 100,
+                value: percent as i64,
+                oninput: move |event| {
+                    if let Ok(percent) = event.value.parse() {
+                        *density.write() = Density(percent);
+                        density.read().store();
+                    }
+                },
+            }
+            span { font_family: "monospace", "{percent}%" }
+        }
+    }
+}
+
+/// A field for a seed to randomize the universe from, and the seed the universe was last randomized from.
+#[component]
+pub fn SeedInput(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    // Read the universe so the last seed is shown as soon as the universe is randomized.
+    let _universe = use_shared_state::<Universe>(cx).unwrap();
+    let density = use_shared_state::<Density>(cx).unwrap();
+    let seed = use_state(cx, String::new);
+    let error = use_state(cx, || None::<String>);
+
+    let last_seed = controls
+        .last_seed()
+        .map(|(seed, density)| format!("last seed {seed} at {density}%"))
+        .unwrap_or_default();
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            input {
+                r#type: "text",
+                size: 20,
+                placeholder: "seed",
+                value: "{seed}",
+                oninput: move |event| seed.set(event.value.clone()),
+            }
+            button {
+                onclick: move |_| match seed.get().trim().parse::<u64>() {
+                    Ok(seed) => {
+                        error.set(None);
+                        let Density(density) = *density.read();
+                        controls.dispatch(Command::Seed { seed, density });
+                    }
+                    Err(_) => error.set(Some(format!(
+                        "{:?} is not a seed: expected a whole number",
+                        seed.get()
+                    ))),
+                },
+                "Randomize from seed"
+            }
+            error.get().as_ref().map(|message| rsx! { span { color: "red", "{message}" } })
+            span { font_family: "monospace", user_select: "all", "{last_seed}" }
+        }
+    }
+}
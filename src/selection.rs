@@ -0,0 +1,567 @@
+//! Tools for working with the grid, and the rectangle of cells selected with them.
+//!
+//! The selection can be copied or cut, and pasted elsewhere: at the top left corner of the selection, or in
+//! the middle of the universe if nothing is selected.  It can also be rotated and flipped in place, keeping
+//! its top left corner, and its cells cleared or randomized.  While there is a selection, how many of its
+//! cells are alive is shown, and kept up to date as the universe changes.
+
+use std::collections::{HashMap, HashSet};
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    command::{use_controls, Command, Controls},
+    game_of_life::{
+        random::random_seed,
+        transform::Transform,
+        universe::{BoundingBox, Universe},
+        CellLayer,
+    },
+    seed::Density,
+};
+
+const SELECTION_COLOR: &str = "dodgerblue";
+const GHOST_OPACITY: f64 = 0.4;
+
+/// The label of the button for each transform.
+pub const TRANSFORM_LABELS: [(Transform, &str); 4] = [
+    (Transform::Rotate90, "Rotate ↻"),
+    (Transform::Rotate180, "Rotate 180°"),
+    (Transform::FlipH, "Flip ↔"),
+    (Transform::FlipV, "Flip ↕"),
+];
+
+/// What clicking on the grid does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tool {
+    /// Toggle the clicked cell.
+    #[default]
+    Toggle,
+    /// Bring to life the cells clicked or dragged over.
+    Draw,
+    /// Kill the cells clicked or dragged over.
+    Erase,
+    /// Drag out a rectangle of cells to select.
+    Select,
+    /// Drag the living cells of the selection to a new place.
+    Move,
+    /// Drag the view of a zoomed in grid around.
+    Pan,
+}
+
+/// The rectangle of cells selected on the grid, if any.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Selection(pub Option<BoundingBox>);
+
+impl Selection {
+    /// Select the rectangle with the given (row, column) cells as opposite corners.
+    pub fn between(corner: (u32, u32), opposite: (u32, u32)) -> Selection {
+        Selection(Some(BoundingBox {
+            top: corner.0.min(opposite.0),
+            left: corner.1.min(opposite.1),
+            bottom: corner.0.max(opposite.0),
+            right: corner.1.max(opposite.1),
+        }))
+    }
+
+    /// Select the cells covered by a region, which is not empty, placed with its top left corner at the
+    /// (row, column) cell, cut off at the edges of the universe.
+    pub fn covering(region: &Universe, (top, left): (u32, u32), universe: &Universe) -> Selection {
+        let bottom = (top + region.height() - 1).min(universe.height() - 1);
+        let right = (left + region.width() - 1).min(universe.width() - 1);
+        Selection::between((top, left), (bottom, right))
+    }
+
+    /// True if the (row, column) cell is within the selection.
+    pub fn contains(&self, (row, col): (u32, u32)) -> bool {
+        self.0.is_some_and(|bounds| {
+            (bounds.top..=bounds.bottom).contains(&row)
+                && (bounds.left..=bounds.right).contains(&col)
+        })
+    }
+
+    /// Return the (rows, columns) offset limited so that the moved selection stays within the universe.
+    pub fn clamp_offset(&self, (rows, cols): (i64, i64), universe: &Universe) -> (i64, i64) {
+        let Some(bounds) = self.0 else {
+            return (0, 0);
+        };
+        let last_row = universe.height() as i64 - 1;
+        let last_col = universe.width() as i64 - 1;
+
+        (
+            rows.clamp(-(bounds.top as i64), last_row - bounds.bottom as i64),
+            cols.clamp(-(bounds.left as i64), last_col - bounds.right as i64),
+        )
+    }
+
+    /// Return the selection moved by a (rows, columns) offset that keeps it within the universe.
+    pub fn moved(&self, (rows, cols): (i64, i64)) -> Selection {
+        let shift = |value: u32, by: i64| (value as i64 + by) as u32;
+
+        Selection(self.0.map(|bounds| BoundingBox {
+            top: shift(bounds.top, rows),
+            left: shift(bounds.left, cols),
+            bottom: shift(bounds.bottom, rows),
+            right: shift(bounds.right, cols),
+        }))
+    }
+
+    /// Return the (row, column) of the living cells within the selection.
+    pub fn living_cells(&self, universe: &Universe) -> Vec<(u32, u32)> {
+        let Some(bounds) = self.0 else {
+            return Vec::new();
+        };
+
+        (bounds.top..=bounds.bottom.min(universe.height() - 1))
+            .flat_map(|row| {
+                (bounds.left..=bounds.right.min(universe.width() - 1)).map(move |col| (row, col))
+            })
+            .filter(|(row, col)| universe.is_alive(*row, *col))
+            .collect()
+    }
+}
+
+/// The living cells of the selection, lifted by the move tool and being dragged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lifted {
+    pub cells: Vec<(u32, u32)>,
+    /// The cell the drag started from.
+    pub grab: (u32, u32),
+    /// The (rows, columns) the cells have been dragged.
+    pub offset: (i64, i64),
+}
+
+/// The cells being moved, if any.  Shown as a ghost of the cells where they would be dropped.
+#[derive(Default)]
+pub struct MoveDrag(pub Option<Lifted>);
+
+/// The cells last copied or cut from the selection, as a universe the size of the selection.
+#[derive(Default)]
+pub struct CopiedRegion(pub Option<Universe>);
+
+/// Return the paints that change the universe into the target universe, which is the same size, setting each
+/// cell whose state differs to its state in the target, so automata with more than two states keep them.
+fn paints_to(universe: &Universe, target: &Universe) -> Vec<Command> {
+    (0..universe.height())
+        .flat_map(|row| (0..universe.width()).map(move |col| (row, col)))
+        .filter_map(|(row, col)| {
+            let state = target.state(row, col);
+            (universe.state(row, col) != state).then_some(Command::Paint { row, col, state })
+        })
+        .collect()
+}
+
+/// Return the paints that replace the cells within the bounds with the cells of the region, which is the
+/// size of the bounds cut off at the edges of the universe.
+fn replace_paints(universe: &Universe, bounds: BoundingBox, region: &Universe) -> Vec<Command> {
+    let mut target = universe.clone();
+    target.blit_region(region, bounds.top, bounds.left);
+    paints_to(universe, &target)
+}
+
+/// Kill the selected cells, or with a density, randomize them with density percent of them alive.
+fn refill_selection(
+    controls: &Controls,
+    universe: &UseSharedState<Universe>,
+    selection: Selection,
+    density: Option<u8>,
+) {
+    let Some(bounds) = selection.0 else {
+        return;
+    };
+    let paints = {
+        let universe = universe.read();
+        let mut region = universe.extract_region(bounds);
+        match density {
+            Some(density) => region.random_with_seed_and_density(random_seed(), density),
+            None => region.clear(),
+        }
+        replace_paints(&universe, bounds, &region)
+    };
+
+    controls.dispatch_all(paints);
+}
+
+/// Copy the selected cells, and kill them if cutting.
+fn copy_selection(
+    controls: &Controls,
+    universe: &UseSharedState<Universe>,
+    selection: Selection,
+    copied: &UseSharedState<CopiedRegion>,
+    cut: bool,
+) {
+    let Some(bounds) = selection.0 else {
+        return;
+    };
+    let (region, paints) = {
+        let universe = universe.read();
+        let region = universe.extract_region(bounds);
+        let cleared = Universe::with_size(region.width(), region.height());
+        let paints = replace_paints(&universe, bounds, &cleared);
+        (region, paints)
+    };
+
+    if cut {
+        controls.dispatch_all(paints);
+    }
+    copied.write().0 = Some(region);
+}
+
+/// Paste the copied cells with their top left corner at the top left corner of the selection, or in the
+/// middle of the universe if nothing is selected, and select them.
+fn paste_region(
+    controls: &Controls,
+    universe: &UseSharedState<Universe>,
+    selection: &UseSharedState<Selection>,
+    copied: &UseSharedState<CopiedRegion>,
+) {
+    let copied = copied.read();
+    let Some(region) = copied
+        .0
+        .as_ref()
+        .filter(|region| region.width() > 0 && region.height() > 0)
+    else {
+        return;
+    };
+    let (pasted, paints) = {
+        let universe = universe.read();
+        let (top, left) = match selection.read().0 {
+            Some(bounds) => (bounds.top, bounds.left),
+            None => (
+                universe.height().saturating_sub(region.height()) / 2,
+                universe.width().saturating_sub(region.width()) / 2,
+            ),
+        };
+        let mut target = universe.clone();
+        target.blit_region(region, top, left);
+        (
+            Selection::covering(region, (top, left), &universe),
+            paints_to(&universe, &target),
+        )
+    };
+
+    controls.dispatch_all(paints);
+    *selection.write() = pasted;
+}
+
+/// Rotate or flip the selected cells in place, keeping the top left corner of the selection, and select
+/// them.
+fn transform_selection(
+    controls: &Controls,
+    universe: &UseSharedState<Universe>,
+    selection: &UseSharedState<Selection>,
+    transform: Transform,
+) {
+    let Some(bounds) = selection.read().0 else {
+        return;
+    };
+    let (transformed, paints) = {
+        let universe = universe.read();
+        let region = universe.extract_region(bounds);
+        let mut target = universe.clone();
+        target.blit_region(
+            &Universe::with_size(region.width(), region.height()),
+            bounds.top,
+            bounds.left,
+        );
+        let region = region.transformed(transform);
+        target.blit_region(&region, bounds.top, bounds.left);
+        (
+            Selection::covering(&region, (bounds.top, bounds.left), &universe),
+            paints_to(&universe, &target),
+        )
+    };
+
+    controls.dispatch_all(paints);
+    *selection.write() = transformed;
+}
+
+/// Return the paints that move the living cells by the (rows, columns) offset, in the states they are in,
+/// clearing where they were.
+///
+/// Moved cells are dropped on top of the cells already at their new place.
+pub fn move_paints(
+    universe: &Universe,
+    cells: &[(u32, u32)],
+    (rows, cols): (i64, i64),
+) -> Vec<Command> {
+    let origin: HashSet<(u32, u32)> = cells.iter().cloned().collect();
+    let destination: HashMap<(u32, u32), u8> = cells
+        .iter()
+        .map(|(row, col)| {
+            let moved = ((*row as i64 + rows) as u32, (*col as i64 + cols) as u32);
+            (moved, universe.state(*row, *col))
+        })
+        .filter(|((row, col), _)| *row < universe.height() && *col < universe.width())
+        .collect();
+
+    let mut changed: Vec<(u32, u32)> = origin.iter().chain(destination.keys()).cloned().collect();
+    changed.sort_unstable();
+    changed.dedup();
+    changed
+        .into_iter()
+        .filter_map(|(row, col)| {
+            let state = match destination.get(&(row, col)) {
+                Some(state) => *state,
+                None if origin.contains(&(row, col)) => 0,
+                None => universe.state(row, col),
+            };
+            (universe.state(row, col) != state).then_some(Command::Paint { row, col, state })
+        })
+        .collect()
+}
+
+/// Move the living cells of the selection by the (rows, columns) offset, and the selection with them.
+///
+/// The offset is limited so that the selection stays within the universe.
+pub fn move_selection(
+    controls: &Controls,
+    universe: &UseSharedState<Universe>,
+    selection: &UseSharedState<Selection>,
+    offset: (i64, i64),
+) {
+    let current = *selection.read();
+    let (offset, paints) = {
+        let universe = universe.read();
+        let offset = current.clamp_offset(offset, &universe);
+        (
+            offset,
+            move_paints(&universe, &current.living_cells(&universe), offset),
+        )
+    };
+
+    controls.dispatch_all(paints);
+    *selection.write() = current.moved(offset);
+}
+
+/// Buttons to choose the tool used on the grid, and to copy, cut, paste, rotate and flip the selection.
+#[component]
+pub fn ToolPicker(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let tool = use_shared_state::<Tool>(cx).unwrap();
+    let selection = use_shared_state::<Selection>(cx).unwrap();
+    let copied = use_shared_state::<CopiedRegion>(cx).unwrap();
+    let density = use_shared_state::<Density>(cx).unwrap();
+    let current = *tool.read();
+    let selected = selection.read().0.is_some();
+
+    let tool_button = |choice: Tool, label: &'static str| {
+        rsx! {
+            button {
+                disabled: current == choice,
+                onclick: move |_| *tool.write() = choice,
+                "{label}"
+            }
+        }
+    };
+
+    let transform_buttons = TRANSFORM_LABELS.into_iter().map(|(transform, label)| {
+        rsx! {
+            button {
+                disabled: !selected,
+                onclick: move |_| transform_selection(controls, universe, selection, transform),
+                "{label}"
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center",
+            tool_button(Tool::Toggle, "Toggle cells"),
+            tool_button(Tool::Draw, "Draw"),
+            tool_button(Tool::Erase, "Erase"),
+            tool_button(Tool::Select, "Select"),
+            tool_button(Tool::Move, "Move"),
+            tool_button(Tool::Pan, "Pan"),
+            button {
+                disabled: !selected,
+                onclick: move |_| *selection.write() = Selection::default(),
+                "Clear selection"
+            }
+            button {
+                disabled: !selected,
+                onclick: move |_| copy_selection(controls, universe, *selection.read(), copied, false),
+                "Copy"
+            }
+            button {
+                disabled: !selected,
+                onclick: move |_| copy_selection(controls, universe, *selection.read(), copied, true),
+                "Cut"
+            }
+            button {
+                disabled: copied.read().0.is_none(),
+                onclick: move |_| paste_region(controls, universe, selection, copied),
+                "Paste"
+            }
+            button {
+                disabled: !selected,
+                title: "Kill the selected cells",
+                onclick: move |_| refill_selection(controls, universe, *selection.read(), None),
+                "Clear cells"
+            }
+            button {
+                disabled: !selected,
+                title: "Randomize the selected cells, at the density chosen for random universes",
+                onclick: move |_| {
+                    let Density(density) = *density.read();
+                    refill_selection(controls, universe, *selection.read(), Some(density));
+                },
+                "Randomize cells"
+            }
+        }
+        div { display: "flex", justify_content: "center",
+            transform_buttons
+        }
+    }
+}
+
+/// The size and place of the selection, and how many living cells it holds and the box around them, kept up
+/// to date as the universe changes.
+#[component]
+pub fn SelectionStats(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let selection = *use_shared_state::<Selection>(cx).unwrap().read();
+
+    let bounds = selection.0?;
+    let cells = selection.living_cells(&universe.read());
+    let living = match living_bounds(&cells) {
+        Some(living) => format!(
+            "{} alive, in a {}×{} box from row {}, column {}",
+            cells.len(),
+            living.width(),
+            living.height(),
+            living.top,
+            living.left
+        ),
+        None => "none alive".to_string(),
+    };
+
+    render! {
+        div { display: "flex", justify_content: "center",
+            span { font_family: "monospace",
+                "Selected {bounds.width()}×{bounds.height()} cells from row {bounds.top}, column {bounds.left}: {living}"
+            }
+        }
+    }
+}
+
+/// Return the box around the (row, column) cells, or None if there are none.
+fn living_bounds(cells: &[(u32, u32)]) -> Option<BoundingBox> {
+    let rows = cells.iter().map(|(row, _)| *row);
+    let cols = cells.iter().map(|(_, col)| *col);
+
+    Some(BoundingBox {
+        top: rows.clone().min()?,
+        left: cols.clone().min()?,
+        bottom: rows.max()?,
+        right: cols.max()?,
+    })
+}
+
+/// Outline the selected cells on the grid, and any cells being moved where they would be dropped.
+#[component]
+pub fn SelectionOutline(cx: Scope) -> Element {
+    let selection = use_shared_state::<Selection>(cx).unwrap();
+    let move_drag = use_shared_state::<MoveDrag>(cx).unwrap();
+
+    let move_drag = move_drag.read();
+    let offset = move_drag.0.as_ref().map_or((0, 0), |lifted| lifted.offset);
+    let bounds = selection.read().moved(offset).0?;
+    let ghost = move_drag.0.iter().flat_map(|lifted| {
+        lifted.cells.iter().map(move |(row, col)| {
+            let (x, y) = (*col as i64 + lifted.offset.1, *row as i64 + lifted.offset.0);
+            rsx! { rect { x: x, y: y, width: 1, height: 1, fill: SELECTION_COLOR, fill_opacity: GHOST_OPACITY } }
+        })
+    });
+
+    render! {
+        CellLayer {
+            rect {
+                x: bounds.left as i64,
+                y: bounds.top as i64,
+                width: bounds.width() as i64,
+                height: bounds.height() as i64,
+                fill: SELECTION_COLOR,
+                fill_opacity: 0.15,
+                stroke: SELECTION_COLOR,
+                stroke_width: 0.2,
+                stroke_dasharray: "0.5 0.3"
+            }
+            ghost
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_of_life::automaton::Automaton;
+
+    /// Conductor, electron head and electron tail.
+    const STATES: [u8; 3] = [1, 2, 3];
+
+    /// Return a Wireworld universe with a conductor, head and tail at the start of row 1.
+    fn wireworld() -> Universe {
+        let mut universe = Universe::with_size(8, 4);
+        universe.set_automaton(Automaton::Wireworld);
+        for (col, state) in STATES.into_iter().enumerate() {
+            universe.set_state(1, col as u32, state);
+        }
+        universe
+    }
+
+    fn apply(universe: &mut Universe, commands: &[Command]) {
+        for command in commands {
+            command.apply(universe);
+        }
+    }
+
+    #[test]
+    fn pasting_keeps_the_states_of_the_cells() {
+        let source = wireworld();
+        let region = source.extract_region(BoundingBox {
+            top: 1,
+            left: 0,
+            bottom: 1,
+            right: 2,
+        });
+        let mut universe = Universe::with_size(8, 4);
+        universe.set_automaton(Automaton::Wireworld);
+
+        let mut target = universe.clone();
+        target.blit_region(&region, 2, 4);
+        let paints = paints_to(&universe, &target);
+        apply(&mut universe, &paints);
+        assert_eq!(STATES, [4, 5, 6].map(|col| universe.state(2, col)));
+    }
+
+    #[test]
+    fn cells_that_stay_alive_in_another_state_are_painted() {
+        let universe = wireworld();
+        let mut target = universe.clone();
+        target.set_state(1, 1, 3);
+
+        assert_eq!(
+            paints_to(&universe, &target),
+            [Command::Paint {
+                row: 1,
+                col: 1,
+                state: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn moving_keeps_the_states_of_the_cells() {
+        let mut universe = wireworld();
+        let cells = [(1, 0), (1, 1), (1, 2)];
+
+        let paints = move_paints(&universe, &cells, (1, 1));
+        apply(&mut universe, &paints);
+        assert_eq!([0, 0, 0], [0, 1, 2].map(|col| universe.state(1, col)));
+        assert_eq!(STATES, [1, 2, 3].map(|col| universe.state(2, col)));
+    }
+}
@@ -0,0 +1,373 @@
+//! The settings panel: choices about how the app looks, remembered between sessions.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::use_controls,
+    game_of_life::{engine::EngineChoice, universe::Universe, CellRenderer},
+    history::HISTORY_LENGTHS,
+    memory::{megabytes, MemoryBudget, BUDGET_CHOICES_MB},
+    palette::{Palette, MIN_CONTRAST, PALETTES},
+    selection::Selection,
+    storage,
+    theme::{Appearance, Theme, MIN_CELL_SIZES, THEMES},
+    viewport::Viewport,
+};
+
+const PALETTE_KEY: &str = "palette.txt";
+
+/// The (width, height) universe sizes offered as presets.
+const UNIVERSE_SIZES: [(u32, u32); 5] = [(32, 32), (64, 64), (128, 128), (256, 128), (256, 256)];
+/// The largest width or height a universe can be given.
+pub(crate) const MAX_UNIVERSE_SIZE: u32 = 1024;
+/// The generations jumped ahead unless another number is given.
+const DEFAULT_JUMP: u64 = 1_000_000;
+
+/// Return the palette chosen in an earlier session, or the standard palette.
+pub fn stored_palette() -> Palette {
+    storage::load(PALETTE_KEY)
+        .and_then(|name| Palette::find(name.trim()))
+        .unwrap_or_default()
+}
+
+/// A button that shows or hides the settings.
+#[component]
+pub fn SettingsPanel(cx: Scope) -> Element {
+    let palette = use_shared_state::<Palette>(cx).unwrap();
+    let appearance = use_shared_state::<Appearance>(cx).unwrap();
+    let budget = use_shared_state::<MemoryBudget>(cx).unwrap();
+    let controls = use_controls(cx);
+    let open = use_state(cx, || false);
+
+    let current = *palette.read();
+    let contrast = current.min_contrast();
+    let warning = if contrast < MIN_CONTRAST {
+        " (too low)"
+    } else {
+        ""
+    };
+    let palettes = PALETTES.into_iter().map(|choice| {
+        rsx! {
+            option { key: "{choice.name}", value: "{choice.name}", selected: choice == current, "{choice.name}" }
+        }
+    });
+
+    let current_appearance = *appearance.read();
+    let themes = THEMES.into_iter().map(|choice| {
+        rsx! {
+            option {
+                key: "{choice.name}",
+                value: "{choice.name}",
+                selected: choice == current_appearance.theme,
+                "{choice.name}"
+            }
+        }
+    });
+    let min_cell_sizes = MIN_CELL_SIZES.into_iter().map(|size| {
+        rsx! {
+            option {
+                key: "{size}",
+                value: "{size}",
+                selected: current_appearance.min_cell_size == Some(size),
+                "{size}px"
+            }
+        }
+    });
+
+    let renderers = CellRenderer::ALL.into_iter().map(|choice| {
+        rsx! {
+            option {
+                key: "{choice.name()}",
+                value: "{choice.name()}",
+                selected: choice == current_appearance.renderer,
+                "{choice.name()}"
+            }
+        }
+    });
+
+    let (cap_mb, used) = {
+        let budget = budget.read();
+        let usage: Vec<String> = budget
+            .usage()
+            .map(|(name, bytes)| format!("{name} {:.1} MB", megabytes(bytes)))
+            .collect();
+        let mut used = format!("using {:.1} MB", megabytes(budget.total()));
+        if !usage.is_empty() {
+            used = format!("{used}: {}", usage.join(", "));
+        }
+        (budget.cap_mb(), used)
+    };
+    let budget_choices = BUDGET_CHOICES_MB.into_iter().map(|choice| {
+        rsx! {
+            option { key: "{choice}", value: "{choice}", selected: choice == cap_mb, "{choice} MB" }
+        }
+    });
+
+    let history_length = controls.history_length();
+    let history_lengths = HISTORY_LENGTHS.into_iter().map(|choice| {
+        rsx! {
+            option {
+                key: "{choice}",
+                value: "{choice}",
+                selected: choice == history_length,
+                if choice == 0 { "off".to_string() } else { format!("{choice} changes") }
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", flex_direction: "column", align_items: "center",
+            button { onclick: move |_| open.set(!open.get()), if *open.get() { "Hide settings" } else { "Settings" } }
+            if *open.get() {
+                rsx! {
+                    div { display: "flex", align_items: "center", gap: "8px",
+                        label {
+                            "Palette "
+                            select {
+                                onchange: move |event| {
+                                    if let Some(choice) = Palette::find(&event.value) {
+                                        *palette.write() = choice;
+                                        storage::store(PALETTE_KEY, choice.name);
+                                    }
+                                },
+                                palettes
+                            }
+                        }
+                        span { font_family: "monospace", "contrast {contrast:.1}:1{warning}" }
+                    }
+                    div { display: "flex", align_items: "center", gap: "8px",
+                        label {
+                            "Theme "
+                            select {
+                                onchange: move |event| {
+                                    if let Some(theme) = Theme::find(&event.value) {
+                                        appearance.write().theme = theme;
+                                        appearance.read().store();
+                                        *palette.write() = theme.palette;
+                                        storage::store(PALETTE_KEY, theme.palette.name);
+                                    }
+                                },
+                                themes
+                            }
+                        }
+                        label {
+                            "Minimum cell size "
+                            select {
+                                onchange: move |event| {
+                                    appearance.write().min_cell_size = event.value.parse().ok();
+                                    appearance.read().store();
+                                },
+                                option { value: "none", selected: current_appearance.min_cell_size.is_none(), "none" }
+                                min_cell_sizes
+                            }
+                        }
+                        label {
+                            input {
+                                r#type: "checkbox",
+                                checked: current_appearance.rulers,
+                                onchange: move |event| {
+                                    appearance.write().rulers = event.value == "true";
+                                    appearance.read().store();
+                                },
+                            }
+                            "Rulers"
+                        }
+                        label {
+                            "Cells drawn with "
+                            select {
+                                onchange: move |event| {
+                                    if let Some(renderer) = CellRenderer::find(&event.value) {
+                                        appearance.write().renderer = renderer;
+                                        appearance.read().store();
+                                    }
+                                },
+                                renderers
+                            }
+                        }
+                        label {
+                            input {
+                                r#type: "checkbox",
+                                checked: current_appearance.color_by_age,
+                                onchange: move |event| {
+                                    appearance.write().color_by_age = event.value == "true";
+                                    appearance.read().store();
+                                },
+                            }
+                            "Color by age"
+                        }
+                    }
+                    UniverseSize {}
+                    EngineSettings {}
+                    div { display: "flex", align_items: "center", gap: "8px",
+                        label {
+                            "Memory for history and statistics "
+                            select {
+                                onchange: move |event| {
+                                    if let Ok(choice) = event.value.parse() {
+                                        budget.write().set_cap_mb(choice);
+                                    }
+                                },
+                                budget_choices
+                            }
+                        }
+                        span { font_family: "monospace", "{used}" }
+                    }
+                    div { display: "flex", align_items: "center", gap: "8px",
+                        label {
+                            "Step back through "
+                            select {
+                                onchange: move |event| {
+                                    if let Ok(choice) = event.value.parse() {
+                                        controls.set_history_length(choice);
+                                    }
+                                },
+                                history_lengths
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The size of the universe: presets, and a width and height to resize to.  Resizing clears the universe.
+#[component]
+fn UniverseSize(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let viewport = use_shared_state::<Viewport>(cx).unwrap();
+    let selection = use_shared_state::<Selection>(cx).unwrap();
+    let size = (universe.read().width(), universe.read().height());
+    let width = use_state(cx, || size.0);
+    let height = use_state(cx, || size.1);
+
+    let resize = move |(width, height): (u32, u32)| {
+        controls.resize(width, height);
+        // The viewport and selection may be outside a smaller universe.
+        *viewport.write() = Viewport::default();
+        *selection.write() = Selection::default();
+    };
+    let presets = UNIVERSE_SIZES
+        .into_iter()
+        .map(|(preset_width, preset_height)| {
+            rsx! {
+                option {
+                    key: "{preset_width}x{preset_height}",
+                    value: "{preset_width}x{preset_height}",
+                    selected: (preset_width, preset_height) == size,
+                    "{preset_width} × {preset_height}"
+                }
+            }
+        });
+    let parse_size = |value: &str| {
+        value
+            .parse::<u32>()
+            .ok()
+            .map(|size| size.clamp(1, MAX_UNIVERSE_SIZE))
+    };
+
+    render! {
+        div { display: "flex", align_items: "center", gap: "8px",
+            label {
+                "Universe size "
+                select {
+                    onchange: move |event| {
+                        let preset = UNIVERSE_SIZES
+                            .into_iter()
+                            .find(|(preset_width, preset_height)| format!("{preset_width}x{preset_height}") == event.value);
+                        if let Some((preset_width, preset_height)) = preset {
+                            width.set(preset_width);
+                            height.set(preset_height);
+                            resize((preset_width, preset_height));
+                        }
+                    },
+                    if !UNIVERSE_SIZES.contains(&size) {
+                        rsx! { option { value: "", selected: true, "{size.0} × {size.1}" } }
+                    }
+                    presets
+                }
+            }
+            input {
+                r#type: "number",
+                min: 1,
+                max: MAX_UNIVERSE_SIZE as i64,
+                size: 5,
+                value: "{width}",
+                oninput: move |event| {
+                    if let Some(size) = parse_size(&event.value) {
+                        width.set(size);
+                    }
+                },
+            }
+            span { "×" }
+            input {
+                r#type: "number",
+                min: 1,
+                max: MAX_UNIVERSE_SIZE as i64,
+                size: 5,
+                value: "{height}",
+                oninput: move |event| {
+                    if let Some(size) = parse_size(&event.value) {
+                        height.set(size);
+                    }
+                },
+            }
+            button { onclick: move |_| resize((*width.get(), *height.get())), "Resize" }
+        }
+    }
+}
+
+/// The engine the universe is advanced with, and jumping ahead many generations at once.
+#[component]
+fn EngineSettings(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    // Subscribed, so the jump is enabled and disabled as the universe changes.
+    use_shared_state::<Universe>(cx).unwrap();
+    let engine = use_state(cx, || controls.engine());
+    let jump = use_state(cx, || DEFAULT_JUMP);
+
+    let engines = EngineChoice::all().into_iter().map(|choice| {
+        rsx! {
+            option { key: "{choice.name()}", value: "{choice.name()}", selected: choice == *engine.get(), "{choice.name()}" }
+        }
+    });
+    let can_jump = controls.can_jump();
+
+    render! {
+        div { display: "flex", align_items: "center", gap: "8px",
+            label {
+                "Engine "
+                select {
+                    onchange: move |event| {
+                        if let Some(choice) = EngineChoice::find(&event.value) {
+                            engine.set(controls.set_engine(choice));
+                        }
+                    },
+                    engines
+                }
+            }
+            label { title: "With the hashlife engine, on a Life universe that wraps around at its edges",
+                "Jump ahead "
+                input {
+                    r#type: "number",
+                    min: 1,
+                    size: 12,
+                    disabled: !can_jump,
+                    value: "{jump}",
+                    oninput: move |event| {
+                        if let Ok(generations) = event.value.parse::<u64>() {
+                            jump.set(generations.max(1));
+                        }
+                    },
+                }
+                " generations "
+            }
+            button {
+                disabled: !can_jump,
+                onclick: move |_| controls.jump(*jump.get()),
+                "Jump"
+            }
+        }
+    }
+}
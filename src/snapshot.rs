@@ -0,0 +1,433 @@
+//! Snapshots of the complete state of the app, used for save slots.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    annotations::{Annotation, Annotations},
+    command::{checked_size, use_controls},
+    game_of_life::{
+        ant::{Ant, Heading},
+        universe::Universe,
+    },
+    selection::Tool,
+    storage,
+    viewport::Viewport,
+};
+
+/// The version written to new snapshots.
+const SNAPSHOT_VERSION: u64 = 6;
+
+/// The number of save slots.
+pub const SLOT_COUNT: usize = 3;
+
+/// The complete state of the app, serialized as a single JSON blob.
+///
+/// Each snapshot records the version it was written with.  Fields added in later versions need serde
+/// defaults (or an upgrade in from_json()) so that older snapshots keep loading.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    version: u64,
+    width: u32,
+    height: u32,
+    generation: u64,
+    /// (row, col) of every living cell.
+    cells: Vec<(u32, u32)>,
+    ticks_per_frame: u32,
+    running: bool,
+    /// Added in version 2.
+    #[serde(default)]
+    annotations: Vec<Annotation>,
+    /// The rule in B/S notation.  Added in version 3: earlier snapshots were all of Conway's life.
+    #[serde(default)]
+    rule: String,
+    /// "wrap", "dead" or "infinite".  Added in version 4: earlier snapshots all wrapped around at the edges.
+    #[serde(default)]
+    boundary: String,
+    /// The automaton, as the automaton command writes it.  Added in version 5: earlier snapshots were all of
+    /// Life.
+    #[serde(default)]
+    automaton: String,
+    /// (row, col, state) of every cell in a state above 1, for automata with more than two states.  Added in
+    /// version 5.
+    #[serde(default)]
+    states: Vec<(u32, u32, u8)>,
+    /// (row, col, heading in degrees clockwise from up) of every ant of Langton's Ant.  Added in version 5.
+    #[serde(default)]
+    ants: Vec<(u32, u32, u32)>,
+    /// "square" or "hex".  Added in version 5: earlier snapshots were all square.
+    #[serde(default)]
+    topology: String,
+    /// The part of the universe shown on the grid.  Added in version 6: earlier snapshots are shown whole.
+    #[serde(default)]
+    viewport: Option<Viewport>,
+    /// What clicking on the grid does.  Added in version 6: earlier snapshots leave the tool as it is.
+    #[serde(default)]
+    tool: Option<Tool>,
+}
+
+impl Snapshot {
+    /// Capture the state of the app.
+    pub fn new(
+        universe: &Universe,
+        ticks_per_frame: u32,
+        running: bool,
+        annotations: &Annotations,
+        viewport: Viewport,
+        tool: Tool,
+    ) -> Snapshot {
+        let cells: Vec<(u32, u32)> = universe
+            .get_living_cells()
+            .into_iter()
+            .map(|(x, y)| (y as u32, x as u32))
+            .collect();
+        let states = cells
+            .iter()
+            .map(|(row, col)| (*row, *col, universe.state(*row, *col)))
+            .filter(|(_, _, state)| *state > 1)
+            .collect();
+
+        Snapshot {
+            version: SNAPSHOT_VERSION,
+            width: universe.width(),
+            height: universe.height(),
+            generation: universe.generation(),
+            cells,
+            ticks_per_frame,
+            running,
+            annotations: annotations.0.clone(),
+            rule: universe.rule().to_string(),
+            boundary: universe.boundary().to_string(),
+            automaton: universe.automaton().to_string(),
+            states,
+            ants: universe
+                .ants()
+                .iter()
+                .map(|ant| (ant.row, ant.col, ant.heading.degrees()))
+                .collect(),
+            topology: universe.topology().to_string(),
+            viewport: Some(viewport),
+            tool: Some(tool),
+        }
+    }
+
+    /// Return the universe that was captured, at the size it was captured.
+    pub fn universe(&self) -> Universe {
+        let mut universe = Universe::with_size(self.width, self.height);
+
+        universe.set_rule(self.rule.parse().unwrap_or_default());
+        universe.set_boundary(self.boundary.parse().unwrap_or_default());
+        universe.set_topology(self.topology.parse().unwrap_or_default());
+        universe.set_automaton(self.automaton.parse().unwrap_or_default());
+        universe.set_cells(&self.cells);
+        for (row, col, state) in self.states.iter().copied() {
+            universe.set_state(row, col, state);
+        }
+        let ants: Vec<Ant> = self
+            .ants
+            .iter()
+            .map(|(row, col, degrees)| Ant::new(*row, *col, heading(*degrees)))
+            .collect();
+        universe.set_ants(&ants);
+        universe.set_generation(self.generation);
+        universe
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn ticks_per_frame(&self) -> u32 {
+        self.ticks_per_frame
+    }
+
+    pub fn running(&self) -> bool {
+        self.running
+    }
+
+    pub fn annotations(&self) -> Annotations {
+        Annotations(self.annotations.clone())
+    }
+
+    pub fn viewport(&self) -> Option<Viewport> {
+        self.viewport
+    }
+
+    pub fn tool(&self) -> Option<Tool> {
+        self.tool
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("snapshot should serialize")
+    }
+
+    /// Read a snapshot written by this or any earlier version of the app, or return an error if its universe
+    /// has no cells or more than the largest universe that can be chosen.
+    pub fn from_json(json: &str) -> Result<Snapshot, String> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|error| error.to_string())?;
+
+        match value.get("version").and_then(serde_json::Value::as_u64) {
+            Some(1..=SNAPSHOT_VERSION) => {
+                let snapshot: Snapshot =
+                    serde_json::from_value(value).map_err(|error| error.to_string())?;
+                checked_size(snapshot.width.into(), snapshot.height.into())?;
+                Ok(snapshot)
+            }
+            Some(0) => Err("snapshot version 0 was never written by this app".to_string()),
+            Some(version) => Err(format!("snapshot version {version} is newer than this app")),
+            None => Err("snapshot has no version".to_string()),
+        }
+    }
+}
+
+/// Return the heading at the given degrees clockwise from up, or up if it is not a quarter turn.
+fn heading(degrees: u32) -> Heading {
+    [Heading::Right, Heading::Down, Heading::Left]
+        .into_iter()
+        .find(|heading| heading.degrees() == degrees)
+        .unwrap_or_default()
+}
+
+/// Return the storage key of a save slot.
+fn slot_key(slot: usize) -> String {
+    format!("slot_{slot}.json")
+}
+
+/// Load the snapshot saved in a slot, if there is one.
+pub fn load_slot(slot: usize) -> Option<Snapshot> {
+    Snapshot::from_json(&storage::load(&slot_key(slot))?).ok()
+}
+
+/// Buttons to save the app to, and restore it from, a number of save slots.
+#[component]
+pub fn SaveSlots(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    // The generation saved in each slot, or None if the slot is empty.
+    let saved_generations = use_state(cx, || {
+        (1..=SLOT_COUNT)
+            .map(|slot| load_slot(slot).map(|snapshot| snapshot.generation()))
+            .collect::<Vec<_>>()
+    });
+
+    let slots = saved_generations
+        .get()
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(index, generation)| {
+            let slot = index + 1;
+            let description = match generation {
+                Some(generation) => format!("Slot {slot}: generation {generation}"),
+                None => format!("Slot {slot}: empty"),
+            };
+
+            rsx! {
+                span { key: "{slot}", title: "{description}",
+                    button {
+                        onclick: move |_| {
+                            let snapshot = controls.snapshot();
+                            storage::store(&slot_key(slot), &snapshot.to_json());
+                            saved_generations.modify(|generations| {
+                                let mut generations = generations.clone();
+                                generations[index] = Some(snapshot.generation());
+                                generations
+                            });
+                        },
+                        "Save {slot}"
+                    }
+                    button {
+                        disabled: generation.is_none(),
+                        onclick: move |_| {
+                            if let Some(snapshot) = load_slot(slot) {
+                                controls.restore(&snapshot);
+                            }
+                        },
+                        "Load {slot}"
+                    }
+                }
+            }
+        });
+
+    render! {
+        div { display: "flex", justify_content: "center", gap: "8px", slots }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_of_life::{
+        automaton::Automaton,
+        universe::{Boundary, Topology},
+    };
+
+    /// A glider in an 8 by 6 universe, as the first version of the app saved it.
+    const VERSION_1: &str = r#"{"version":1,"width":8,"height":6,"generation":12,
+        "cells":[[0,1],[1,2],[2,0],[2,1],[2,2]],"ticks_per_frame":2,"running":true}"#;
+    const VERSION_2: &str = r#"{"version":2,"width":8,"height":6,"generation":12,
+        "cells":[[0,1],[1,2],[2,0],[2,1],[2,2]],"ticks_per_frame":2,"running":true,
+        "annotations":[{"Label":{"cell":[0,1],"text":"glider"}}]}"#;
+    const VERSION_3: &str = r#"{"version":3,"width":8,"height":6,"generation":12,
+        "cells":[[0,1],[1,2],[2,0],[2,1],[2,2]],"ticks_per_frame":2,"running":true,
+        "annotations":[],"rule":"B36/S23"}"#;
+    const VERSION_4: &str = r#"{"version":4,"width":8,"height":6,"generation":12,
+        "cells":[[0,1],[1,2],[2,0],[2,1],[2,2]],"ticks_per_frame":2,"running":true,
+        "annotations":[],"rule":"B36/S23","boundary":"dead"}"#;
+    const VERSION_5: &str = r#"{"version":5,"width":8,"height":6,"generation":12,
+        "cells":[[0,1],[1,2],[2,0],[2,1],[2,2]],"ticks_per_frame":2,"running":true,
+        "annotations":[],"rule":"B3/S23","boundary":"dead","automaton":"brians-brain",
+        "states":[[2,2,2]],"ants":[],"topology":"hex"}"#;
+
+    fn settings(universe: &Universe) -> (String, Boundary, Automaton, Topology) {
+        (
+            universe.rule().to_string(),
+            universe.boundary(),
+            universe.automaton(),
+            universe.topology(),
+        )
+    }
+
+    #[test]
+    fn every_version_loads_its_cells() {
+        for json in [VERSION_1, VERSION_2, VERSION_3, VERSION_4, VERSION_5] {
+            let snapshot = Snapshot::from_json(json).unwrap();
+            let universe = snapshot.universe();
+
+            assert_eq!((universe.width(), universe.height()), (8, 6), "{json}");
+            assert_eq!(universe.population(), 5, "{json}");
+            assert!(universe.is_alive(2, 1), "{json}");
+            assert_eq!(snapshot.generation(), 12);
+            assert_eq!(snapshot.ticks_per_frame(), 2);
+            assert!(snapshot.running());
+        }
+    }
+
+    #[test]
+    fn early_versions_are_of_conways_life_wrapping_on_square_cells() {
+        let conway = (
+            "B3/S23".to_string(),
+            Boundary::Wrap,
+            Automaton::Life,
+            Topology::Square,
+        );
+        for json in [VERSION_1, VERSION_2] {
+            assert_eq!(
+                settings(&Snapshot::from_json(json).unwrap().universe()),
+                conway
+            );
+        }
+
+        let annotations = Snapshot::from_json(VERSION_2).unwrap().annotations();
+        assert_eq!(
+            annotations.0,
+            vec![Annotation::Label {
+                cell: (0, 1),
+                text: "glider".to_string()
+            }]
+        );
+        assert!(Snapshot::from_json(VERSION_1)
+            .unwrap()
+            .annotations()
+            .0
+            .is_empty());
+    }
+
+    #[test]
+    fn later_versions_keep_the_settings_they_added() {
+        let universe = |json| settings(&Snapshot::from_json(json).unwrap().universe());
+
+        assert_eq!(
+            universe(VERSION_3),
+            (
+                "B36/S23".to_string(),
+                Boundary::Wrap,
+                Automaton::Life,
+                Topology::Square
+            )
+        );
+        assert_eq!(
+            universe(VERSION_4),
+            (
+                "B36/S23".to_string(),
+                Boundary::Dead,
+                Automaton::Life,
+                Topology::Square
+            )
+        );
+        assert_eq!(
+            universe(VERSION_5),
+            (
+                "B3/S23".to_string(),
+                Boundary::Dead,
+                Automaton::BriansBrain,
+                Topology::Hex
+            )
+        );
+        assert_eq!(
+            Snapshot::from_json(VERSION_5)
+                .unwrap()
+                .universe()
+                .state(2, 2),
+            2
+        );
+    }
+
+    #[test]
+    fn versions_before_6_leave_the_viewport_and_tool_as_they_are() {
+        for json in [VERSION_1, VERSION_2, VERSION_3, VERSION_4, VERSION_5] {
+            let snapshot = Snapshot::from_json(json).unwrap();
+            assert_eq!(
+                (snapshot.viewport(), snapshot.tool()),
+                (None, None),
+                "{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn snapshots_read_back_as_written() {
+        let mut universe = Universe::with_size(20, 10);
+        universe.set_automaton(Automaton::Wireworld);
+        universe.set_state(3, 4, 1);
+        universe.set_state(3, 5, 2);
+        universe.set_generation(7);
+        let viewport = Viewport {
+            zoom: 2.0,
+            left: 3.0,
+            top: 1.0,
+        };
+        let snapshot = Snapshot::new(
+            &universe,
+            4,
+            false,
+            &Annotations::default(),
+            viewport,
+            Tool::Draw,
+        );
+
+        let read = Snapshot::from_json(&snapshot.to_json()).unwrap();
+        assert_eq!(read, snapshot);
+        assert!(read.universe() == universe);
+        assert_eq!(read.universe().state(3, 5), 2);
+    }
+
+    #[test]
+    fn unknown_versions_are_refused() {
+        let with_version =
+            |version| VERSION_1.replace(r#""version":1"#, &format!(r#""version":{version}"#));
+
+        assert_eq!(
+            Snapshot::from_json(&with_version(0)),
+            Err("snapshot version 0 was never written by this app".to_string())
+        );
+        assert_eq!(
+            Snapshot::from_json(&with_version(SNAPSHOT_VERSION + 1)),
+            Err(format!(
+                "snapshot version {} is newer than this app",
+                SNAPSHOT_VERSION + 1
+            ))
+        );
+        assert!(Snapshot::from_json(&VERSION_1.replace(r#""version":1,"#, "")).is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! Sonification: plays the births and deaths of each generation as tones.
+//!
+//! Births play a tone from the chosen scale that rises with the number of cells born, and deaths play a
+//! shorter tone an octave lower that rises with the number of cells that died.  Tones are played with the
+//! Web Audio API on the web, and with rodio on desktop.
+
+use std::fmt;
+
+use dioxus::prelude::*;
+
+use crate::game_of_life::universe::{Cell, Universe};
+
+/// The frequency of the lowest birth tone: A3.
+const BASE_FREQUENCY: f64 = 220.0;
+const BIRTH_SECONDS: f64 = 0.12;
+const DEATH_SECONDS: f64 = 0.05;
+
+/// The scale tones are chosen from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Scale {
+    #[default]
+    Pentatonic,
+    Major,
+    Minor,
+    Chromatic,
+}
+
+impl Scale {
+    const ALL: [Scale; 4] = [
+        Scale::Pentatonic,
+        Scale::Major,
+        Scale::Minor,
+        Scale::Chromatic,
+    ];
+
+    /// The semitones above the root of each note of an octave of the scale.
+    fn semitones(&self) -> &'static [u32] {
+        match self {
+            Scale::Pentatonic => &[0, 2, 4, 7, 9],
+            Scale::Major => &[0, 2, 4, 5, 7, 9, 11],
+            Scale::Minor => &[0, 2, 3, 5, 7, 8, 10],
+            Scale::Chromatic => &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        }
+    }
+
+    /// Return the frequency of the given note of the scale, counting up from the base frequency.
+    fn frequency(&self, note: u32) -> f64 {
+        let semitones = self.semitones();
+        let octave = note / semitones.len() as u32;
+        let semitone = octave * 12 + semitones[note as usize % semitones.len()];
+
+        BASE_FREQUENCY * 2_f64.powf(semitone as f64 / 12.0)
+    }
+
+    /// Return the note for a number of cells: each doubling of the count is one note higher.
+    fn note(count: usize) -> u32 {
+        (count as f64).log2().max(0.0).round() as u32
+    }
+}
+
+impl fmt::Display for Scale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Scale::Pentatonic => "pentatonic",
+            Scale::Major => "major",
+            Scale::Minor => "minor",
+            Scale::Chromatic => "chromatic",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Plays tones through the Web Audio API.
+#[cfg(feature = "web")]
+struct Synth {
+    context: web_sys::AudioContext,
+}
+
+#[cfg(feature = "web")]
+impl Synth {
+    /// Create a synth, or None if audio isn't available.
+    ///
+    /// Browsers only allow audio to start after a user gesture, so this must be called from an event handler.
+    fn new() -> Option<Synth> {
+        let context = web_sys::AudioContext::new().ok()?;
+        Some(Synth { context })
+    }
+
+    /// Play a tone of the frequency for the given seconds at the given volume, from 0 to 1.
+    fn play(&self, frequency: f64, seconds: f64, volume: f64) {
+        let context = &self.context;
+        let start = context.current_time();
+        let (Ok(oscillator), Ok(gain)) = (context.create_oscillator(), context.create_gain())
+        else {
+            return;
+        };
+
+        oscillator.frequency().set_value(frequency as f32);
+        // Fade out, so the tone doesn't end with a click.
+        let _ = gain.gain().set_value_at_time(volume as f32, start);
+        let _ = gain
+            .gain()
+            .linear_ramp_to_value_at_time(0.0, start + seconds);
+
+        let _ = oscillator.connect_with_audio_node(&gain);
+        let _ = gain.connect_with_audio_node(&context.destination());
+        let _ = oscillator.start();
+        let _ = oscillator.stop_with_when(start + seconds);
+    }
+}
+
+/// Plays tones through the default audio output device.
+#[cfg(feature = "desktop")]
+struct Synth {
+    // The stream must be kept alive for the handle to play.
+    _stream: rodio::OutputStream,
+    handle: rodio::OutputStreamHandle,
+}
+
+#[cfg(feature = "desktop")]
+impl Synth {
+    /// Create a synth, or None if there is no audio output device.
+    fn new() -> Option<Synth> {
+        let (stream, handle) = rodio::OutputStream::try_default().ok()?;
+        Some(Synth {
+            _stream: stream,
+            handle,
+        })
+    }
+
+    /// Play a tone of the frequency for the given seconds at the given volume, from 0 to 1.
+    fn play(&self, frequency: f64, seconds: f64, volume: f64) {
+        use rodio::Source;
+
+        let tone = rodio::source::SineWave::new(frequency as f32)
+            .take_duration(std::time::Duration::from_secs_f64(seconds))
+            .fade_in(std::time::Duration::from_millis(5))
+            .amplify(volume as f32);
+        let _ = self.handle.play_raw(tone);
+    }
+}
+
+/// Return the number of cells born and the number that died between the two generations.
+fn births_and_deaths(previous: &[Cell], next: impl Iterator<Item = Cell>) -> (usize, usize) {
+    previous
+        .iter()
+        .zip(next)
+        .fold((0, 0), |(births, deaths), (before, after)| {
+            match (before, after) {
+                (Cell::Dead, Cell::Alive) => (births + 1, deaths),
+                (Cell::Alive, Cell::Dead) => (births, deaths + 1),
+                _ => (births, deaths),
+            }
+        })
+}
+
+/// Controls to turn sound on and off, and choose its volume and scale.
+#[component]
+pub fn Sonification(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    // Created when sound is first turned on, since audio may only start after a user gesture.
+    let synth = use_ref(cx, || None::<Synth>);
+    let enabled = use_state(cx, || false);
+    let volume = use_state(cx, || 30_u32);
+    let scale = use_state(cx, Scale::default);
+    // The cells of the last generation played, to count the births and deaths of the next.
+    let previous = use_ref(cx, || universe.read().cells().collect::<Vec<_>>());
+
+    let generation = universe.read().generation();
+    use_effect(cx, (&generation,), |_| {
+        to_owned![universe, synth, enabled, volume, scale, previous];
+        async move {
+            let universe = universe.read();
+            let (births, deaths) = births_and_deaths(&previous.read(), universe.cells());
+            previous.set(universe.cells().collect());
+
+            if !*enabled.current() {
+                return;
+            }
+            if let Some(synth) = synth.read().as_ref() {
+                let volume = *volume.current() as f64 / 100.0;
+                let scale = *scale.current();
+
+                if births > 0 {
+                    synth.play(scale.frequency(Scale::note(births)), BIRTH_SECONDS, volume);
+                }
+                if deaths > 0 {
+                    synth.play(
+                        scale.frequency(Scale::note(deaths)) / 2.0,
+                        DEATH_SECONDS,
+                        volume,
+                    );
+                }
+            }
+        }
+    });
+
+    let scales = Scale::ALL.into_iter().map(|choice| {
+        rsx! {
+            option { key: "{choice}", value: "{choice}", selected: choice == *scale.get(), "{choice}" }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: *enabled.get(),
+                    onchange: move |event| {
+                        let on = event.value == "true";
+                        if on && synth.read().is_none() {
+                            synth.set(Synth::new());
+                        }
+                        enabled.set(on);
+                    },
+                }
+                "Sound"
+            }
+            label {
+                "Volume "
+                input {
+                    r#type: "range",
+                    min: 0,
+                    max: 100,
+                    value: "{volume}",
+                    oninput: move |event| {
+                        if let Ok(value) = event.value.parse() {
+                            volume.set(value);
+                        }
+                    },
+                }
+            }
+            select {
+                onchange: move |event| {
+                    if let Some(choice) = Scale::ALL.into_iter().find(|choice| choice.to_string() == event.value) {
+                        scale.set(choice);
+                    }
+                },
+                scales
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Soup census: runs the universe until it settles, and counts the objects in the debris (the "ash").
+//!
+//! This is how soup searchers survey a rule: randomize a universe, let it burn out, and record what is left.
+//! The report can be exported as JSON for collecting many soups.
+
+use std::collections::BTreeMap;
+
+use dioxus::prelude::*;
+use serde::Serialize;
+
+use crate::{
+    file_io::save_text_file,
+    game_of_life::{
+        census::{run_until_stable, Recognizer},
+        universe::Universe,
+    },
+};
+
+const SOUP_REPORT_FILE_NAME: &str = "soup_census.json";
+/// Generations a soup is run for before giving up on it settling.
+const STABLE_GENERATIONS: u64 = 10_000;
+
+/// The census of the debris left by a soup once it settled.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SoupReport {
+    pub rule: String,
+    /// The generation the soup was run from.
+    pub start_generation: u64,
+    /// The generation the debris was counted at.
+    pub stable_generation: u64,
+    /// The period the debris repeats with, or None if it didn't settle.
+    pub period: Option<u64>,
+    /// The number of each recognized object, by name.
+    pub objects: BTreeMap<String, usize>,
+    pub unrecognized: usize,
+    /// The number of living cells in the debris.
+    pub ash_population: usize,
+    /// The fraction of cells in the universe that are alive in the debris.
+    pub ash_density: f64,
+}
+
+impl SoupReport {
+    /// Run a copy of the universe until it settles, and count what is left.
+    pub fn run(universe: &Universe, recognizer: &Recognizer) -> SoupReport {
+        let mut ash = universe.clone();
+        let period = run_until_stable(&mut ash, STABLE_GENERATIONS);
+        let census = recognizer.census(&ash);
+        let ash_population = ash.population();
+
+        SoupReport {
+            rule: universe.rule().to_string(),
+            start_generation: universe.generation(),
+            stable_generation: ash.generation(),
+            period,
+            objects: census
+                .objects
+                .into_iter()
+                .map(|(name, count)| (name.to_string(), count))
+                .collect(),
+            unrecognized: census.unrecognized,
+            ash_population,
+            ash_density: ash_population as f64 / (ash.width() * ash.height()) as f64,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("soup report should serialize")
+    }
+}
+
+/// A button that runs a soup census of the universe, and a report of the debris.
+#[component]
+pub fn SoupCensus(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let recognizer = cx.use_hook(Recognizer::new);
+    let report = use_state(cx, || None::<SoupReport>);
+
+    let results = report.get().as_ref().map(|report| {
+        let settled = match report.period {
+            Some(period) => format!("settled at generation {} with period {period}", report.stable_generation),
+            None => format!("still active at generation {}", report.stable_generation),
+        };
+        let rows = report.objects.iter().map(|(name, count)| {
+            rsx! {
+                tr { key: "{name}",
+                    td { "{name}" }
+                    td { text_align: "right", "{count}" }
+                }
+            }
+        });
+        let unrecognized = report.unrecognized;
+        let ash_population = report.ash_population;
+        let ash_density = report.ash_density * 100.0;
+
+        rsx! {
+            span { font_family: "monospace", "{report.rule} soup from generation {report.start_generation}, {settled}" }
+            table { font_family: "monospace",
+                rows,
+                tr {
+                    td { "other" }
+                    td { text_align: "right", "{unrecognized}" }
+                }
+                tr {
+                    td { "ash cells" }
+                    td { text_align: "right", "{ash_population} ({ash_density:.2}%)" }
+                }
+            }
+            button { onclick: move |_| save_text_file(SOUP_REPORT_FILE_NAME, &report.to_json()), "Export JSON" }
+        }
+    });
+
+    render! {
+        div { display: "flex", flex_direction: "column", align_items: "center",
+            button {
+                onclick: move |_| report.set(Some(SoupReport::run(&universe.read(), recognizer))),
+                "Soup census"
+            }
+            results
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return a soup that settles into two blocks, a blinker and a traffic light (four blinkers too close
+    /// to be told apart), with a glider flying around the universe.
+    fn known_soup() -> Universe {
+        let mut universe = Universe::with_size(40, 40);
+        // Each of these corners of three cells fills in to a block.
+        universe.set_cells(&[(1, 20), (1, 21), (2, 20)]);
+        universe.set_cells(&[(5, 24), (5, 25), (6, 25)]);
+        // The glider flies down and right, clear of the debris.
+        universe.set_cells(&[(10, 11), (11, 12), (12, 10), (12, 11), (12, 12)]);
+        // A T of four cells becomes a traffic light after nine generations.
+        universe.set_cells(&[(30, 9), (30, 10), (30, 11), (31, 10)]);
+        universe.set_cells(&[(20, 30), (21, 30), (22, 30)]);
+        universe
+    }
+
+    #[test]
+    fn reports_count_the_objects_in_the_ash() {
+        let report = SoupReport::run(&known_soup(), &Recognizer::new());
+
+        assert_eq!(
+            report.objects,
+            BTreeMap::from([
+                ("Block".to_string(), 2),
+                ("Blinker".to_string(), 1),
+                ("Glider".to_string(), 1),
+            ])
+        );
+        assert_eq!(report.unrecognized, 1);
+        assert_eq!(report.ash_population, 4 + 4 + 3 + 5 + 12);
+        assert_eq!(report.ash_density, 28.0 / 1600.0);
+    }
+
+    #[test]
+    fn reports_wait_for_the_glider_to_come_around() {
+        // The glider moves one cell diagonally every four generations, so it returns to where it was after
+        // four generations for each row of the universe.
+        let report = SoupReport::run(&known_soup(), &Recognizer::new());
+
+        assert_eq!(report.period, Some(160));
+        assert_eq!(report.start_generation, 0);
+        assert!(report.stable_generation >= 160);
+    }
+
+    #[test]
+    fn reports_export_as_json() {
+        let report = SoupReport::run(&known_soup(), &Recognizer::new());
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+
+        assert_eq!(json["rule"], "B3/S23");
+        assert_eq!(json["period"], 160);
+        assert_eq!(json["objects"]["Glider"], 1);
+        assert_eq!(json["ash_population"], 28);
+    }
+}
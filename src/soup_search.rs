@@ -0,0 +1,132 @@
+//! Searching random soups for interesting ones, without drawing them.
+//!
+//! Soups are made in a copy of the universe (its size, rule and boundary), so the universe shown is left
+//! alone while the search runs.  Any soup found can be replayed in the universe from its seed.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::{
+        random::random_seed,
+        soup_search::{SoupOutcome, SoupSearch},
+        universe::Universe,
+    },
+    platform::sleep_ms,
+    seed::Density,
+};
+
+/// The most interesting soups listed.
+const LISTED_SOUPS: usize = 10;
+/// Generations each soup runs for unless another number is chosen.
+const DEFAULT_GENERATIONS: u64 = 2_000;
+/// Milliseconds the search pauses between soups, so the page stays responsive.
+const PAUSE_MS: u32 = 0;
+
+/// A field for the generations each soup runs for, buttons to start and stop the search, and the most
+/// interesting soups found, each with a button to replay it.
+#[component]
+pub fn SoupSearchPanel(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let density = use_shared_state::<Density>(cx).unwrap();
+    let generations = use_state(cx, || DEFAULT_GENERATIONS);
+    let search = use_ref(cx, || SoupSearch::new(LISTED_SOUPS));
+    let running = use_state(cx, || false);
+
+    use_future(cx, (running.get(),), |(running,)| {
+        to_owned![universe, density, generations, search];
+        async move {
+            // The future is replaced when running changes, so a running search runs until then.
+            if !running {
+                return;
+            }
+            // Soups are made in a copy of the universe as it was when the search started.
+            let template = universe.read().clone();
+            let Density(density) = *density.read();
+            let mut seed = random_seed();
+            loop {
+                let outcome = SoupOutcome::run(&template, seed, density, *generations.get());
+                search.write().record(outcome);
+                seed = seed.wrapping_add(1);
+                sleep_ms(PAUSE_MS).await;
+            }
+        }
+    });
+
+    let Density(current_density) = *density.read();
+    let searched = search.read().searched;
+    let rows = search.read().best.clone().into_iter().map(|outcome| {
+        let SoupOutcome {
+            seed,
+            lifespan,
+            period,
+            final_population,
+        } = outcome;
+        let period = match period {
+            Some(period) => period.to_string(),
+            None => "still active".to_string(),
+        };
+        rsx! {
+            tr { key: "{seed}",
+                td { "{seed}" }
+                td { text_align: "right", "{lifespan}" }
+                td { text_align: "right", "{period}" }
+                td { text_align: "right", "{final_population}" }
+                td {
+                    button {
+                        title: "Randomize the universe from this seed, at the density searched with",
+                        onclick: move |_| {
+                            running.set(false);
+                            controls.dispatch(Command::Seed { seed, density: current_density });
+                        },
+                        "Replay"
+                    }
+                }
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", flex_direction: "column", align_items: "center",
+            div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+                label { "Soup search: generations per soup ",
+                    input {
+                        r#type: "number",
+                        min: 1,
+                        value: "{generations}",
+                        oninput: move |event| {
+                            if let Ok(value) = event.value.parse::<u64>() {
+                                generations.set(value.max(1));
+                            }
+                        },
+                    }
+                }
+                button {
+                    disabled: *running.get(),
+                    onclick: move |_| {
+                        *search.write() = SoupSearch::new(LISTED_SOUPS);
+                        running.set(true);
+                    },
+                    "Search"
+                }
+                button { disabled: !*running.get(), onclick: move |_| running.set(false), "Stop" }
+                span { font_family: "monospace", "{searched} soups searched" }
+            }
+            if !search.read().best.is_empty() {
+                rsx! {
+                    table { font_family: "monospace",
+                        tr {
+                            th { "seed" }
+                            th { "lifespan" }
+                            th { "period" }
+                            th { "final population" }
+                            th {}
+                        }
+                        rows
+                    }
+                }
+            }
+        }
+    }
+}
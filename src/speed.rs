@@ -0,0 +1,70 @@
+//! How fast the universe advances: by default a step each animation frame, so the speed follows the refresh
+//! rate of the display, or a chosen number of steps each second.  Each step advances the universe by the
+//! fast-forward multiplier (the ticks per frame), and the universe is drawn once after the frame's steps.
+//!
+//! At a chosen rate, each frame takes the steps owed for the time since the last frame, and carries the
+//! fraction of a step left over to the next frame.  So 2 steps a second take one frame in thirty on a 60Hz
+//! display, and 240 a second take four steps each frame.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::TicksPerFrame,
+};
+
+/// The rates offered, in steps each second.
+const RATES: [u32; 8] = [1, 2, 5, 10, 30, 60, 120, 240];
+/// The fast-forward multipliers offered, in generations each step.
+const MULTIPLIERS: [u32; 4] = [1, 2, 10, 100];
+/// The most generations a step advances, however the multiplier is set.
+pub const MAX_TICKS_PER_FRAME: u32 = MULTIPLIERS[MULTIPLIERS.len() - 1];
+
+/// The steps the universe takes each second, or None to take a step every frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StepsPerSecond(pub Option<u32>);
+
+/// The choice of how many steps the universe takes each second, and buttons for the generations each step
+/// advances, to fast-forward through long-lived patterns.
+#[component]
+pub fn SpeedControls(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let steps_per_second = use_shared_state::<StepsPerSecond>(cx).unwrap();
+    let StepsPerSecond(current) = *steps_per_second.read();
+    let TicksPerFrame(multiplier) = *use_shared_state::<TicksPerFrame>(cx).unwrap().read();
+
+    let rates = RATES.into_iter().map(|rate| {
+        rsx! {
+            option { key: "{rate}", value: "{rate}", selected: current == Some(rate), "{rate} per second" }
+        }
+    });
+    let multipliers = MULTIPLIERS.into_iter().map(|choice| {
+        rsx! {
+            button {
+                key: "{choice}",
+                disabled: choice == multiplier,
+                onclick: move |_| controls.dispatch(Command::SetTicksPerFrame(choice)),
+                "{choice}×"
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            label {
+                "Steps "
+                select {
+                    onchange: move |event| *steps_per_second.write() = StepsPerSecond(event.value.parse().ok()),
+                    option { value: "frame", selected: current.is_none(), "every frame" }
+                    rates
+                }
+            }
+            span { "Fast-forward" }
+            multipliers
+            // Other multipliers can be set by embedding options and by performance mode.
+            if !MULTIPLIERS.contains(&multiplier) {
+                rsx! { span { font_family: "monospace", "{multiplier}×" } }
+            }
+        }
+    }
+}
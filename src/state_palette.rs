@@ -0,0 +1,69 @@
+//! Painting cells of automata with more than two states, such as the conductors of Wireworld circuits.
+//!
+//! While such an automaton is chosen, clicking a cell with the toggle tool paints it in the chosen state,
+//! or empties it if it was already in that state.  The draw tool always paints in the chosen state.
+
+use dioxus::prelude::*;
+
+use crate::{game_of_life::universe::Universe, palette::Palette};
+
+/// The state cells are painted in, from 1 for the first state after the empty state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PaintState(pub u8);
+
+impl Default for PaintState {
+    fn default() -> Self {
+        PaintState(1)
+    }
+}
+
+impl PaintState {
+    /// Return the state painted in the universe: the chosen state, or the last state of the automaton if it has
+    /// fewer states.
+    pub fn state(self, universe: &Universe) -> u8 {
+        self.0.clamp(1, universe.automaton().states() - 1)
+    }
+
+    /// Return the state a cell in the given state is painted to: the paint state, or empty if the cell is in it.
+    pub fn painted(self, state: u8, universe: &Universe) -> u8 {
+        match self.state(universe) {
+            paint if paint == state => 0,
+            paint => paint,
+        }
+    }
+}
+
+/// A button for each state to paint cells in, colored as the cells are drawn.  Shown only for automata with
+/// more than two states.
+#[component]
+pub fn StatePalette(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let paint_state = use_shared_state::<PaintState>(cx).unwrap();
+    let palette = *use_shared_state::<Palette>(cx).unwrap().read();
+
+    let automaton = universe.read().automaton();
+    if !automaton.is_multi_state() {
+        return None;
+    }
+    let current = paint_state.read().state(&universe.read());
+
+    let buttons = automaton.state_names().iter().zip(1..).map(|(name, state)| {
+        let color = palette.states[(state as usize - 1) % palette.states.len()];
+        rsx! {
+            button {
+                key: "{state}",
+                disabled: state == current,
+                onclick: move |_| *paint_state.write() = PaintState(state),
+                span { display: "inline-block", width: "10px", height: "10px", margin_right: "4px", background: color }
+                "{name}"
+            }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "4px",
+            "Paint "
+            buttons
+        }
+    }
+}
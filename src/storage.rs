@@ -0,0 +1,62 @@
+//! Persistent key/value storage of text.
+//!
+//! On the web, values are kept in the browser's local storage.  On desktop, each value is a file in the
+//! user's data directory.
+
+#[cfg(feature = "web")]
+const KEY_PREFIX: &str = "game_of_life.";
+
+/// Return the browser's local storage, if it is available.
+#[cfg(feature = "web")]
+fn local_storage() -> Option<web_sys::Storage> {
+    crate::platform::window().local_storage().ok().flatten()
+}
+
+/// Return the value stored under the key.
+#[cfg(feature = "web")]
+pub fn load(key: &str) -> Option<String> {
+    local_storage()?
+        .get_item(&format!("{KEY_PREFIX}{key}"))
+        .ok()
+        .flatten()
+}
+
+/// Store the value under the key, replacing any previous value.
+#[cfg(feature = "web")]
+pub fn store(key: &str, value: &str) {
+    if let Some(storage) = local_storage() {
+        if storage
+            .set_item(&format!("{KEY_PREFIX}{key}"), value)
+            .is_err()
+        {
+            console_log!("Could not store {key}");
+        }
+    }
+}
+
+/// Return the directory that stored values are kept in.
+#[cfg(feature = "desktop")]
+fn storage_dir() -> Option<std::path::PathBuf> {
+    Some(dirs::data_dir()?.join("game_of_life"))
+}
+
+/// Return the value stored under the key.
+#[cfg(feature = "desktop")]
+pub fn load(key: &str) -> Option<String> {
+    std::fs::read_to_string(storage_dir()?.join(key)).ok()
+}
+
+/// Store the value under the key, replacing any previous value.
+#[cfg(feature = "desktop")]
+pub fn store(key: &str, value: &str) {
+    let Some(dir) = storage_dir() else {
+        println!("Could not store {key}: no data directory");
+        return;
+    };
+
+    if let Err(error) =
+        std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(dir.join(key), value))
+    {
+        println!("Could not store {key}: {error}");
+    }
+}
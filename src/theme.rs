@@ -0,0 +1,150 @@
+//! Themes: the size of cells and how the grid lines are drawn.
+//!
+//! The grid is laid out in CELL_SIZE units, and scaled up as a whole to draw larger cells, so grid lines
+//! are scaled with the cells.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game_of_life::{
+        CellRenderer, BIG_GRID_COLOR, BIG_GRID_STROKE, CELL_SIZE, SMALL_GRID_COLOR,
+        SMALL_GRID_STROKE,
+    },
+    palette::{Palette, HIGH_CONTRAST, STANDARD},
+    storage,
+};
+
+const APPEARANCE_KEY: &str = "appearance.json";
+
+/// The smallest sizes, in pixels, cells can be limited to.
+pub const MIN_CELL_SIZES: [u32; 4] = [12, 16, 24, 32];
+
+/// How the grid is drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    pub name: &'static str,
+    /// The width and height of a cell, in pixels.
+    pub cell_size: f64,
+    /// Widths of the grid lines, in CELL_SIZE units.
+    pub small_grid_stroke: f64,
+    pub big_grid_stroke: f64,
+    pub small_grid_color: &'static str,
+    pub big_grid_color: &'static str,
+    /// The palette chosen along with the theme.
+    pub palette: Palette,
+}
+
+pub const STANDARD_THEME: Theme = Theme {
+    name: "Standard",
+    cell_size: CELL_SIZE as f64,
+    small_grid_stroke: SMALL_GRID_STROKE,
+    big_grid_stroke: BIG_GRID_STROKE,
+    small_grid_color: SMALL_GRID_COLOR,
+    big_grid_color: BIG_GRID_COLOR,
+    palette: STANDARD,
+};
+
+/// For low vision: large cells, thick grid lines, and bold colors on black.
+pub const HIGH_CONTRAST_THEME: Theme = Theme {
+    name: "High contrast",
+    cell_size: (CELL_SIZE * 2) as f64,
+    small_grid_stroke: 1.0,
+    big_grid_stroke: 2.0,
+    small_grid_color: "#9e9e9e",
+    big_grid_color: "#ffffff",
+    palette: HIGH_CONTRAST,
+};
+
+pub const THEMES: [Theme; 2] = [STANDARD_THEME, HIGH_CONTRAST_THEME];
+
+impl Theme {
+    /// Return the theme with the given name.
+    pub fn find(name: &str) -> Option<Theme> {
+        THEMES.into_iter().find(|theme| theme.name == name)
+    }
+}
+
+/// The theme, and the smallest size cells may be drawn at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Appearance {
+    pub theme: Theme,
+    /// Cells are drawn at least this many pixels wide, whatever the theme.
+    pub min_cell_size: Option<u32>,
+    /// Show rulers numbering the rows and columns of the grid.
+    pub rulers: bool,
+    pub renderer: CellRenderer,
+    /// Color living cells by how many generations they have been alive, which makes still lifes and
+    /// oscillators stand out.
+    pub color_by_age: bool,
+    /// On desktop, the size of cells at which the grid fits the window, used instead of the size of the theme.
+    /// Not stored, since it follows the window.
+    pub fitted_cell_size: Option<f64>,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Appearance {
+            theme: STANDARD_THEME,
+            min_cell_size: None,
+            rulers: false,
+            renderer: CellRenderer::default(),
+            color_by_age: false,
+            fitted_cell_size: None,
+        }
+    }
+}
+
+/// The appearance as it is stored between sessions.
+#[derive(Serialize, Deserialize)]
+struct StoredAppearance {
+    theme: String,
+    min_cell_size: Option<u32>,
+    #[serde(default)]
+    rulers: bool,
+    #[serde(default)]
+    renderer: String,
+    #[serde(default)]
+    color_by_age: bool,
+}
+
+impl Appearance {
+    /// Return how much the grid is scaled up from CELL_SIZE cells.
+    pub fn scale(&self) -> f64 {
+        let min_cell_size = self.min_cell_size.unwrap_or(0) as f64;
+        let cell_size = self.fitted_cell_size.unwrap_or(self.theme.cell_size);
+        cell_size.max(min_cell_size) / CELL_SIZE as f64
+    }
+
+    /// Return the appearance chosen in an earlier session, or the default appearance.
+    pub fn stored() -> Appearance {
+        let Some(stored) = storage::load(APPEARANCE_KEY)
+            .and_then(|json| serde_json::from_str::<StoredAppearance>(&json).ok())
+        else {
+            return Appearance::default();
+        };
+
+        Appearance {
+            theme: Theme::find(&stored.theme).unwrap_or(STANDARD_THEME),
+            min_cell_size: stored.min_cell_size,
+            rulers: stored.rulers,
+            renderer: CellRenderer::find(&stored.renderer).unwrap_or_default(),
+            color_by_age: stored.color_by_age,
+            fitted_cell_size: None,
+        }
+    }
+
+    /// Remember the appearance for later sessions.
+    pub fn store(&self) {
+        let stored = StoredAppearance {
+            theme: self.theme.name.to_string(),
+            min_cell_size: self.min_cell_size,
+            rulers: self.rulers,
+            renderer: self.renderer.name().to_string(),
+            color_by_age: self.color_by_age,
+        };
+        storage::store(
+            APPEARANCE_KEY,
+            &serde_json::to_string(&stored).expect("appearance should serialize"),
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! The statistics of each generation of a recorded run, exported as CSV or JSON so runs can be analyzed
+//! and plotted elsewhere.
+//!
+//! The recording is replayed from its start without drawing, so the statistics cover the whole run however
+//! it was watched.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::{
+    game_of_life::{
+        census::Recognizer,
+        universe::{Cell, Universe},
+    },
+    replay::{Recording, Replay},
+};
+
+pub const CSV_FILE_NAME: &str = "game_of_life_statistics.csv";
+pub const JSON_FILE_NAME: &str = "game_of_life_statistics.json";
+
+/// The statistics of one generation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct GenerationStats {
+    pub generation: u64,
+    pub population: usize,
+    /// Cells alive that were dead in the previous row.
+    pub births: usize,
+    /// Cells dead that were alive in the previous row.
+    pub deaths: usize,
+    /// The number of each recognized object, by name.
+    pub objects: BTreeMap<&'static str, usize>,
+    /// The number of objects that were not recognized.
+    pub unrecognized: usize,
+}
+
+/// Replay the recording, and return the statistics of the universe it starts from and of each step of the
+/// replay after that.
+pub fn run_statistics(recording: &Recording) -> Vec<GenerationStats> {
+    let recognizer = Recognizer::new();
+    let mut universe = recording.initial_universe();
+    let mut replay = Replay::new(recording.clone());
+
+    let mut series = vec![stats(&recognizer, None, &universe)];
+    loop {
+        let previous = universe.clone();
+        if !replay.advance(&mut universe) {
+            break;
+        }
+        series.push(stats(&recognizer, Some(&previous), &universe));
+    }
+    series
+}
+
+fn stats(
+    recognizer: &Recognizer,
+    previous: Option<&Universe>,
+    universe: &Universe,
+) -> GenerationStats {
+    let (births, deaths) = match previous {
+        // Loading a universe of another size has nothing to compare with.
+        Some(previous) if previous.cells().len() == universe.cells().len() => {
+            previous.cells().zip(universe.cells()).fold(
+                (0, 0),
+                |(births, deaths), (before, after)| match (before, after) {
+                    (Cell::Dead, Cell::Alive) => (births + 1, deaths),
+                    (Cell::Alive, Cell::Dead) => (births, deaths + 1),
+                    _ => (births, deaths),
+                },
+            )
+        }
+        _ => (0, 0),
+    };
+    let census = recognizer.census(universe);
+
+    GenerationStats {
+        generation: universe.generation(),
+        population: universe.population(),
+        births,
+        deaths,
+        objects: census.objects,
+        unrecognized: census.unrecognized,
+    }
+}
+
+/// Return the statistics as CSV, with a column for each object recognized in any generation.
+pub fn to_csv(series: &[GenerationStats]) -> String {
+    let names: BTreeSet<&'static str> = series
+        .iter()
+        .flat_map(|stats| stats.objects.keys().copied())
+        .collect();
+
+    let mut csv = String::from("generation,population,births,deaths");
+    for name in names.iter() {
+        csv.push_str(&format!(",{}", name.to_lowercase().replace(' ', "_")));
+    }
+    csv.push_str(",unrecognized\n");
+
+    for stats in series {
+        csv.push_str(&format!(
+            "{},{},{},{}",
+            stats.generation, stats.population, stats.births, stats.deaths
+        ));
+        for name in names.iter() {
+            csv.push_str(&format!(",{}", stats.objects.get(name).unwrap_or(&0)));
+        }
+        csv.push_str(&format!(",{}\n", stats.unrecognized));
+    }
+    csv
+}
+
+/// Return the statistics as a JSON array with an object for each generation.
+pub fn to_json(series: &[GenerationStats]) -> String {
+    serde_json::to_string_pretty(series).expect("statistics should serialize")
+}
@@ -0,0 +1,132 @@
+//! A guided tour of famous patterns: each step loads a pattern, describes it, and runs it for a while.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command, Controls},
+    game_of_life::patterns::{Pattern, GLIDER, GOSPER_GLIDER_GUN, PUFFER_TRAIN},
+    game_of_life::universe::Universe,
+};
+
+/// One stop of the tour.
+struct TourStep {
+    title: &'static str,
+    narration: &'static str,
+    /// The pattern to load, if any.
+    pattern: Option<Pattern>,
+    /// (row, col) of the top left corner of the pattern.
+    position: (u32, u32),
+    /// The generation at which the pattern is stopped.
+    generations: u64,
+}
+
+const TOUR: &[TourStep] = &[
+    TourStep {
+        title: "Glider",
+        narration: "Five cells that rebuild themselves one cell further along the diagonal every four generations.  \
+            Gliders carry signals across the universe.",
+        pattern: Some(GLIDER),
+        position: (2, 2),
+        generations: 120,
+    },
+    TourStep {
+        title: "Gosper glider gun",
+        narration: "Bill Gosper found this gun in 1970, winning Conway's prize for a pattern that grows forever.  \
+            It fires a new glider every 30 generations.",
+        pattern: Some(GOSPER_GLIDER_GUN),
+        position: (2, 2),
+        generations: 150,
+    },
+    TourStep {
+        title: "Puffer train",
+        narration: "A puffer moves like a spaceship, but leaves debris behind.  Here two spaceships escort \
+            an engine that would burn out on its own.",
+        pattern: Some(PUFFER_TRAIN),
+        position: (24, 2),
+        generations: 100,
+    },
+    TourStep {
+        title: "Garden of Eden",
+        narration: "A Garden of Eden is a pattern with no parent: no pattern evolves into it, so it can only \
+            ever appear in generation 0.  Their existence proves that the rules cannot run backwards.",
+        pattern: None,
+        position: (0, 0),
+        generations: 0,
+    },
+];
+
+/// Load the pattern of a step and run it.
+fn start_step(step: &TourStep, controls: &Controls) {
+    match step.pattern {
+        Some(pattern) => {
+            let (row, col) = step.position;
+            controls.dispatch(Command::Load(pattern.cells_at(row, col)));
+            controls.dispatch(Command::Start);
+        }
+        None => {
+            controls.dispatch(Command::Stop);
+            controls.dispatch(Command::Clear);
+        }
+    }
+}
+
+/// Buttons to take, and move through, the tour, with the narration of the current step.
+#[component]
+pub fn TourControls(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    // The index of the current step, or None if not touring.
+    let step_index = use_state(cx, || None::<usize>);
+    // True while the current step is running its pattern.
+    let auto_running = use_state(cx, || false);
+
+    // Stop the pattern once it has run for the generations of its step.
+    let generation = universe.read().generation();
+    use_effect(cx, (&generation,), |(generation,)| {
+        to_owned![controls, step_index, auto_running];
+        async move {
+            if let Some(index) = *step_index.current() {
+                if *auto_running.current() && generation >= TOUR[index].generations {
+                    auto_running.set(false);
+                    controls.dispatch(Command::Stop);
+                }
+            }
+        }
+    });
+
+    let go_to_step = move |index: usize| {
+        step_index.set(Some(index));
+        auto_running.set(TOUR[index].pattern.is_some());
+        start_step(&TOUR[index], controls);
+    };
+
+    let Some(index) = *step_index.get() else {
+        return render! {
+            div { display: "flex", justify_content: "center",
+                button { onclick: move |_| go_to_step(0), "Tour famous patterns" }
+            }
+        };
+    };
+    let step = &TOUR[index];
+    let number = index + 1;
+    let total = TOUR.len();
+
+    render! {
+        div { display: "flex", flex_direction: "column", align_items: "center", gap: "4px",
+            b { "Tour {number}/{total}: {step.title}" }
+            div { max_width: "500px", text_align: "center", "{step.narration}" }
+            div { display: "flex", gap: "4px",
+                button { disabled: index == 0, onclick: move |_| go_to_step(index - 1), "Previous" }
+                button { onclick: move |_| go_to_step(index), "Replay" }
+                button { disabled: number == total, onclick: move |_| go_to_step(index + 1), "Next" }
+                button {
+                    onclick: move |_| {
+                        step_index.set(None);
+                        auto_running.set(false);
+                    },
+                    "End tour"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Importing patterns from the web, by the address of a LifeWiki page, a Catagolue object, or an RLE file.
+//!
+//! The RLE is fetched over HTTP: with fetch() on the web, and with reqwest on desktop.  Browsers only let
+//! the page read the response if the site allows it (CORS), so imports on the web can fail where they
+//! succeed on desktop.
+
+use dioxus::prelude::*;
+
+use crate::{
+    command::{use_controls, Command},
+    game_of_life::{centered, rle::read_rle, universe::Universe},
+};
+
+const LIFEWIKI_PAGE: &str = "conwaylife.com/wiki/";
+const LIFEWIKI_PATTERNS: &str = "https://conwaylife.com/patterns/";
+const CATAGOLUE_OBJECT: &str = "catagolue.hatsya.com/object/";
+const CATAGOLUE_RLE: &str = "https://catagolue.hatsya.com/rle/";
+
+/// Return the address of the RLE file for a pattern address.
+///
+/// LifeWiki pattern files are named after the page, lowercased without spaces or punctuation (the file
+/// of "Gosper glider gun" is gosperglidergun.rle).  Some pages name their file differently; the address
+/// of the file itself can be given instead.
+fn rle_url(url: &str) -> Result<String, String> {
+    let url = url.trim();
+    // Query strings and fragments don't name the pattern.
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+
+    if !path.starts_with("https://") && !path.starts_with("http://") {
+        return Err(format!("{url} is not a web address"));
+    }
+
+    if path.ends_with(".rle") {
+        Ok(path.to_string())
+    } else if let Some((_, page)) = path.split_once(LIFEWIKI_PAGE) {
+        let name: String = page
+            .trim_start_matches("Pattern:")
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        if name.is_empty() {
+            return Err(format!("{url} doesn't name a LifeWiki page"));
+        }
+        Ok(format!("{LIFEWIKI_PATTERNS}{name}.rle"))
+    } else if let Some((_, object)) = path.split_once(CATAGOLUE_OBJECT) {
+        match object.trim_end_matches('/').split_once('/') {
+            Some((apgcode, rule)) => Ok(format!("{CATAGOLUE_RLE}{rule}/{apgcode}")),
+            None => Err(format!("{url} doesn't name a Catagolue object and rule")),
+        }
+    } else {
+        Err(format!(
+            "{url} is not a LifeWiki page, a Catagolue object, or an RLE file"
+        ))
+    }
+}
+
+/// Fetch the text at the address.
+#[cfg(feature = "desktop")]
+async fn fetch_text(url: &str) -> Result<String, String> {
+    let response = reqwest::get(url)
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|error| format!("Could not fetch {url}: {error}"))?;
+
+    response
+        .text()
+        .await
+        .map_err(|error| format!("Could not read {url}: {error}"))
+}
+
+/// Fetch the text at the address.
+#[cfg(feature = "web")]
+async fn fetch_text(url: &str) -> Result<String, String> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    let response = JsFuture::from(crate::platform::window().fetch_with_str(url))
+        .await
+        // fetch() only rejects when there is no response to read, which is usually because the site
+        // doesn't allow other pages to read it.
+        .map_err(|_| {
+            format!("Could not fetch {url}: the site may not allow it from the browser (CORS). Try the desktop app, or the address of the RLE file.")
+        })?
+        .dyn_into::<web_sys::Response>()
+        .map_err(|_| format!("Could not fetch {url}"))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Could not fetch {url}: status {}",
+            response.status()
+        ));
+    }
+    let text = response
+        .text()
+        .map_err(|_| format!("Could not read {url}"))?;
+
+    JsFuture::from(text)
+        .await
+        .ok()
+        .and_then(|text| text.as_string())
+        .ok_or(format!("Could not read {url}"))
+}
+
+/// A field for the address of a pattern, and a button to import it onto the grid.
+#[component]
+pub fn UrlImport(cx: Scope) -> Element {
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let url = use_state(cx, String::new);
+    let status = use_state(cx, || None::<String>);
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            input {
+                r#type: "url",
+                size: 50,
+                placeholder: "https://conwaylife.com/wiki/Glider",
+                value: "{url}",
+                oninput: move |event| url.set(event.value.clone()),
+            }
+            button {
+                disabled: url.trim().is_empty(),
+                onclick: move |_| {
+                    to_owned![controls, universe, url, status];
+                    cx.spawn(async move {
+                        status.set(Some("Importing...".to_string()));
+                        let imported = match rle_url(url.get()) {
+                            Ok(rle_url) => fetch_text(&rle_url).await.and_then(|rle| {
+                                // The universe as it is when the pattern arrives.
+                                let universe = universe.read();
+                                read_rle(&rle, (universe.height(), universe.width()))
+                                    .map(|cells| centered(cells, &universe))
+                            }),
+                            Err(error) => Err(error),
+                        };
+                        match imported {
+                            Ok(cells) => {
+                                controls.dispatch(Command::Load(cells));
+                                status.set(None);
+                            }
+                            Err(error) => status.set(Some(error)),
+                        }
+                    });
+                },
+                "Import"
+            }
+            status.get().as_ref().map(|status| rsx! { span { "{status}" } })
+        }
+    }
+}
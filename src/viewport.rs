@@ -0,0 +1,312 @@
+//! The part of the universe shown on the grid: zooming in shows fewer, larger cells.
+//!
+//! The grid keeps its size on the page; zooming changes the view box of the grid, so the cells, grid
+//! lines, overlays, and rulers are all scaled together.  The mouse wheel zooms in and out around the cell
+//! under the mouse, and the pan tool drags the view around.
+//!
+//! An infinite universe grows as patterns reach its edges.  The grid then stays the same size on the page,
+//! with smaller cells, and zooms in to keep showing the cells it showed before, so the grid is a window onto
+//! the growing universe: zoom out or show all to see where the patterns went.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game_of_life::{
+        universe::{Boundary, BoundingBox, Universe},
+        CELL_SIZE,
+    },
+    theme::Appearance,
+};
+
+/// Cells left around the pattern when zooming to fit it.
+const FIT_MARGIN: u32 = 2;
+/// The most times larger cells are drawn by zooming in, unless the universe grew while zoomed in further.
+const MAX_ZOOM: f64 = 64.0;
+/// How many times larger cells are drawn after zooming in with the buttons or keys.
+pub const ZOOM_STEP: f64 = 2.0;
+/// How many times larger cells are drawn after zooming in one notch of the mouse wheel.
+pub const WHEEL_ZOOM_STEP: f64 = 1.25;
+
+/// The part of the universe shown on the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Viewport {
+    /// How many times larger cells are drawn than when the whole universe is shown.  At least 1.
+    pub zoom: f64,
+    /// The column at the left edge of the grid.
+    pub left: f64,
+    /// The row at the top edge of the grid.
+    pub top: f64,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Viewport {
+            zoom: 1.0,
+            left: 0.0,
+            top: 0.0,
+        }
+    }
+}
+
+impl Viewport {
+    /// Return the viewport that shows all living cells of the universe, with a margin, as large as they fit.
+    ///
+    /// An empty universe is shown whole.
+    pub fn fit(universe: &Universe) -> Viewport {
+        let Some(bounds) = universe.bounding_box() else {
+            return Viewport::default();
+        };
+        let (width, height) = (universe.width() as f64, universe.height() as f64);
+        let fit_width = (bounds.width() + FIT_MARGIN * 2) as f64;
+        let fit_height = (bounds.height() + FIT_MARGIN * 2) as f64;
+        let zoom = (width / fit_width).min(height / fit_height).max(1.0);
+
+        Viewport {
+            zoom,
+            ..Viewport::default()
+        }
+        .centered_on(box_center(bounds), universe)
+    }
+
+    /// Return a viewport read from a snapshot, zoomed in at least once and moved as little as needed to not
+    /// show past the edges of the universe, whatever the snapshot held.
+    pub fn restored(self, universe: &Universe) -> Viewport {
+        Viewport {
+            zoom: self.zoom.max(1.0),
+            ..self
+        }
+        .clamped(universe)
+    }
+
+    /// Return the viewport showing the same cells after the universe grew from the given origin, with cells
+    /// drawn the given times smaller so the grid keeps its size on the page.
+    fn after_growth(
+        self,
+        (origin_top, origin_left): (i64, i64),
+        shrink: f64,
+        universe: &Universe,
+    ) -> Viewport {
+        let (top, left) = universe.origin();
+
+        Viewport {
+            zoom: self.zoom * shrink,
+            left: self.left + (origin_left - left) as f64,
+            top: self.top + (origin_top - top) as f64,
+        }
+    }
+
+    /// Return the viewport at the same zoom, moved so the (row, col) point is in the middle of the grid, or
+    /// as near as it can be without showing past the edges of the universe.
+    pub fn centered_on(self, (row, col): (f64, f64), universe: &Universe) -> Viewport {
+        let (shown_width, shown_height) = self.shown(universe);
+
+        Viewport {
+            zoom: self.zoom,
+            left: col - shown_width / 2.0,
+            top: row - shown_height / 2.0,
+        }
+        .clamped(universe)
+    }
+
+    /// Return the (row, col) point in the middle of the grid.
+    pub fn center(&self, universe: &Universe) -> (f64, f64) {
+        let (shown_width, shown_height) = self.shown(universe);
+        (self.top + shown_height / 2.0, self.left + shown_width / 2.0)
+    }
+
+    /// Return the viewport with cells drawn the given times larger, keeping the (row, col) point at the same
+    /// place on the grid, as near as it can without showing past the edges of the universe.
+    pub fn zoomed(self, factor: f64, (row, col): (f64, f64), universe: &Universe) -> Viewport {
+        let zoom = (self.zoom * factor).clamp(1.0, MAX_ZOOM.max(self.zoom));
+        let shrink = self.zoom / zoom;
+
+        Viewport {
+            zoom,
+            left: col - (col - self.left) * shrink,
+            top: row - (row - self.top) * shrink,
+        }
+        .clamped(universe)
+    }
+
+    /// Return the viewport moved by the given (rows, cols), stopping at the edges of the universe.
+    pub fn panned(self, (rows, cols): (f64, f64), universe: &Universe) -> Viewport {
+        Viewport {
+            left: self.left + cols,
+            top: self.top + rows,
+            ..self
+        }
+        .clamped(universe)
+    }
+
+    /// Return the (width, height) in cells of the part of the universe shown.
+    fn shown(&self, universe: &Universe) -> (f64, f64) {
+        (
+            universe.width() as f64 / self.zoom,
+            universe.height() as f64 / self.zoom,
+        )
+    }
+
+    /// Return the viewport moved as little as needed to not show past the edges of the universe.
+    fn clamped(self, universe: &Universe) -> Viewport {
+        let (shown_width, shown_height) = self.shown(universe);
+
+        Viewport {
+            zoom: self.zoom,
+            left: self.left.clamp(0.0, universe.width() as f64 - shown_width),
+            top: self.top.clamp(0.0, universe.height() as f64 - shown_height),
+        }
+    }
+}
+
+/// What the viewport follows as the universe advances, so moving patterns stay in view.  The whole
+/// universe is always in view, so following only moves the viewport when zoomed in.
+///
+/// On a torus, a pattern that wraps around an edge is split between both sides, and the viewport
+/// follows the middle of the pieces until it has wrapped all the way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Follow {
+    Off,
+    /// The average position of the living cells.  Debris left behind pulls on it less than on the box.
+    Centroid,
+    /// The middle of the box holding all living cells.
+    BoundingBox,
+}
+
+impl Follow {
+    const ALL: [Follow; 3] = [Follow::Off, Follow::Centroid, Follow::BoundingBox];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Follow::Off => "off",
+            Follow::Centroid => "centroid",
+            Follow::BoundingBox => "bounding box",
+        }
+    }
+
+    /// Return the (row, col) point followed in the universe, or None if there is nothing to follow.
+    fn center(&self, universe: &Universe) -> Option<(f64, f64)> {
+        match self {
+            Follow::Off => None,
+            Follow::Centroid => {
+                let cells = universe.get_living_cells();
+                if cells.is_empty() {
+                    return None;
+                }
+                let count = cells.len() as f64;
+                let (cols, rows) = cells.iter().fold((0.0, 0.0), |(cols, rows), (x, y)| {
+                    (cols + *x as f64, rows + *y as f64)
+                });
+                // The middle of the cell, not its top left corner.
+                Some((rows / count + 0.5, cols / count + 0.5))
+            }
+            Follow::BoundingBox => universe.bounding_box().map(box_center),
+        }
+    }
+}
+
+/// Return the (row, col) point in the middle of the box.
+fn box_center(bounds: BoundingBox) -> (f64, f64) {
+    (
+        bounds.top as f64 + bounds.height() as f64 / 2.0,
+        bounds.left as f64 + bounds.width() as f64 / 2.0,
+    )
+}
+
+/// Buttons to zoom the grid in and out, to fit the pattern, and to show the whole universe again, and the
+/// choice of what the viewport follows.
+///
+/// When the grid has the focus, + and - zoom in and out and F zooms to fit.
+#[component]
+pub fn ZoomControls(cx: Scope) -> Element {
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let viewport = use_shared_state::<Viewport>(cx).unwrap();
+    let appearance = use_shared_state::<Appearance>(cx).unwrap();
+    let follow = use_state(cx, || Follow::Off);
+    // The (width, height) and origin of the universe when last drawn, to notice it grow.
+    let last_extent = use_ref(cx, || (0, 0, (0, 0)));
+
+    // Keep showing the same cells, at the same size on the page, when an infinite universe grows.
+    let extent = {
+        let universe = universe.read();
+        (universe.width(), universe.height(), universe.origin())
+    };
+    use_effect(cx, (&extent,), |(extent,)| {
+        to_owned![viewport, appearance, last_extent, universe];
+        async move {
+            let (width, height, origin) =
+                std::mem::replace(&mut *last_extent.write_silent(), extent);
+            // A universe resized to be larger starts again at generation 0, and is shown whole.
+            let grew = universe.read().boundary() == Boundary::Infinite
+                && universe.read().generation() > 0
+                && extent.0 >= width
+                && extent.1 >= height
+                && (extent.0, extent.1) != (width, height);
+            if !grew || width == 0 || height == 0 {
+                return;
+            }
+            let shrink = (extent.0 as f64 / width as f64).max(extent.1 as f64 / height as f64);
+            let cell_size = appearance.read().scale() * CELL_SIZE as f64;
+            appearance.write().fitted_cell_size = Some(cell_size / shrink);
+            let grown = viewport
+                .read()
+                .after_growth(origin, shrink, &universe.read());
+            *viewport.write() = grown;
+        }
+    });
+
+    // Recenter on what is followed each generation.
+    let generation = universe.read().generation();
+    use_effect(cx, (&generation, follow.get()), |(_, follow)| {
+        to_owned![universe, viewport];
+        async move {
+            let center = follow.center(&universe.read());
+            if let Some(center) = center {
+                let followed = viewport.read().centered_on(center, &universe.read());
+                if followed != *viewport.read() {
+                    *viewport.write() = followed;
+                }
+            }
+        }
+    });
+
+    let zoomed = *viewport.read() != Viewport::default();
+    let zoom_by = move |factor: f64| {
+        let universe = universe.read();
+        let view = *viewport.read();
+        *viewport.write() = view.zoomed(factor, view.center(&universe), &universe);
+    };
+    let follows = Follow::ALL.into_iter().map(|choice| {
+        rsx! {
+            option { key: "{choice.label()}", value: "{choice.label()}", selected: choice == *follow.get(), "{choice.label()}" }
+        }
+    });
+
+    render! {
+        div { display: "flex", justify_content: "center", align_items: "center", gap: "8px",
+            button { title: "+", onclick: move |_| zoom_by(ZOOM_STEP), "Zoom in" }
+            button { title: "-", disabled: !zoomed, onclick: move |_| zoom_by(1.0 / ZOOM_STEP), "Zoom out" }
+            button {
+                title: "F",
+                onclick: move |_| *viewport.write() = Viewport::fit(&universe.read()),
+                "Zoom to fit"
+            }
+            button {
+                disabled: !zoomed,
+                onclick: move |_| *viewport.write() = Viewport::default(),
+                "Show all"
+            }
+            label {
+                "Follow "
+                select {
+                    onchange: move |event| {
+                        if let Some(choice) = Follow::ALL.into_iter().find(|choice| choice.label() == event.value) {
+                            follow.set(choice);
+                        }
+                    },
+                    follows
+                }
+            }
+        }
+    }
+}
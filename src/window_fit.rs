@@ -0,0 +1,90 @@
+//! Sizing the grid to the desktop window, so that resizing the window resizes the grid.
+//!
+//! Cells stay square: the grid is scaled to fit the narrower of the width and height of the window, and
+//! centered, leaving room below it for the buttons.  The other controls follow below the grid.
+
+use dioxus::prelude::*;
+use dioxus_desktop::{
+    tao::event::{Event, WindowEvent},
+    use_window, use_wry_event_handler, DesktopContext,
+};
+
+use crate::{
+    game_of_life::{cells_size, universe::Universe, CELL_SIZE},
+    ruler::RULER_SIZE,
+    theme::Appearance,
+};
+
+/// Pixels left at the sides of the grid, for the margins of the page and a scroll bar.
+const SIDE_MARGIN: f64 = 40.0;
+/// Pixels left above and below the grid, for the title and the buttons right under the grid.
+const RESERVED_HEIGHT: f64 = 140.0;
+/// Cells are never drawn smaller than this, in pixels, however small the window.
+const SMALLEST_CELL_SIZE: f64 = 1.0;
+
+/// Return the (width, height) of the inside of the window, in the pixels the page is laid out in.
+fn inner_size(window: &DesktopContext) -> (f64, f64) {
+    let size = window.inner_size().to_logical::<f64>(window.scale_factor());
+    (size.width, size.height)
+}
+
+/// Return the size of cells, in pixels, at which the grid of the universe fits in a window of the given size.
+fn fitted_cell_size(
+    (window_width, window_height): (f64, f64),
+    universe: &Universe,
+    appearance: &Appearance,
+) -> f64 {
+    let rulers = if appearance.rulers { RULER_SIZE } else { 0.0 };
+    let (cells_width, cells_height) = cells_size(universe);
+    let big_grid_stroke = appearance.theme.big_grid_stroke;
+    let (grid_width, grid_height) = (
+        cells_width + big_grid_stroke,
+        cells_height + big_grid_stroke,
+    );
+
+    let scale = ((window_width - SIDE_MARGIN - rulers) / grid_width)
+        .min((window_height - RESERVED_HEIGHT - rulers) / grid_height);
+    (scale * CELL_SIZE as f64).max(SMALLEST_CELL_SIZE)
+}
+
+/// Fit the grid to the window whenever the window is resized, or the universe changes size.
+///
+/// Must be called after the Universe and Appearance are provided.
+pub fn use_window_fit(cx: &ScopeState) {
+    let window = use_window(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    let appearance = use_shared_state::<Appearance>(cx).unwrap();
+    let window_size = use_state(cx, || inner_size(window));
+
+    use_wry_event_handler(cx, {
+        to_owned![window, window_size];
+        move |event, _| {
+            if let Event::WindowEvent {
+                event: WindowEvent::Resized(_),
+                ..
+            } = event
+            {
+                window_size.set(inner_size(&window));
+            }
+        }
+    });
+
+    let universe_size = (universe.read().width(), universe.read().height());
+    let (rulers, big_grid_stroke) = {
+        let appearance = appearance.read();
+        (appearance.rulers, appearance.theme.big_grid_stroke)
+    };
+    use_effect(
+        cx,
+        (window_size.get(), &universe_size, &rulers, &big_grid_stroke),
+        |(window_size, _, _, _)| {
+            to_owned![universe, appearance];
+            async move {
+                let fitted = fitted_cell_size(window_size, &universe.read(), &appearance.read());
+                if appearance.read().fitted_cell_size != Some(fitted) {
+                    appearance.write().fitted_cell_size = Some(fitted);
+                }
+            }
+        },
+    );
+}
@@ -0,0 +1,82 @@
+//! Remembering the size and position of the desktop window between sessions.
+
+use dioxus::prelude::*;
+use dioxus_desktop::{
+    tao::{
+        dpi::{LogicalPosition, LogicalSize},
+        event::{Event, WindowEvent},
+    },
+    use_window, use_wry_event_handler, WindowBuilder,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const GEOMETRY_KEY: &str = "window.json";
+
+/// The size of the inside of the window and the position of its top left corner, in logical pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub width: f64,
+    pub height: f64,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl WindowGeometry {
+    /// Return the geometry of the window when the last session ended, if any.
+    pub fn stored() -> Option<WindowGeometry> {
+        let geometry: WindowGeometry = serde_json::from_str(&storage::load(GEOMETRY_KEY)?).ok()?;
+        (geometry.width > 0.0 && geometry.height > 0.0).then_some(geometry)
+    }
+
+    /// Remember the geometry for the next session.
+    pub fn store(&self) {
+        storage::store(
+            GEOMETRY_KEY,
+            &serde_json::to_string(self).expect("window geometry should serialize"),
+        );
+    }
+
+    /// Return the builder sized and placed to the geometry.
+    pub fn apply(&self, builder: WindowBuilder) -> WindowBuilder {
+        builder
+            .with_inner_size(LogicalSize::new(self.width, self.height))
+            .with_position(LogicalPosition::new(self.x, self.y))
+    }
+}
+
+/// Store the geometry of the window when it is closed, so the next session opens the window the same way.
+pub fn use_window_geometry(cx: &ScopeState) {
+    let window = use_window(cx);
+
+    use_wry_event_handler(cx, {
+        to_owned![window];
+        move |event, _| {
+            if let Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                ..
+            } = event
+            {
+                // Fullscreen is not a geometry to open a window with.
+                if window.webview.window().fullscreen().is_some() {
+                    return;
+                }
+                let scale_factor = window.scale_factor();
+                let size = window.inner_size().to_logical::<f64>(scale_factor);
+                let Ok(position) = window.outer_position() else {
+                    return;
+                };
+                let position = position.to_logical::<f64>(scale_factor);
+
+                WindowGeometry {
+                    width: size.width,
+                    height: size.height,
+                    x: position.x,
+                    y: position.y,
+                }
+                .store();
+            }
+        }
+    });
+}
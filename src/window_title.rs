@@ -0,0 +1,60 @@
+//! The desktop window title shows the status of the universe, so it can be seen even when the window is
+//! small or only in the taskbar.
+
+use dioxus::prelude::*;
+use dioxus_desktop::use_window;
+
+use crate::{command::use_controls, game_of_life::universe::Universe};
+
+/// The title before anything is known of the universe.
+pub const TITLE: &str = "Game of Life";
+
+/// Keeps the window title up to date, such as "Game of Life — gen 1523 — 312 alive — 60fps — running".
+///
+/// frame_time is the time of the latest animation frame from use_animation_frame().  Renders nothing.
+#[component]
+pub fn WindowTitle(cx: Scope, frame_time: f64) -> Element {
+    let window = use_window(cx);
+    let controls = use_controls(cx);
+    let universe = use_shared_state::<Universe>(cx).unwrap();
+    // The time of the last frame, and the frames per second measured at it.
+    let timing = use_ref(cx, || (None::<f64>, 0_f64));
+    let shown_title = use_ref(cx, || TITLE.to_string());
+
+    use_effect(cx, (frame_time,), |(frame_time,)| {
+        to_owned![timing];
+        async move {
+            timing.with_mut(|(last_time, fps)| {
+                if let Some(last_time) = last_time {
+                    if frame_time > *last_time {
+                        *fps = 1000_f64 / (frame_time - *last_time);
+                    }
+                }
+                *last_time = Some(frame_time);
+            });
+        }
+    });
+
+    let (generation, alive) = {
+        let universe = universe.read();
+        (universe.generation(), universe.population())
+    };
+    let status = if controls.is_running() {
+        "running"
+    } else {
+        "paused"
+    };
+    let fps = if controls.is_running() {
+        timing.read().1.round()
+    } else {
+        0_f64
+    };
+    let title = format!("{TITLE} — gen {generation} — {alive} alive — {fps}fps — {status}");
+
+    if *shown_title.read() != title {
+        window.set_title(&title);
+        *shown_title.write_silent() = title;
+    }
+
+    None
+}